@@ -37,6 +37,22 @@ pub enum SourError {
     #[msg("Handshake deadline has not yet passed")]
     DeadlineNotReached,
 
+    #[msg("Review window after delivery has not yet passed")]
+    ReviewWindowOpen,
+
+    // Milestone errors
+    #[msg("Too many milestones (max 8)")]
+    TooManyMilestones,
+
+    #[msg("Milestone amounts must be non-zero and sum to the escrow amount")]
+    MilestoneAmountMismatch,
+
+    #[msg("Milestone deadlines must be ascending and within the handshake deadline")]
+    InvalidMilestoneDeadline,
+
+    #[msg("Milestones account is missing or does not match this handshake")]
+    MilestonesMismatch,
+
     // Fee / config errors
     #[msg("Fee shares must sum to 10000 basis points")]
     InvalidFeeShares,
@@ -44,17 +60,235 @@ pub enum SourError {
     #[msg("Pinch fee basis points must be between 0 and 5000 (50%)")]
     InvalidPinchBps,
 
+    #[msg("Crank tip basis points must be between 0 and 100 (1%)")]
+    InvalidCrankTip,
+
+    #[msg("Review window must be between 1 second and 90 days")]
+    InvalidReviewWindow,
+
+    #[msg("Config account is not a pre-migration config")]
+    ConfigNotMigratable,
+
+    #[msg("This mint is not enabled for escrow")]
+    MintNotAllowed,
+
+    #[msg("Allowed-mint entry is missing or does not match the escrow mint")]
+    AllowedMintMismatch,
+
+    #[msg("Fee destination does not match the configured token account")]
+    InvalidFeeDestination,
+
     #[msg("Insufficient escrow balance for transfer")]
     InsufficientEscrow,
 
     // Dispute errors
-    #[msg("Invalid dispute ruling — must be 0 (refund) or 1 (pay worker)")]
+    #[msg("Invalid dispute ruling — worker share must be at most 10000 bps")]
     InvalidRuling,
 
+    #[msg("Only the handshake's arbiter or the protocol authority can resolve this dispute")]
+    NotResolver,
+
+    #[msg("Arbiter must be a third party and charge at most 1000 bps (10%)")]
+    InvalidArbiter,
+
+    #[msg("A dispute bond is required — pass the token account to post it from")]
+    BondRequired,
+
+    #[msg("There is no dispute bond to match, or it has already been matched")]
+    BondNotMatchable,
+
+    // Evidence errors
+    #[msg("Evidence submissions are closed for this dispute")]
+    EvidenceClosed,
+
+    #[msg("Evidence limit reached for this participant (max 4)")]
+    EvidenceLimitReached,
+
+    #[msg("Evidence URI is too long (max 200 characters)")]
+    EvidenceUriTooLong,
+
+    // Arbitration panel errors
+    #[msg("Panel size must be odd and at most 7, voting window at most 90 days")]
+    InvalidPanelConfig,
+
+    #[msg("Arbiter registry is full")]
+    RegistryFull,
+
+    #[msg("Arbiter is already registered")]
+    ArbiterAlreadyRegistered,
+
+    #[msg("Arbiter is not registered")]
+    ArbiterNotRegistered,
+
+    #[msg("Not enough eligible arbiters to draw a panel")]
+    PanelUnavailable,
+
+    #[msg("The parties agreed on an arbiter for this handshake; it settles the dispute")]
+    ArbiterAgreed,
+
+    #[msg("The slot this panel is drawn from has not been reached yet")]
+    DrawSlotPending,
+
+    #[msg("The panel for this dispute case has already been drawn")]
+    PanelAlreadyDrawn,
+
+    #[msg("The panel for this dispute case has not been drawn yet")]
+    PanelNotDrawn,
+
+    #[msg("A panel case is open for this dispute; only the panel can settle it unless it stalls")]
+    DisputeCaseOpen,
+
+    #[msg("Only a member of this dispute panel can vote")]
+    NotPanelist,
+
+    #[msg("This panel member has already voted")]
+    AlreadyVoted,
+
+    #[msg("The voting window for this dispute has closed")]
+    VotingClosed,
+
+    #[msg("The voting window is still open and not every panel member has voted")]
+    VotingOpen,
+
+    #[msg("Not enough panel votes to finalize this dispute")]
+    QuorumNotReached,
+
+    #[msg("This dispute case has already been finalized")]
+    CaseFinalized,
+
+    // Open bounty errors
+    #[msg("Bounty terms require an open handshake and a matching allowlist account")]
+    InvalidBountyTerms,
+
+    #[msg("Worker has not completed enough handshakes for this bounty")]
+    InsufficientTrackRecord,
+
+    #[msg("Worker is not on this bounty's allowlist")]
+    NotAllowlisted,
+
+    #[msg("This bounty hires through applications; use apply instead")]
+    ApplicationRequired,
+
+    #[msg("Application amount or deadline is invalid for this handshake")]
+    InvalidApplication,
+
+    #[msg("The application no longer matches the bid being selected")]
+    BidMismatch,
+
+    // Worker collateral errors
+    #[msg("Collateral vault must be passed exactly when the handshake holds or requires collateral")]
+    CollateralVaultMismatch,
+
+    #[msg("The worker's token account is required to post collateral")]
+    CollateralAccountRequired,
+
+    #[msg("Commons slice of forfeited collateral exceeds maximum (5000 bps)")]
+    InvalidCollateralSlice,
+
+    // Amendment errors
+    #[msg("Amendment must change the deadline or amount, to a valid value")]
+    InvalidAmendment,
+
+    #[msg("Only the other party can accept this amendment")]
+    NotCounterparty,
+
+    #[msg("The creator's token account is required to fund this amendment")]
+    TopUpAccountRequired,
+
+    // Mutual cancel errors
+    #[msg("Worker share must be at most 10000 bps")]
+    InvalidCancelSplit,
+
+    // Revision errors
+    #[msg("Revision limit must not exceed 10")]
+    InvalidRevisionLimit,
+
+    #[msg("No revisions left — reject the delivery through dispute")]
+    RevisionsExhausted,
+
+    #[msg("Review window after delivery has passed")]
+    ReviewWindowClosed,
+
+    #[msg("Revision deadline must be later than the current one and leave at least a day to rework")]
+    InvalidRevisionDeadline,
+
+    // Delivery errors
+    #[msg("Delivery URI is too long (max 200 characters)")]
+    DeliveryUriTooLong,
+
+    // Payout stream errors
+    #[msg("Vesting needs a period of up to 4 years with the cliff inside it, and no milestones")]
+    InvalidVestingTerms,
+
+    #[msg("Payout stream accounts must be passed exactly when the payout vests")]
+    PayoutStreamMismatch,
+
+    #[msg("Nothing has vested since the last withdrawal")]
+    NothingVested,
+
+    // Recurring errors
+    #[msg("Recurring terms need a positive amount, a period of up to a year and 1-120 periods")]
+    InvalidRecurringTerms,
+
+    #[msg("Funding would exceed the retainer's remaining periods")]
+    TooManyPeriods,
+
+    #[msg("The current period has not been funded")]
+    PeriodNotFunded,
+
+    #[msg("Delivery is only possible while the current period is running")]
+    PeriodNotRunning,
+
+    #[msg("The current period cannot be settled yet")]
+    PeriodNotSettleable,
+
+    // Bonus errors
+    #[msg("Bonus Pinch rate must not exceed 10%")]
+    InvalidBonusPinch,
+
+    #[msg("The creator's token account is required to pay a bonus")]
+    BonusAccountRequired,
+
+    // Invoice errors
+    #[msg("Invoice deadline has passed — it can no longer be funded")]
+    InvoiceExpired,
+
+    #[msg("Only the worker can revoke an invoice before its deadline")]
+    InvoiceNotRevocable,
+
+    // Team payout errors
+    #[msg("Team payees need a named worker, 1-8 distinct wallets and shares summing to 100%")]
+    InvalidTeam,
+
+    #[msg("Team payees account must be passed exactly for team handshakes")]
+    TeamMismatch,
+
+    #[msg("Payee token accounts must lead the remaining accounts, in payee order")]
+    PayeeAccountMismatch,
+
+    // Subcontract errors
+    #[msg("Subcontract needs a named subcontractor, a positive amount within the worker's payout and a deadline before the parent's, on a single-worker parent without milestones")]
+    InvalidSubcontract,
+
+    #[msg("This handshake has already been subcontracted")]
+    SubcontractExists,
+
+    #[msg("Subcontract accounts must be passed exactly while a lien is outstanding")]
+    SubcontractMismatch,
+
+    #[msg("The parent handshake has not paid this subcontract's lien yet")]
+    LienUnpaid,
+
+    #[msg("The worker's share no longer covers the subcontract lien and its fees")]
+    LienUncovered,
+
     // Authority errors
     #[msg("Only the protocol authority can perform this action")]
     NotAuthority,
 
+    #[msg("Only the proposed authority can accept the handover")]
+    NotPendingAuthority,
+
     // Math errors
     #[msg("Arithmetic overflow in fee calculation")]
     MathOverflow,
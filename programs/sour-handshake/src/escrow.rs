@@ -0,0 +1,290 @@
+// ============================================================================
+// Escrow — token movements in and out of handshake vaults
+//
+// Every transfer goes through `transfer_checked` so both the classic Token
+// program and Token-2022 mints work. Transfer-hook mints need their extra
+// accounts, which callers forward from `ctx.remaining_accounts`.
+//
+// Team handshakes prepend one token account per payee (in payee order) to
+// the remaining accounts; `TeamPayout::split` peels them off first.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::{Account as AccountState, Mint as MintState};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::TeamPayees;
+use crate::errors::SourError;
+use crate::pinch::{bps_of, PinchSplit};
+
+/// Live balance of a token account, including transfers made earlier in
+/// this instruction
+pub fn token_balance(account: &AccountInfo) -> Result<u64> {
+    Ok(TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?.amount)
+}
+
+/// Size of a token account for `mint`, including the extensions a
+/// Token-2022 mint requires of its accounts (as `token::mint` init does)
+pub fn token_account_space(mint: &AccountInfo) -> Result<usize> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(anchor_spl::token::TokenAccount::LEN);
+    }
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+    let required = ExtensionType::get_required_init_account_extensions(
+        &mint_state.get_extension_types()?,
+    );
+    Ok(ExtensionType::try_calculate_account_len::<AccountState>(&required)?)
+}
+
+/// Token program + mint context shared by every transfer of one escrow
+pub struct MintTransfer<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub decimals: u8,
+    /// Transfer-hook extra accounts (empty for mints without a hook)
+    pub hook_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> MintTransfer<'a, 'info> {
+    pub fn new(
+        token_program: &Interface<'info, TokenInterface>,
+        mint: &InterfaceAccount<'info, Mint>,
+        hook_accounts: &'a [AccountInfo<'info>],
+    ) -> Self {
+        Self {
+            token_program: token_program.to_account_info(),
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+            hook_accounts,
+        }
+    }
+
+    /// `transfer_checked` of `amount` from `from` to `to`. Zero amounts are skipped.
+    pub fn transfer(
+        &self,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        invoke_transfer_checked(
+            self.token_program.key,
+            from.clone(),
+            self.mint.clone(),
+            to.clone(),
+            authority.clone(),
+            self.hook_accounts,
+            amount,
+            self.decimals,
+            signer_seeds,
+        )
+        .map_err(Into::into)
+    }
+
+    /// Let `delegate` pull up to `amount` from `from` later, on the
+    /// owner's (`authority`'s) say-so.
+    pub fn approve(
+        &self,
+        from: &AccountInfo<'info>,
+        delegate: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        token_interface::approve(
+            CpiContext::new(
+                self.token_program.clone(),
+                token_interface::Approve {
+                    to: from.clone(),
+                    delegate: delegate.clone(),
+                    authority: authority.clone(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Withdraw any delegation on `from`, on the owner's (`authority`'s)
+    /// say-so.
+    pub fn revoke(&self, from: &AccountInfo<'info>, authority: &AccountInfo<'info>) -> Result<()> {
+        token_interface::revoke(CpiContext::new(
+            self.token_program.clone(),
+            token_interface::Revoke {
+                source: from.clone(),
+                authority: authority.clone(),
+            },
+        ))
+    }
+}
+
+/// A team handshake's payees with their token accounts
+pub struct TeamPayout<'a, 'info> {
+    pub team: &'a TeamPayees,
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> TeamPayout<'a, 'info> {
+    /// Split `remaining_accounts` into the payee token accounts (first, one
+    /// per payee) and the transfer-hook accounts after them. Without a team
+    /// every remaining account is a hook account.
+    pub fn split(
+        team: Option<&'a TeamPayees>,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Result<(Option<Self>, &'a [AccountInfo<'info>])> {
+        match team {
+            Some(team) => {
+                require!(
+                    remaining_accounts.len() >= team.payees.len(),
+                    SourError::PayeeAccountMismatch
+                );
+                let (accounts, hook_accounts) = remaining_accounts.split_at(team.payees.len());
+                Ok((Some(Self { team, accounts }), hook_accounts))
+            }
+            None => Ok((None, remaining_accounts)),
+        }
+    }
+}
+
+/// Accounts involved in paying out of a handshake vault
+pub struct VaultPayout<'a, 'info> {
+    pub token: MintTransfer<'a, 'info>,
+    pub vault: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl<'a, 'info> VaultPayout<'a, 'info> {
+    /// Move `amount` from the vault to `to`. Zero amounts are skipped.
+    pub fn transfer(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.transfer_from(&self.vault, to, amount)
+    }
+
+    /// Move `amount` out of another vault held by the same PDA authority
+    /// (e.g. the bond vault). Zero amounts are skipped.
+    pub fn transfer_from(
+        &self,
+        vault: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        self.token.transfer(
+            vault,
+            to,
+            &self.vault_authority,
+            self.signer_seeds,
+            amount,
+        )
+    }
+
+    /// Forfeit worker collateral held in `collateral_vault`: `commons_bps`
+    /// of it to the Commons treasury, the rest to the creator. Returns the
+    /// Commons slice.
+    pub fn forfeit_collateral(
+        &self,
+        collateral_vault: &AccountInfo<'info>,
+        amount: u64,
+        creator: &AccountInfo<'info>,
+        commons_treasury: Option<&AccountInfo<'info>>,
+        commons_bps: u16,
+    ) -> Result<u64> {
+        let to_commons = bps_of(amount, commons_bps)?;
+        if to_commons > 0 {
+            let commons_treasury = commons_treasury.ok_or(SourError::InvalidFeeDestination)?;
+            self.transfer_from(collateral_vault, commons_treasury, to_commons)?;
+        }
+        self.transfer_from(collateral_vault, creator, amount - to_commons)?;
+        Ok(to_commons)
+    }
+
+    /// Pay a subcontract's `lien` out of the worker's share of `split` into
+    /// its vault. Fails rather than underfund it when that share falls short.
+    pub fn pay_lien(
+        &self,
+        split: &mut PinchSplit,
+        lien: u64,
+        subcontract_vault: &AccountInfo<'info>,
+    ) -> Result<u64> {
+        require!(split.to_worker >= lien, SourError::LienUncovered);
+        self.transfer(subcontract_vault, lien)?;
+        split.to_worker -= lien;
+        Ok(lien)
+    }
+
+    /// Pay the worker's `amount`: straight to `worker`, or across `team` —
+    /// each payee its bps, the rounding remainder to `worker` (the lead).
+    pub fn pay_worker(
+        &self,
+        worker: &AccountInfo<'info>,
+        team: Option<&TeamPayout<'_, 'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let mut remainder = amount;
+        if let Some(team) = team {
+            for (payee, account) in team.team.payees.iter().zip(team.accounts) {
+                require!(
+                    account.owner == self.token.token_program.key,
+                    SourError::PayeeAccountMismatch
+                );
+                let token_account =
+                    TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+                require!(
+                    token_account.mint == self.token.mint.key()
+                        && token_account.owner == payee.wallet,
+                    SourError::PayeeAccountMismatch
+                );
+                let share = bps_of(amount, payee.share_bps)?;
+                self.transfer(account, share)?;
+                remainder = remainder
+                    .checked_sub(share)
+                    .ok_or(SourError::MathOverflow)?;
+            }
+        }
+        self.transfer(worker, remainder)
+    }
+
+    /// Pay the worker (or team) and the three Pinch pools according to
+    /// `split`.
+    pub fn pay_split(
+        &self,
+        split: &PinchSplit,
+        worker: &AccountInfo<'info>,
+        team: Option<&TeamPayout<'_, 'info>>,
+        buyback_treasury: &AccountInfo<'info>,
+        keepers_pool: &AccountInfo<'info>,
+        commons_treasury: &AccountInfo<'info>,
+    ) -> Result<()> {
+        self.pay_worker(worker, team, split.to_worker)?;
+        self.transfer(buyback_treasury, split.to_treasury)?;
+        self.transfer(keepers_pool, split.to_keepers)?;
+        self.transfer(commons_treasury, split.to_commons)
+    }
+
+    /// Close another vault held by the same PDA authority once a handshake
+    /// is over: anything still in it goes to `sweep_to` (so stray deposits
+    /// cannot block the close), the rent to `rent_to`.
+    pub fn close_vault(
+        &self,
+        vault: &AccountInfo<'info>,
+        sweep_to: &AccountInfo<'info>,
+        rent_to: &AccountInfo<'info>,
+    ) -> Result<()> {
+        self.transfer_from(vault, sweep_to, token_balance(vault)?)?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token.token_program.clone(),
+            token_interface::CloseAccount {
+                account: vault.clone(),
+                destination: rent_to.clone(),
+                authority: self.vault_authority.clone(),
+            },
+            self.signer_seeds,
+        ))
+    }
+}
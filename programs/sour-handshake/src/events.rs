@@ -9,15 +9,28 @@ pub struct HandshakeCreated {
     pub handshake_id: u64,
     pub creator: Pubkey,
     pub worker: Pubkey,
+    pub mint: Pubkey,
     pub amount: u64,
     pub deadline_ts: i64,
     pub description: String,
+    pub milestone_count: u8,
+    pub arbiter: Pubkey,
+    pub arbiter_fee_bps: u16,
+    pub min_completed: u32,
+    pub allowlisted: bool,
+    pub by_application: bool,
+    pub worker_collateral: u64,
+    pub max_revisions: u8,
+    pub vesting_duration_secs: i64,
+    pub vesting_cliff_secs: i64,
+    pub team_size: u8,
 }
 
 #[event]
 pub struct HandshakeAccepted {
     pub handshake_id: u64,
     pub worker: Pubkey,
+    pub collateral: u64,
     pub accepted_at: i64,
 }
 
@@ -25,6 +38,10 @@ pub struct HandshakeAccepted {
 pub struct WorkDelivered {
     pub handshake_id: u64,
     pub worker: Pubkey,
+    pub milestone_index: u8,
+    pub revision: u8,
+    pub content_hash: [u8; 32],
+    pub uri: String,
     pub delivered_at: i64,
 }
 
@@ -33,11 +50,35 @@ pub struct HandshakeApproved {
     pub handshake_id: u64,
     pub creator: Pubkey,
     pub worker: Pubkey,
+    pub milestone_index: u8,
+    pub completed: bool,
     pub amount: u64,
     pub pinch_total: u64,
     pub to_treasury: u64,
     pub to_keepers: u64,
     pub to_commons: u64,
+    pub collateral_returned: u64,
+    pub vested: u64,
+    pub bonus: u64,
+    pub bonus_pinch: u64,
+    pub lien_paid: u64,
+}
+
+#[event]
+pub struct PaymentClaimed {
+    pub handshake_id: u64,
+    pub worker: Pubkey,
+    pub claimed_by: Pubkey,
+    pub milestone_index: u8,
+    pub completed: bool,
+    pub amount: u64,
+    pub pinch_total: u64,
+    pub to_treasury: u64,
+    pub to_keepers: u64,
+    pub to_commons: u64,
+    pub collateral_returned: u64,
+    pub vested: u64,
+    pub lien_paid: u64,
 }
 
 #[event]
@@ -47,17 +88,45 @@ pub struct HandshakeCancelled {
     pub cancelled_at: i64,
 }
 
+#[event]
+pub struct HandshakeExpired {
+    pub handshake_id: u64,
+    pub creator: Pubkey,
+    pub cranker: Pubkey,
+    pub refunded: u64,
+    pub crank_tip: u64,
+    pub collateral_forfeited: u64,
+    pub expired_at: i64,
+}
+
 #[event]
 pub struct HandshakeDisputed {
     pub handshake_id: u64,
     pub disputed_by: Pubkey,
+    pub bond: u64,
     pub disputed_at: i64,
 }
 
+#[event]
+pub struct DisputeBondMatched {
+    pub handshake_id: u64,
+    pub matched_by: Pubkey,
+    pub bond: u64,
+}
+
 #[event]
 pub struct DisputeResolved {
     pub handshake_id: u64,
-    pub ruling: u8,   // 0 = refund, 1 = pay worker
+    pub resolver: Pubkey,
+    pub worker_share_bps: u16, // 0 = refund, 10000 = pay worker
+    pub arbiter_fee: u64,
+    pub to_worker: u64,
+    pub pinch_total: u64,
+    pub refunded: u64,
+    pub bonds_returned: u64,
+    pub bonds_forfeited: u64,
+    pub collateral_returned: u64,
+    pub collateral_forfeited: u64,
     pub resolved_at: i64,
 }
 
@@ -67,3 +136,313 @@ pub struct ConfigInitialized {
     pub sour_mint: Pubkey,
     pub pinch_bps: u16,
 }
+
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub pinch_bps: u16,
+    pub treasury_share_bps: u16,
+    pub keepers_share_bps: u16,
+    pub commons_share_bps: u16,
+    pub crank_tip_bps: u16,
+    pub review_window_secs: i64,
+    pub dispute_bond: u64,
+    pub collateral_commons_bps: u16,
+    pub bonus_pinch_bps: u16,
+    pub keepers_pool: Pubkey,
+    pub commons_treasury: Pubkey,
+    pub buyback_treasury: Pubkey,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub authority: Pubkey,
+    pub crank_tip_bps: u16,
+    pub review_window_secs: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AllowedMintUpdated {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub pinch_bps: u16,
+    pub dispute_bond: u64,
+    pub keepers_pool: Pubkey,
+    pub commons_treasury: Pubkey,
+    pub buyback_treasury: Pubkey,
+}
+
+#[event]
+pub struct ArbiterRegistryUpdated {
+    pub arbiter: Pubkey,
+    pub registered: bool,
+    pub arbiter_count: u8,
+}
+
+#[event]
+pub struct DisputeCaseOpened {
+    pub handshake_id: u64,
+    pub draw_slot: u64,
+}
+
+#[event]
+pub struct PanelDrawn {
+    pub handshake_id: u64,
+    pub panel: Vec<Pubkey>,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct PanelVoteCast {
+    pub handshake_id: u64,
+    pub arbiter: Pubkey,
+    pub worker_share_bps: u16,
+    pub votes_cast: u8,
+}
+
+#[event]
+pub struct DisputeFinalized {
+    pub handshake_id: u64,
+    pub votes_cast: u8,
+    pub worker_share_bps: u16,
+}
+
+#[event]
+pub struct EvidenceSubmitted {
+    pub handshake_id: u64,
+    pub submitter: Pubkey,
+    pub index: u8,
+    pub content_hash: [u8; 32],
+    pub uri: String,
+    pub submitted_at: i64,
+}
+
+#[event]
+pub struct ApplicationSubmitted {
+    pub handshake_id: u64,
+    pub applicant: Pubkey,
+    pub amount: u64,
+    pub deadline_ts: i64,
+    pub pitch_hash: [u8; 32],
+}
+
+#[event]
+pub struct ApplicationSelected {
+    pub handshake_id: u64,
+    pub worker: Pubkey,
+    pub amount: u64,
+    pub deadline_ts: i64,
+    pub topped_up: u64,
+    pub refunded: u64,
+    pub collateral: u64,
+}
+
+#[event]
+pub struct AmendmentProposed {
+    pub handshake_id: u64,
+    pub proposer: Pubkey,
+    pub deadline_ts: Option<i64>,
+    pub amount: Option<u64>,
+}
+
+#[event]
+pub struct HandshakeAmended {
+    pub handshake_id: u64,
+    pub proposer: Pubkey,
+    pub accepted_by: Pubkey,
+    pub deadline_ts: i64,
+    pub amount: u64,
+    pub topped_up: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct AmendmentCancelled {
+    pub handshake_id: u64,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct MutualCancelProposed {
+    pub handshake_id: u64,
+    pub proposer: Pubkey,
+    pub worker_share_bps: u16,
+}
+
+#[event]
+pub struct MutualCancelWithdrawn {
+    pub handshake_id: u64,
+    pub withdrawn_by: Pubkey,
+}
+
+#[event]
+pub struct HandshakeMutuallyCancelled {
+    pub handshake_id: u64,
+    pub proposer: Pubkey,
+    pub accepted_by: Pubkey,
+    pub worker_share_bps: u16,
+    pub to_worker: u64,
+    pub pinch_total: u64,
+    pub refunded: u64,
+    pub collateral_returned: u64,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct HandshakeDeclined {
+    pub handshake_id: u64,
+    pub worker: Pubkey,
+    pub refunded: u64,
+    pub declined_at: i64,
+}
+
+#[event]
+pub struct WorkerWithdrew {
+    pub handshake_id: u64,
+    pub worker: Pubkey,
+    pub refunded: u64,
+    pub withdrawals: u32,
+    pub collateral_forfeited: u64,
+    pub withdrawn_at: i64,
+}
+
+#[event]
+pub struct RevisionRequested {
+    pub handshake_id: u64,
+    pub milestone_index: u8,
+    pub feedback_hash: [u8; 32],
+    pub revision_count: u8,
+    pub max_revisions: u8,
+    pub deadline_ts: i64,
+    pub requested_at: i64,
+}
+
+#[event]
+pub struct VestedWithdrawn {
+    pub handshake_id: u64,
+    pub worker: Pubkey,
+    pub amount: u64,
+    pub withdrawn: u64,
+    pub total: u64,
+    pub withdrawn_at: i64,
+}
+
+#[event]
+pub struct RecurringCreated {
+    pub recurring_id: u64,
+    pub creator: Pubkey,
+    pub worker: Pubkey,
+    pub mint: Pubkey,
+    pub amount_per_period: u64,
+    pub period_secs: i64,
+    pub max_periods: u16,
+    pub funded_periods: u16,
+    pub description: String,
+}
+
+#[event]
+pub struct RecurringAccepted {
+    pub recurring_id: u64,
+    pub worker: Pubkey,
+    pub started_at: i64,
+}
+
+#[event]
+pub struct RecurringFunded {
+    pub recurring_id: u64,
+    pub periods: u16,
+    pub funded_periods: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PeriodDelivered {
+    pub recurring_id: u64,
+    pub period: u16,
+    pub content_hash: [u8; 32],
+    pub delivered_at: i64,
+}
+
+#[event]
+pub struct PeriodSettled {
+    pub recurring_id: u64,
+    pub period: u16,
+    pub settled_by: Pubkey,
+    pub delivered: bool,
+    pub to_worker: u64,
+    pub pinch_total: u64,
+    pub to_treasury: u64,
+    pub to_keepers: u64,
+    pub to_commons: u64,
+    pub refunded: u64,
+    pub completed: bool,
+}
+
+#[event]
+pub struct RecurringStopped {
+    pub recurring_id: u64,
+    pub stopped_by: Pubkey,
+    pub end_periods: u16,
+    pub refunded: u64,
+    pub completed: bool,
+    pub stopped_at: i64,
+}
+
+#[event]
+pub struct InvoiceCreated {
+    pub invoice_id: u64,
+    pub worker: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub deadline_ts: i64,
+    pub description: String,
+}
+
+#[event]
+pub struct InvoiceFunded {
+    pub invoice_id: u64,
+    pub handshake_id: u64,
+    pub creator: Pubkey,
+    pub worker: Pubkey,
+    pub amount: u64,
+    pub accepted_at: i64,
+}
+
+#[event]
+pub struct InvoiceRevoked {
+    pub invoice_id: u64,
+    pub revoked_by: Pubkey,
+    pub expired: bool,
+}
+
+#[event]
+pub struct SubcontractCreated {
+    pub parent_id: u64,
+    pub handshake_id: u64,
+    pub creator: Pubkey,
+    pub worker: Pubkey,
+    pub lien: u64,
+    pub deadline_ts: i64,
+    pub description: String,
+}
+
+#[event]
+pub struct LienSettled {
+    pub parent_id: u64,
+    pub handshake_id: u64,
+    pub paid: u64,
+    pub released: u64,
+}
@@ -1,12 +1,22 @@
 // ============================================================================
 // Accept Handshake — Baker B commits to delivering the work
+//
+// Open bounties have no worker yet: the first eligible signer becomes the
+// worker. Eligibility is checked against the worker's record (minimum
+// completed handshakes) and the bounty allowlist, when the creator set them.
+// Bounties that hire by application are taken via `select_application`.
+//
+// Handshakes that require worker collateral lock it in the collateral
+// vault here.
 // ============================================================================
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::{Handshake, HandshakeStatus};
+use crate::state::{BountyAllowlist, Handshake, HandshakeStatus, WorkerRecord};
 use crate::errors::SourError;
 use crate::events::HandshakeAccepted;
+use crate::escrow::MintTransfer;
 
 #[derive(Accounts)]
 pub struct AcceptHandshake<'info> {
@@ -15,15 +25,65 @@ pub struct AcceptHandshake<'info> {
         seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
         bump = handshake.bump,
         constraint = handshake.status == HandshakeStatus::Created @ SourError::InvalidStatus,
-        constraint = handshake.worker == worker.key() @ SourError::NotWorker,
+        constraint = handshake.is_open() || handshake.worker == worker.key() @ SourError::NotWorker,
     )]
     pub handshake: Account<'info, Handshake>,
 
+    /// Worker's track record — required when the bounty sets a minimum
+    #[account(
+        seeds = [WorkerRecord::SEED_PREFIX, worker.key().as_ref()],
+        bump = worker_record.bump,
+    )]
+    pub worker_record: Option<Account<'info, WorkerRecord>>,
+
+    /// Bounty allowlist — required when the bounty is allowlisted
+    #[account(
+        seeds = [BountyAllowlist::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = bounty_allowlist.bump,
+    )]
+    pub bounty_allowlist: Option<Account<'info, BountyAllowlist>>,
+
+    /// Collateral vault — only for handshakes that require worker collateral
+    #[account(
+        init,
+        payer = worker,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Worker's token account — source of the collateral
+    #[account(
+        mut,
+        constraint = worker_token_account.mint == handshake.mint,
+        constraint = worker_token_account.owner == worker.key(),
+    )]
+    pub worker_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Escrow mint
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vaults
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
     pub worker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<AcceptHandshake>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AcceptHandshake<'info>>) -> Result<()> {
     let clock = Clock::get()?;
+    let worker = ctx.accounts.worker.key();
     let handshake = &mut ctx.accounts.handshake;
 
     // Check deadline hasn't passed
@@ -32,12 +92,52 @@ pub fn handler(ctx: Context<AcceptHandshake>) -> Result<()> {
         SourError::HandshakeExpired
     );
 
+    // Open bounty: check eligibility, then bind the worker
+    if handshake.is_open() {
+        require!(!handshake.by_application, SourError::ApplicationRequired);
+        handshake.check_eligibility(
+            &worker,
+            ctx.accounts.worker_record.as_deref(),
+            ctx.accounts.bounty_allowlist.as_deref(),
+        )?;
+        handshake.worker = worker;
+    }
+
+    // Lock the worker's collateral
+    require!(
+        ctx.accounts.collateral_vault.is_some() == (handshake.worker_collateral > 0),
+        SourError::CollateralVaultMismatch
+    );
+    if let Some(collateral_vault) = ctx.accounts.collateral_vault.as_mut() {
+        let worker_token_account = ctx
+            .accounts
+            .worker_token_account
+            .as_ref()
+            .ok_or(SourError::CollateralAccountRequired)?;
+        MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        )
+        .transfer(
+            &worker_token_account.to_account_info(),
+            &collateral_vault.to_account_info(),
+            &ctx.accounts.worker.to_account_info(),
+            &[],
+            handshake.worker_collateral,
+        )?;
+        // Fee-on-transfer mints deliver less than was sent — hold what arrived
+        collateral_vault.reload()?;
+        handshake.collateral_held = collateral_vault.amount;
+    }
+
     handshake.status = HandshakeStatus::Accepted;
     handshake.accepted_at = clock.unix_timestamp;
 
     emit!(HandshakeAccepted {
         handshake_id: handshake.id,
         worker: handshake.worker,
+        collateral: handshake.collateral_held,
         accepted_at: handshake.accepted_at,
     });
 
@@ -0,0 +1,219 @@
+// ============================================================================
+// Accept Amendment — the counterparty applies a proposed change atomically
+//
+// A larger escrow is pulled from the creator (signing directly, or through
+// the delegation made at proposal); a smaller one refunds the difference.
+// For milestone handshakes the change lands on the final tranche.
+//
+// A parent cannot be amended below what its subcontract lien needs: the
+// worker's share of the new escrow, after the Pinch, must still cover it.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    AllowedMint, Amendment, Handshake, HandshakeStatus, Milestones, ProtocolConfig,
+};
+use crate::errors::SourError;
+use crate::events::HandshakeAmended;
+use crate::pinch::{FeeTerms, PinchSplit};
+use crate::escrow::{MintTransfer, VaultPayout};
+
+#[derive(Accounts)]
+pub struct AcceptAmendment<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = matches!(
+            handshake.status,
+            HandshakeStatus::Accepted | HandshakeStatus::Delivered
+        ) @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [Amendment::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = amendment.bump,
+    )]
+    pub amendment: Account<'info, Amendment>,
+
+    /// Milestone schedule — required for milestone handshakes
+    #[account(
+        mut,
+        seeds = [Milestones::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
+    /// Allowlist entry for the escrow mint — only needed (for non-$SOUR
+    /// escrows) while a subcontract lien is outstanding
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// The escrow vault
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account — funds a top-up or receives the difference
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The proposer — receives the amendment's rent back
+    /// CHECK: address checked against the amendment
+    #[account(mut, address = amendment.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// The other party
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AcceptAmendment<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let signer_key = ctx.accounts.signer.key();
+    let proposer = ctx.accounts.amendment.proposer;
+    {
+        let handshake = &ctx.accounts.handshake;
+        require!(
+            (signer_key == handshake.creator || signer_key == handshake.worker)
+                && signer_key != proposer,
+            SourError::NotCounterparty
+        );
+        ctx.accounts.amendment.check(handshake, clock.unix_timestamp)?;
+    }
+
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+
+    // Move escrow to match the new amount
+    let escrowed = ctx.accounts.handshake.amount;
+    let new_amount = ctx.accounts.amendment.amount.unwrap_or(escrowed);
+    let mut topped_up = 0;
+    let mut refunded = 0;
+    if new_amount > escrowed {
+        // The creator signs here, or delegated the top-up when proposing
+        let (authority, seeds) = if signer_key == ctx.accounts.handshake.creator {
+            (ctx.accounts.signer.to_account_info(), &[][..])
+        } else {
+            (ctx.accounts.vault_authority.to_account_info(), signer_seeds)
+        };
+        let before = ctx.accounts.vault.amount;
+        payout.token.transfer(
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &authority,
+            seeds,
+            new_amount - escrowed,
+        )?;
+        // Fee-on-transfer mints deliver less than was sent
+        ctx.accounts.vault.reload()?;
+        topped_up = ctx
+            .accounts
+            .vault
+            .amount
+            .checked_sub(before)
+            .ok_or(SourError::MathOverflow)?;
+    } else if new_amount < escrowed {
+        refunded = escrowed - new_amount;
+        payout.transfer(
+            &ctx.accounts.creator_token_account.to_account_info(),
+            refunded,
+        )?;
+    }
+
+    let handshake = &mut ctx.accounts.handshake;
+    let new_deadline = ctx.accounts.amendment.deadline_ts;
+    if let Some(milestones) = ctx.accounts.milestones.as_mut() {
+        milestones.amend(topped_up as i128 - refunded as i128, new_deadline)?;
+    } else {
+        require!(handshake.milestone_count == 0, SourError::MilestonesMismatch);
+    }
+
+    handshake.amount = escrowed
+        .checked_add(topped_up)
+        .ok_or(SourError::MathOverflow)?
+        .checked_sub(refunded)
+        .ok_or(SourError::MathOverflow)?;
+    if let Some(deadline_ts) = new_deadline {
+        handshake.deadline_ts = deadline_ts;
+    }
+
+    // The subcontract's lien is paid out of the worker's share, after fees
+    if handshake.child_id.is_some() && handshake.lien > 0 {
+        let fee_terms = FeeTerms::for_mint(
+            &handshake.mint,
+            &ctx.accounts.config,
+            ctx.accounts.allowed_mint.as_deref(),
+        )?;
+        let split = PinchSplit::compute(handshake.remaining()?, &fee_terms)?;
+        require!(split.to_worker >= handshake.lien, SourError::LienUncovered);
+    }
+
+    emit!(HandshakeAmended {
+        handshake_id: handshake.id,
+        proposer,
+        accepted_by: signer_key,
+        deadline_ts: handshake.deadline_ts,
+        amount: handshake.amount,
+        topped_up,
+        refunded,
+    });
+
+    msg!(
+        "Handshake #{} amended: {} escrowed, deadline {}",
+        handshake.id,
+        handshake.amount,
+        handshake.deadline_ts
+    );
+    Ok(())
+}
@@ -0,0 +1,46 @@
+// ============================================================================
+// Accept Authority — step 2 of the authority handover
+//
+// Only the pending authority can complete the handover, so a typo in
+// `propose_authority` can never lock the protocol.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::ProtocolConfig;
+use crate::errors::SourError;
+use crate::events::AuthorityTransferred;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.pending_authority != Pubkey::default() @ SourError::NotPendingAuthority,
+        constraint = config.pending_authority == new_authority.key() @ SourError::NotPendingAuthority,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub new_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_authority = config.authority;
+
+    config.authority = config.pending_authority;
+    config.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: config.authority,
+    });
+
+    msg!(
+        "Authority handed over from {} to {}",
+        previous_authority,
+        config.authority
+    );
+    Ok(())
+}
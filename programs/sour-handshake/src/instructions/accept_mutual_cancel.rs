@@ -0,0 +1,344 @@
+// ============================================================================
+// Accept Mutual Cancel — the other party co-signs the proposed split and
+// the handshake is settled and closed out as Cancelled
+//
+// The worker's share of the remaining escrow pays the Pinch (exactly as
+// `approve`); the creator's refund is fee-free and any worker collateral is
+// returned. Dispute statistics are not touched.
+//
+// A parent's subcontract lien is paid out of the worker's portion when it
+// covers it; otherwise the lien is released and the subcontract cancelled.
+//
+// A vested handshake's stream only starts on approval, so its worker share
+// is paid directly. The collateral vault and the payout stream are closed,
+// their rent returned to the creator.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    AllowedMint, CancelProposal, Handshake, HandshakeStatus, PayoutStream, ProtocolConfig,
+    TeamPayees,
+};
+use crate::errors::SourError;
+use crate::events::HandshakeMutuallyCancelled;
+use crate::pinch::{bps_of, FeeTerms, PinchSplit};
+use crate::escrow::{MintTransfer, TeamPayout, VaultPayout};
+use crate::settlement::settle_lien;
+
+#[derive(Accounts)]
+pub struct AcceptMutualCancel<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = matches!(
+            handshake.status,
+            HandshakeStatus::Accepted | HandshakeStatus::Delivered
+        ) @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [CancelProposal::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = cancel_proposal.bump,
+    )]
+    pub cancel_proposal: Account<'info, CancelProposal>,
+
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        mut,
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// Escrow vault
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account — receives the refunded portion
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Worker's token account — receives the worker's portion
+    #[account(
+        mut,
+        constraint = worker_token_account.mint == handshake.mint,
+        constraint = worker_token_account.owner == handshake.worker,
+    )]
+    pub worker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral vault — required when the handshake holds worker
+    /// collateral; closed to the creator
+    #[account(
+        mut,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Team payees — required when the worker's payout is split across a
+    /// team (payee token accounts lead the remaining accounts)
+    #[account(
+        seeds = [TeamPayees::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = team.bump,
+    )]
+    pub team: Option<Account<'info, TeamPayees>>,
+
+    /// Subcontract carved out of this handshake — required while its lien
+    /// is outstanding
+    #[account(
+        mut,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            handshake.child_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump = subcontract.bump,
+    )]
+    pub subcontract: Option<Account<'info, Handshake>>,
+
+    /// Subcontract's escrow vault — receives the lien when the worker's
+    /// share covers it
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.child_id.unwrap_or_default().to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub subcontract_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payout stream — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator — receives the rent of the accounts closed here
+    /// CHECK: must match handshake.creator
+    #[account(mut, address = handshake.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Keepers pool
+    #[account(mut)]
+    pub keepers_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Commons treasury
+    #[account(mut)]
+    pub commons_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Buyback+LP treasury — receives 50% of Pinch
+    #[account(mut)]
+    pub buyback_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// The proposer — receives the proposal's rent back
+    /// CHECK: address checked against the proposal
+    #[account(mut, address = cancel_proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// The other party
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AcceptMutualCancel<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let signer_key = ctx.accounts.signer.key();
+    let proposer = ctx.accounts.cancel_proposal.proposer;
+    let worker_share_bps = ctx.accounts.cancel_proposal.worker_share_bps;
+    {
+        let handshake = &ctx.accounts.handshake;
+        require!(
+            (signer_key == handshake.creator || signer_key == handshake.worker)
+                && signer_key != proposer,
+            SourError::NotCounterparty
+        );
+        require!(
+            ctx.accounts.collateral_vault.is_some() == (handshake.collateral_held > 0),
+            SourError::CollateralVaultMismatch
+        );
+        require!(
+            ctx.accounts.payout_stream.is_some() == handshake.vested_payout
+                && ctx.accounts.stream_vault.is_some() == handshake.vested_payout,
+            SourError::PayoutStreamMismatch
+        );
+    }
+
+    // Pinch rate + fee destinations for the escrow mint
+    let fee_terms = FeeTerms::for_mint(
+        &ctx.accounts.handshake.mint,
+        &ctx.accounts.config,
+        ctx.accounts.allowed_mint.as_deref(),
+    )?;
+    fee_terms.check_destinations(
+        &ctx.accounts.keepers_pool.key(),
+        &ctx.accounts.commons_treasury.key(),
+        &ctx.accounts.buyback_treasury.key(),
+    )?;
+
+    // Worker portion pays Pinch; the creator's refund (plus any dust left in
+    // the vault) is fee-free
+    let remaining = ctx.accounts.handshake.remaining()?;
+    let to_worker_gross = bps_of(remaining, worker_share_bps)?;
+    let mut split = PinchSplit::compute(to_worker_gross, &fee_terms)?;
+    let refunded = ctx
+        .accounts
+        .vault
+        .amount
+        .checked_sub(to_worker_gross)
+        .ok_or(SourError::InsufficientEscrow)?;
+
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    // Team handshakes: payee token accounts lead the remaining accounts
+    require!(
+        ctx.accounts.team.is_some() == ctx.accounts.handshake.team_payout,
+        SourError::TeamMismatch
+    );
+    let (team, hook_accounts) =
+        TeamPayout::split(ctx.accounts.team.as_deref(), ctx.remaining_accounts)?;
+
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            hook_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+    // A subcontract lien comes out of the worker's portion first
+    let subcontract_vault = ctx
+        .accounts
+        .subcontract_vault
+        .as_ref()
+        .map(|account| account.to_account_info());
+    settle_lien(
+        &mut ctx.accounts.handshake,
+        ctx.accounts.subcontract.as_deref_mut(),
+        subcontract_vault.as_ref(),
+        &payout,
+        Some(&mut split),
+        false,
+        clock.unix_timestamp,
+    )?;
+    payout.pay_split(
+        &split,
+        &ctx.accounts.worker_token_account.to_account_info(),
+        team.as_ref(),
+        &ctx.accounts.buyback_treasury.to_account_info(),
+        &ctx.accounts.keepers_pool.to_account_info(),
+        &ctx.accounts.commons_treasury.to_account_info(),
+    )?;
+    payout.transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        refunded,
+    )?;
+
+    // Walking away by agreement returns the worker's collateral
+    let collateral_returned = ctx.accounts.handshake.take_collateral();
+    if let Some(collateral_vault) = &ctx.accounts.collateral_vault {
+        payout.transfer_from(
+            &collateral_vault.to_account_info(),
+            &ctx.accounts.worker_token_account.to_account_info(),
+            collateral_returned,
+        )?;
+        payout.close_vault(
+            &collateral_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
+    // The payout never started streaming — hand back the stream's rent
+    if let Some(stream_vault) = &ctx.accounts.stream_vault {
+        payout.close_vault(
+            &stream_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
+    // Fee totals only — no completion or dispute stats
+    if to_worker_gross > 0 {
+        split.record(&mut ctx.accounts.config, ctx.accounts.allowed_mint.as_deref_mut())?;
+    }
+
+    let handshake = &mut ctx.accounts.handshake;
+    handshake.status = HandshakeStatus::Cancelled;
+    handshake.resolved_at = clock.unix_timestamp;
+    handshake.released = handshake
+        .released
+        .checked_add(to_worker_gross)
+        .ok_or(SourError::MathOverflow)?;
+
+    emit!(HandshakeMutuallyCancelled {
+        handshake_id: handshake.id,
+        proposer,
+        accepted_by: signer_key,
+        worker_share_bps,
+        to_worker: split.to_worker,
+        pinch_total: split.pinch_total,
+        refunded,
+        collateral_returned,
+        cancelled_at: handshake.resolved_at,
+    });
+
+    msg!(
+        "Handshake #{} cancelled by agreement: {} to worker, {} refunded",
+        handshake.id,
+        split.to_worker,
+        refunded
+    );
+    Ok(())
+}
@@ -0,0 +1,40 @@
+// ============================================================================
+// Accept Recurring — Baker B takes the retainer; period 0 starts now
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{RecurringHandshake, RecurringStatus};
+use crate::errors::SourError;
+use crate::events::RecurringAccepted;
+
+#[derive(Accounts)]
+pub struct AcceptRecurring<'info> {
+    #[account(
+        mut,
+        seeds = [RecurringHandshake::SEED_PREFIX, recurring.id.to_le_bytes().as_ref()],
+        bump = recurring.bump,
+        constraint = recurring.status == RecurringStatus::Created @ SourError::InvalidStatus,
+        constraint = recurring.worker == worker.key() @ SourError::NotWorker,
+    )]
+    pub recurring: Account<'info, RecurringHandshake>,
+
+    pub worker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptRecurring>) -> Result<()> {
+    let clock = Clock::get()?;
+    let recurring = &mut ctx.accounts.recurring;
+
+    recurring.status = RecurringStatus::Active;
+    recurring.started_at = clock.unix_timestamp;
+
+    emit!(RecurringAccepted {
+        recurring_id: recurring.id,
+        worker: recurring.worker,
+        started_at: recurring.started_at,
+    });
+
+    msg!("Recurring handshake #{} accepted by worker", recurring.id);
+    Ok(())
+}
@@ -0,0 +1,84 @@
+// ============================================================================
+// Add Allowed Mint — authority allowlists a non-$SOUR escrow mint
+//
+// Each entry carries its own Pinch rate and fee destination token
+// accounts (denominated in that mint). The share split follows config.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::{AllowedMint, ProtocolConfig};
+use crate::errors::SourError;
+use crate::events::AllowedMintUpdated;
+
+#[derive(Accounts)]
+pub struct AddAllowedMint<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ SourError::NotAuthority,
+        constraint = config.sour_mint != mint.key() @ SourError::AllowedMintMismatch,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AllowedMint::INIT_SPACE,
+        seeds = [AllowedMint::SEED_PREFIX, mint.key().as_ref()],
+        bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    /// The mint being allowlisted
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Receives the Keepers' share of Pinch fees in this mint
+    #[account(constraint = keepers_pool.mint == mint.key() @ SourError::InvalidFeeDestination)]
+    pub keepers_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the Commons' share of Pinch fees in this mint
+    #[account(constraint = commons_treasury.mint == mint.key() @ SourError::InvalidFeeDestination)]
+    pub commons_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the Buyback+LP share of Pinch fees in this mint
+    #[account(constraint = buyback_treasury.mint == mint.key() @ SourError::InvalidFeeDestination)]
+    pub buyback_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddAllowedMint>, pinch_bps: u16, dispute_bond: u64) -> Result<()> {
+    // Validate: pinch fee must be reasonable (max 50%)
+    require!(pinch_bps <= 5000, SourError::InvalidPinchBps);
+
+    let allowed_mint = &mut ctx.accounts.allowed_mint;
+    allowed_mint.mint = ctx.accounts.mint.key();
+    allowed_mint.enabled = true;
+    allowed_mint.pinch_bps = pinch_bps;
+    allowed_mint.keepers_pool = ctx.accounts.keepers_pool.key();
+    allowed_mint.commons_treasury = ctx.accounts.commons_treasury.key();
+    allowed_mint.buyback_treasury = ctx.accounts.buyback_treasury.key();
+    allowed_mint.dispute_bond = dispute_bond;
+    allowed_mint.total_to_treasury = 0;
+    allowed_mint.total_to_keepers = 0;
+    allowed_mint.total_to_commons = 0;
+    allowed_mint.bump = ctx.bumps.allowed_mint;
+
+    emit!(AllowedMintUpdated {
+        mint: allowed_mint.mint,
+        enabled: allowed_mint.enabled,
+        pinch_bps,
+        dispute_bond,
+        keepers_pool: allowed_mint.keepers_pool,
+        commons_treasury: allowed_mint.commons_treasury,
+        buyback_treasury: allowed_mint.buyback_treasury,
+    });
+
+    msg!("Mint {} allowed for escrow. Pinch: {}bps", allowed_mint.mint, pinch_bps);
+    Ok(())
+}
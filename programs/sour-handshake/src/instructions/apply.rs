@@ -0,0 +1,180 @@
+// ============================================================================
+// Apply — a prospective worker bids on an open bounty
+//
+// The bid proposes an escrow amount and a deadline, and commits to an
+// off-chain pitch by hash. Applicants must meet the bounty's eligibility
+// (track record / allowlist); the creator picks one with
+// `select_application`.
+//
+// When the bounty requires worker collateral, the applicant approves it
+// (delegated) to the vault authority here; it is pulled only if they are
+// selected. A token account has a single delegate, so a later approval
+// elsewhere replaces this one; `close_application` revokes it. The
+// applicant also deposits the collateral vault's rent with the application,
+// so the creator does not pay for it on selection.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{Application, BountyAllowlist, Handshake, HandshakeStatus, WorkerRecord};
+use crate::errors::SourError;
+use crate::events::ApplicationSubmitted;
+use crate::escrow::{token_account_space, MintTransfer};
+
+#[derive(Accounts)]
+pub struct Apply<'info> {
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Created @ SourError::InvalidStatus,
+        constraint = handshake.is_open() @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        init,
+        payer = applicant,
+        space = 8 + Application::INIT_SPACE,
+        seeds = [
+            Application::SEED_PREFIX,
+            handshake.id.to_le_bytes().as_ref(),
+            applicant.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub application: Account<'info, Application>,
+
+    /// Applicant's track record — required when the bounty sets a minimum
+    #[account(
+        seeds = [WorkerRecord::SEED_PREFIX, applicant.key().as_ref()],
+        bump = worker_record.bump,
+    )]
+    pub worker_record: Option<Account<'info, WorkerRecord>>,
+
+    /// Bounty allowlist — required when the bounty is allowlisted
+    #[account(
+        seeds = [BountyAllowlist::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = bounty_allowlist.bump,
+    )]
+    pub bounty_allowlist: Option<Account<'info, BountyAllowlist>>,
+
+    /// Applicant's token account — required when the bounty asks for
+    /// collateral, which is delegated from it
+    #[account(
+        mut,
+        constraint = applicant_token_account.mint == handshake.mint,
+        constraint = applicant_token_account.owner == applicant.key(),
+    )]
+    pub applicant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Escrow mint
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vaults — delegate for the collateral
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub applicant: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<Apply>,
+    amount: u64,
+    deadline_ts: i64,
+    pitch_hash: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake = &ctx.accounts.handshake;
+    let applicant = ctx.accounts.applicant.key();
+
+    require!(
+        clock.unix_timestamp <= handshake.deadline_ts,
+        SourError::HandshakeExpired
+    );
+    handshake.check_eligibility(
+        &applicant,
+        ctx.accounts.worker_record.as_deref(),
+        ctx.accounts.bounty_allowlist.as_deref(),
+    )?;
+
+    // Bid terms: a positive amount and a future deadline. Milestone
+    // schedules are fixed, so those bids may only push the deadline out
+    // (the tranches shift with it on selection).
+    require!(
+        amount > 0 && deadline_ts > clock.unix_timestamp,
+        SourError::InvalidApplication
+    );
+    if handshake.milestone_count > 0 {
+        require!(
+            amount == handshake.amount && deadline_ts >= handshake.deadline_ts,
+            SourError::InvalidApplication
+        );
+    }
+
+    // Delegate the collateral, pulled on selection, and deposit the rent of
+    // the vault it would be locked in
+    let mut collateral_rent = 0;
+    if handshake.worker_collateral > 0 {
+        let applicant_token_account = ctx
+            .accounts
+            .applicant_token_account
+            .as_ref()
+            .ok_or(SourError::CollateralAccountRequired)?;
+        MintTransfer::new(&ctx.accounts.token_program, &ctx.accounts.mint, &[]).approve(
+            &applicant_token_account.to_account_info(),
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.applicant.to_account_info(),
+            handshake.worker_collateral,
+        )?;
+
+        collateral_rent = Rent::get()?
+            .minimum_balance(token_account_space(&ctx.accounts.mint.to_account_info())?);
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.applicant.to_account_info(),
+                    to: ctx.accounts.application.to_account_info(),
+                },
+            ),
+            collateral_rent,
+        )?;
+    }
+
+    let application = &mut ctx.accounts.application;
+    application.handshake_id = handshake.id;
+    application.applicant = applicant;
+    application.amount = amount;
+    application.deadline_ts = deadline_ts;
+    application.pitch_hash = pitch_hash;
+    application.collateral_rent = collateral_rent;
+    application.submitted_at = clock.unix_timestamp;
+    application.bump = ctx.bumps.application;
+
+    emit!(ApplicationSubmitted {
+        handshake_id: handshake.id,
+        applicant,
+        amount,
+        deadline_ts,
+        pitch_hash,
+    });
+
+    msg!(
+        "Application to handshake #{}: {} by {}",
+        handshake.id,
+        amount,
+        deadline_ts
+    );
+    Ok(())
+}
@@ -1,6 +1,26 @@
 // ============================================================================
 // Approve — Baker A approves delivery, releases funds with Pinch fee
 //
+// Milestone handshakes release only the open tranche and return to
+// Accepted for the next one; the last tranche completes the handshake and
+// returns any worker collateral.
+//
+// Handshakes with a vested payout send the worker's share to the payout
+// stream instead; the Pinch is still taken in full here.
+//
+// Team handshakes split the worker's share across the payees, the rounding
+// remainder going to the lead (the handshake's worker).
+//
+// A subcontracted handshake first pays its child's lien out of the worker's
+// share into the child's vault; a subcontract cannot be approved until its
+// parent has done so.
+//
+// An optional bonus is paid straight from the creator's token account to
+// the worker (the lead, for a team) on top of the release, charged the
+// config's bonus Pinch rate (feeless by default).
+//
+// Release itself lives in `settlement.rs`, shared with `claim_after_review`.
+//
 // Pinch Fee Flow (default 2% of escrow):
 //   50% → Treasury PDA (batched → SOUR buyback + Protocol-Owned LP)
 //   30% → Keepers pool (holder rewards)
@@ -10,11 +30,17 @@
 // ============================================================================
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::{Handshake, HandshakeStatus, ProtocolConfig};
+use crate::state::{
+    AllowedMint, Handshake, HandshakeStatus, Milestones, PayoutStream, ProtocolConfig,
+    TeamPayees, WorkerRecord,
+};
 use crate::errors::SourError;
 use crate::events::HandshakeApproved;
+use crate::pinch::{FeeTerms, PinchSplit};
+use crate::escrow::{MintTransfer, TeamPayout, VaultPayout};
+use crate::settlement::{self, SettlementAccounts};
 
 #[derive(Accounts)]
 pub struct Approve<'info> {
@@ -34,13 +60,33 @@ pub struct Approve<'info> {
     )]
     pub handshake: Account<'info, Handshake>,
 
-    /// The escrow vault holding $SOUR
+    /// Milestone schedule — required for milestone handshakes
+    #[account(
+        mut,
+        seeds = [Milestones::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        mut,
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// The escrow vault holding the escrowed tokens
     #[account(
         mut,
         seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
         bump,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// PDA authority over the vault
     /// CHECK: PDA seeds verified
@@ -53,80 +99,112 @@ pub struct Approve<'info> {
     /// Worker's token account — receives the payment
     #[account(
         mut,
-        constraint = worker_token_account.mint == config.sour_mint,
+        constraint = worker_token_account.mint == handshake.mint,
         constraint = worker_token_account.owner == handshake.worker,
     )]
-    pub worker_token_account: Account<'info, TokenAccount>,
+    pub worker_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// Keepers pool — receives 30% of Pinch
+    /// Collateral vault — required when the handshake holds worker collateral
     #[account(
         mut,
-        constraint = keepers_pool.key() == config.keepers_pool,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
     )]
-    pub keepers_pool: Account<'info, TokenAccount>,
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// Commons treasury — receives 20% of Pinch
+    /// Payout stream — required when the handshake vests its payout
     #[account(
         mut,
-        constraint = commons_treasury.key() == config.commons_treasury,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
     )]
-    pub commons_treasury: Account<'info, TokenAccount>,
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
 
-    /// Buyback+LP treasury — receives 50% of Pinch (batched → buyback + LP)
+    /// Stream vault — receives the worker's share of a vested payout
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Team payees — required when the worker's payout is split across a
+    /// team (payee token accounts lead the remaining accounts)
+    #[account(
+        seeds = [TeamPayees::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = team.bump,
+    )]
+    pub team: Option<Account<'info, TeamPayees>>,
+
+    /// Subcontract carved out of this handshake — required while its lien
+    /// is outstanding
+    #[account(
+        mut,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            handshake.child_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump = subcontract.bump,
+    )]
+    pub subcontract: Option<Account<'info, Handshake>>,
+
+    /// Subcontract's escrow vault — receives the lien
     #[account(
         mut,
-        constraint = buyback_treasury.key() == config.buyback_treasury,
+        seeds = [b"vault", handshake.child_id.unwrap_or_default().to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub subcontract_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Worker's track record — credited when the handshake completes
+    /// (opened here if the worker has none yet)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + WorkerRecord::INIT_SPACE,
+        seeds = [WorkerRecord::SEED_PREFIX, handshake.worker.as_ref()],
+        bump,
+    )]
+    pub worker_record: Account<'info, WorkerRecord>,
+
+    /// Creator's token account — funds the bonus (required with a bonus)
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == creator.key(),
     )]
-    pub buyback_treasury: Account<'info, TokenAccount>,
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Keepers pool — receives 30% of Pinch
+    #[account(mut)]
+    pub keepers_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Commons treasury — receives 20% of Pinch
+    #[account(mut)]
+    pub commons_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Buyback+LP treasury — receives 50% of Pinch (batched → buyback + LP)
+    #[account(mut)]
+    pub buyback_treasury: InterfaceAccount<'info, TokenAccount>,
 
     /// The creator (Baker A) approving the delivery
+    #[account(mut)]
     pub creator: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Approve>) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Approve<'info>>,
+    bonus: Option<u64>,
+) -> Result<()> {
     let clock = Clock::get()?;
-    let config = &ctx.accounts.config;
-    let handshake_id = ctx.accounts.handshake.id;
-    let amount = ctx.accounts.handshake.amount;
+    let milestone_index = ctx.accounts.handshake.current_milestone;
 
-    // -----------------------------------------------------------------------
-    // Calculate Pinch fee splits
-    // -----------------------------------------------------------------------
-    let pinch_total = (amount as u128)
-        .checked_mul(config.pinch_bps as u128)
-        .ok_or(SourError::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(SourError::MathOverflow)? as u64;
-
-    let treasury_amount = (pinch_total as u128)
-        .checked_mul(config.treasury_share_bps as u128)
-        .ok_or(SourError::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(SourError::MathOverflow)? as u64;
-
-    let keepers_amount = (pinch_total as u128)
-        .checked_mul(config.keepers_share_bps as u128)
-        .ok_or(SourError::MathOverflow)?
-        .checked_div(10_000)
-        .ok_or(SourError::MathOverflow)? as u64;
-
-    // Commons gets the remainder to avoid rounding dust
-    let commons_amount = pinch_total
-        .checked_sub(treasury_amount)
-        .ok_or(SourError::MathOverflow)?
-        .checked_sub(keepers_amount)
-        .ok_or(SourError::MathOverflow)?;
-
-    let worker_amount = amount
-        .checked_sub(pinch_total)
-        .ok_or(SourError::MathOverflow)?;
-
-    // -----------------------------------------------------------------------
     // Build PDA signer seeds for vault authority
-    // -----------------------------------------------------------------------
-    let id_bytes = handshake_id.to_le_bytes();
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
     let vault_bump = ctx.accounts.handshake.vault_bump;
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"vault_auth",
@@ -134,113 +212,114 @@ pub fn handler(ctx: Context<Approve>) -> Result<()> {
         &[vault_bump],
     ]];
 
-    // -----------------------------------------------------------------------
-    // 1. Transfer to worker (98% by default)
-    // -----------------------------------------------------------------------
-    if worker_amount > 0 {
-        let transfer_worker_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.worker_token_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_worker_ctx, worker_amount)?;
-    }
+    let accounts = ctx.accounts;
+    accounts
+        .worker_record
+        .open_if_new(accounts.handshake.worker, ctx.bumps.worker_record);
 
-    // -----------------------------------------------------------------------
-    // 2. Transfer 50% of Pinch to Buyback+LP Treasury
-    // -----------------------------------------------------------------------
-    if treasury_amount > 0 {
-        let transfer_treasury_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.buyback_treasury.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_treasury_ctx, treasury_amount)?;
-    }
+    // Team handshakes: payee token accounts lead the remaining accounts
+    let (team, hook_accounts) =
+        TeamPayout::split(accounts.team.as_deref(), ctx.remaining_accounts)?;
+    let payout = VaultPayout {
+        token: MintTransfer::new(&accounts.token_program, &accounts.mint, hook_accounts),
+        vault: accounts.vault.to_account_info(),
+        vault_authority: accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
 
     // -----------------------------------------------------------------------
-    // 3. Transfer 30% of Pinch to Keepers pool
+    // Release the tranche: worker 98% by default + Pinch to the pools
     // -----------------------------------------------------------------------
-    if keepers_amount > 0 {
-        let transfer_keepers_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.keepers_pool.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_keepers_ctx, keepers_amount)?;
-    }
+    let settled = settlement::execute(
+        SettlementAccounts {
+            config: &mut accounts.config,
+            handshake: &mut accounts.handshake,
+            milestones: accounts.milestones.as_deref_mut(),
+            allowed_mint: accounts.allowed_mint.as_deref_mut(),
+            payout: &payout,
+            worker_token_account: accounts.worker_token_account.to_account_info(),
+            team,
+            collateral_vault: accounts
+                .collateral_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            payout_stream: accounts.payout_stream.as_deref_mut(),
+            stream_vault: accounts
+                .stream_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            subcontract: accounts.subcontract.as_deref_mut(),
+            subcontract_vault: accounts
+                .subcontract_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            worker_record: &mut accounts.worker_record,
+            keepers_pool: accounts.keepers_pool.to_account_info(),
+            commons_treasury: accounts.commons_treasury.to_account_info(),
+            buyback_treasury: accounts.buyback_treasury.to_account_info(),
+        },
+        clock.unix_timestamp,
+    )?;
+    let split = settled.split;
 
     // -----------------------------------------------------------------------
-    // 4. Transfer 20% of Pinch to Commons treasury
+    // Bonus: straight from the creator to the worker (+ bonus Pinch)
     // -----------------------------------------------------------------------
-    if commons_amount > 0 {
-        let transfer_commons_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.commons_treasury.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_commons_ctx, commons_amount)?;
+    let bonus = bonus.unwrap_or(0);
+    let bonus_split = PinchSplit::compute(
+        bonus,
+        &FeeTerms {
+            pinch_bps: accounts.config.bonus_pinch_bps,
+            ..settled.fee_terms
+        },
+    )?;
+    if bonus > 0 {
+        let creator_token_account = accounts
+            .creator_token_account
+            .as_ref()
+            .ok_or(SourError::BonusAccountRequired)?
+            .to_account_info();
+        let creator = accounts.creator.to_account_info();
+        for (to, amount) in [
+            (accounts.worker_token_account.to_account_info(), bonus_split.to_worker),
+            (accounts.buyback_treasury.to_account_info(), bonus_split.to_treasury),
+            (accounts.keepers_pool.to_account_info(), bonus_split.to_keepers),
+            (accounts.commons_treasury.to_account_info(), bonus_split.to_commons),
+        ] {
+            payout
+                .token
+                .transfer(&creator_token_account, &to, &creator, &[], amount)?;
+        }
     }
+    bonus_split.record(&mut accounts.config, accounts.allowed_mint.as_deref_mut())?;
 
-    // -----------------------------------------------------------------------
-    // Update state
-    // -----------------------------------------------------------------------
-    let handshake = &mut ctx.accounts.handshake;
-    handshake.status = HandshakeStatus::Approved;
-    handshake.resolved_at = clock.unix_timestamp;
-
-    let config = &mut ctx.accounts.config;
-    config.total_completed = config
-        .total_completed
-        .checked_add(1)
-        .ok_or(SourError::MathOverflow)?;
-    config.total_to_treasury = config
-        .total_to_treasury
-        .checked_add(treasury_amount)
-        .ok_or(SourError::MathOverflow)?;
-    config.total_to_keepers = config
-        .total_to_keepers
-        .checked_add(keepers_amount)
-        .ok_or(SourError::MathOverflow)?;
-    config.total_to_commons = config
-        .total_to_commons
-        .checked_add(commons_amount)
-        .ok_or(SourError::MathOverflow)?;
-
+    let handshake = &accounts.handshake;
     emit!(HandshakeApproved {
         handshake_id: handshake.id,
         creator: handshake.creator,
         worker: handshake.worker,
-        amount,
-        pinch_total,
-        to_treasury: treasury_amount,
-        to_keepers: keepers_amount,
-        to_commons: commons_amount,
+        milestone_index,
+        completed: settled.completed,
+        amount: settled.amount,
+        pinch_total: split.pinch_total,
+        to_treasury: split.to_treasury,
+        to_keepers: split.to_keepers,
+        to_commons: split.to_commons,
+        collateral_returned: settled.collateral_returned,
+        vested: settled.vested,
+        lien_paid: settled.lien_paid,
+        bonus,
+        bonus_pinch: bonus_split.pinch_total,
     });
 
     msg!(
-        "Handshake #{} approved! {} to worker, {} to treasury, {} to keepers, {} to commons",
+        "Handshake #{} approved (milestone {})! {} to worker, {} to treasury, {} to keepers, {} to commons",
         handshake.id,
-        worker_amount,
-        treasury_amount,
-        keepers_amount,
-        commons_amount,
+        milestone_index,
+        split.to_worker,
+        split.to_treasury,
+        split.to_keepers,
+        split.to_commons,
     );
     Ok(())
 }
@@ -1,13 +1,17 @@
 // ============================================================================
 // Cancel — creator cancels before acceptance, gets full refund
+//
+// The payout stream of a vested handshake is closed along with it, its rent
+// returned to the creator.
 // ============================================================================
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::{Handshake, HandshakeStatus, ProtocolConfig};
+use crate::state::{Handshake, HandshakeStatus, PayoutStream, ProtocolConfig};
 use crate::errors::SourError;
 use crate::events::HandshakeCancelled;
+use crate::escrow::{MintTransfer, VaultPayout};
 
 #[derive(Accounts)]
 pub struct Cancel<'info> {
@@ -32,7 +36,11 @@ pub struct Cancel<'info> {
         seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
         bump,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// PDA authority over the vault
     /// CHECK: PDA seeds verified
@@ -45,19 +53,44 @@ pub struct Cancel<'info> {
     /// Creator's token account — receives the refund
     #[account(
         mut,
-        constraint = creator_token_account.mint == config.sour_mint,
+        constraint = creator_token_account.mint == handshake.mint,
         constraint = creator_token_account.owner == creator.key(),
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Payout stream — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
 
+    /// Stream vault — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
     pub creator: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<Cancel>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Cancel<'info>>) -> Result<()> {
     let clock = Clock::get()?;
     let handshake = &ctx.accounts.handshake;
+    require!(
+        ctx.accounts.payout_stream.is_some() == ctx.accounts.handshake.vested_payout
+            && ctx.accounts.stream_vault.is_some() == ctx.accounts.handshake.vested_payout,
+        SourError::PayoutStreamMismatch
+    );
 
     // Build PDA signer seeds
     let id_bytes = handshake.id.to_le_bytes();
@@ -70,17 +103,28 @@ pub fn handler(ctx: Context<Cancel>) -> Result<()> {
 
     // Refund full amount back to creator
     let refund_amount = ctx.accounts.vault.amount;
-    if refund_amount > 0 {
-        let transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.creator_token_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_ctx, refund_amount)?;
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+    payout.transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        refund_amount,
+    )?;
+
+    // The payout never started streaming — hand back the stream's rent
+    if let Some(stream_vault) = &ctx.accounts.stream_vault {
+        payout.close_vault(
+            &stream_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
     }
 
     // Update state
@@ -0,0 +1,52 @@
+// ============================================================================
+// Cancel Amendment — the proposer withdraws, or the counterparty rejects,
+// a pending amendment. Rent goes back to the proposer.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{Amendment, Handshake};
+use crate::errors::SourError;
+use crate::events::AmendmentCancelled;
+
+#[derive(Accounts)]
+pub struct CancelAmendment<'info> {
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [Amendment::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = amendment.bump,
+    )]
+    pub amendment: Account<'info, Amendment>,
+
+    /// The proposer — receives the amendment's rent back
+    /// CHECK: address checked against the amendment
+    #[account(mut, address = amendment.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Creator or worker
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelAmendment>) -> Result<()> {
+    let handshake = &ctx.accounts.handshake;
+    let signer_key = ctx.accounts.signer.key();
+    require!(
+        signer_key == handshake.creator || signer_key == handshake.worker,
+        SourError::NotParticipant
+    );
+
+    emit!(AmendmentCancelled {
+        handshake_id: handshake.id,
+        cancelled_by: signer_key,
+    });
+
+    msg!("Amendment for handshake #{} cancelled", handshake.id);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// ============================================================================
+// Cast Vote — a panel member rules on a dispute case
+//
+// Each drawn arbiter votes once (refund, pay, or split) before the voting
+// window closes.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{DisputeCase, DisputeRuling};
+use crate::errors::SourError;
+use crate::events::PanelVoteCast;
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [DisputeCase::SEED_PREFIX, dispute_case.handshake_id.to_le_bytes().as_ref()],
+        bump = dispute_case.bump,
+        constraint = !dispute_case.finalized @ SourError::CaseFinalized,
+        constraint = dispute_case.is_drawn() @ SourError::PanelNotDrawn,
+    )]
+    pub dispute_case: Account<'info, DisputeCase>,
+
+    /// A member of the drawn panel
+    pub arbiter: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CastVote>, ruling: DisputeRuling) -> Result<()> {
+    let worker_share_bps = ruling.worker_share_bps()?;
+
+    let clock = Clock::get()?;
+    let dispute_case = &mut ctx.accounts.dispute_case;
+    require!(
+        clock.unix_timestamp <= dispute_case.voting_ends_at,
+        SourError::VotingClosed
+    );
+
+    let arbiter = ctx.accounts.arbiter.key();
+    let seat = dispute_case
+        .panel
+        .iter_mut()
+        .find(|seat| seat.arbiter == arbiter)
+        .ok_or(SourError::NotPanelist)?;
+    require!(seat.vote.is_none(), SourError::AlreadyVoted);
+    seat.vote = Some(ruling);
+
+    let votes_cast = dispute_case.votes_cast() as u8;
+
+    emit!(PanelVoteCast {
+        handshake_id: dispute_case.handshake_id,
+        arbiter,
+        worker_share_bps,
+        votes_cast,
+    });
+
+    msg!(
+        "Dispute case #{}: {} voted {}bps to worker ({}/{})",
+        dispute_case.handshake_id,
+        arbiter,
+        worker_share_bps,
+        votes_cast,
+        dispute_case.panel.len()
+    );
+    Ok(())
+}
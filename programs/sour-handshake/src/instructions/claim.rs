@@ -0,0 +1,265 @@
+// ============================================================================
+// Claim After Review — release payment when the creator goes silent
+//
+// Once a delivery has sat unreviewed for `handshake.review_window_secs`
+// without approval or dispute, the worker (or any crank) may release the
+// open tranche. Payout uses the same Pinch split as `approve`, and the last
+// tranche likewise returns any worker collateral. Vested payouts start
+// their stream, team payouts are split and subcontract liens are paid here
+// just as on approval — both go through `settlement.rs`.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    AllowedMint, Handshake, HandshakeStatus, Milestones, PayoutStream, ProtocolConfig,
+    TeamPayees, WorkerRecord,
+};
+use crate::errors::SourError;
+use crate::events::PaymentClaimed;
+use crate::escrow::{MintTransfer, TeamPayout, VaultPayout};
+use crate::settlement::{self, SettlementAccounts};
+
+#[derive(Accounts)]
+pub struct ClaimAfterReview<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Delivered @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// Milestone schedule — required for milestone handshakes
+    #[account(
+        mut,
+        seeds = [Milestones::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        mut,
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// The escrow vault holding the escrowed tokens
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Worker's token account — receives the payment
+    #[account(
+        mut,
+        constraint = worker_token_account.mint == handshake.mint,
+        constraint = worker_token_account.owner == handshake.worker,
+    )]
+    pub worker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral vault — required when the handshake holds worker collateral
+    #[account(
+        mut,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payout stream — required when the handshake vests its payout
+    #[account(
+        mut,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — receives the worker's share of a vested payout
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Team payees — required when the worker's payout is split across a
+    /// team (payee token accounts lead the remaining accounts)
+    #[account(
+        seeds = [TeamPayees::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = team.bump,
+    )]
+    pub team: Option<Account<'info, TeamPayees>>,
+
+    /// Subcontract carved out of this handshake — required while its lien
+    /// is outstanding
+    #[account(
+        mut,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            handshake.child_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump = subcontract.bump,
+    )]
+    pub subcontract: Option<Account<'info, Handshake>>,
+
+    /// Subcontract's escrow vault — receives the lien
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.child_id.unwrap_or_default().to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub subcontract_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Worker's track record — credited when the handshake completes
+    /// (opened here if the worker has none yet)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + WorkerRecord::INIT_SPACE,
+        seeds = [WorkerRecord::SEED_PREFIX, handshake.worker.as_ref()],
+        bump,
+    )]
+    pub worker_record: Account<'info, WorkerRecord>,
+
+    /// Keepers pool — receives 30% of Pinch
+    #[account(mut)]
+    pub keepers_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Commons treasury — receives 20% of Pinch
+    #[account(mut)]
+    pub commons_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Buyback+LP treasury — receives 50% of Pinch (batched → buyback + LP)
+    #[account(mut)]
+    pub buyback_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// The worker or any crank (pays for the worker record if it is new)
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAfterReview<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake_id = ctx.accounts.handshake.id;
+    let milestone_index = ctx.accounts.handshake.current_milestone;
+
+    // Review window must have elapsed since delivery
+    let review_ends_at = ctx
+        .accounts
+        .handshake
+        .delivered_at
+        .checked_add(ctx.accounts.handshake.review_window_secs)
+        .ok_or(SourError::MathOverflow)?;
+    require!(
+        clock.unix_timestamp >= review_ends_at,
+        SourError::ReviewWindowOpen
+    );
+
+    // Build PDA signer seeds for vault authority
+    let id_bytes = handshake_id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    let accounts = ctx.accounts;
+    accounts
+        .worker_record
+        .open_if_new(accounts.handshake.worker, ctx.bumps.worker_record);
+
+    // Team handshakes: payee token accounts lead the remaining accounts
+    let (team, hook_accounts) =
+        TeamPayout::split(accounts.team.as_deref(), ctx.remaining_accounts)?;
+    let payout = VaultPayout {
+        token: MintTransfer::new(&accounts.token_program, &accounts.mint, hook_accounts),
+        vault: accounts.vault.to_account_info(),
+        vault_authority: accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+    let settled = settlement::execute(
+        SettlementAccounts {
+            config: &mut accounts.config,
+            handshake: &mut accounts.handshake,
+            milestones: accounts.milestones.as_deref_mut(),
+            allowed_mint: accounts.allowed_mint.as_deref_mut(),
+            payout: &payout,
+            worker_token_account: accounts.worker_token_account.to_account_info(),
+            team,
+            collateral_vault: accounts
+                .collateral_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            payout_stream: accounts.payout_stream.as_deref_mut(),
+            stream_vault: accounts
+                .stream_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            subcontract: accounts.subcontract.as_deref_mut(),
+            subcontract_vault: accounts
+                .subcontract_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            worker_record: &mut accounts.worker_record,
+            keepers_pool: accounts.keepers_pool.to_account_info(),
+            commons_treasury: accounts.commons_treasury.to_account_info(),
+            buyback_treasury: accounts.buyback_treasury.to_account_info(),
+        },
+        clock.unix_timestamp,
+    )?;
+    let split = settled.split;
+
+    let handshake = &accounts.handshake;
+    emit!(PaymentClaimed {
+        handshake_id: handshake.id,
+        worker: handshake.worker,
+        claimed_by: accounts.claimer.key(),
+        milestone_index,
+        completed: settled.completed,
+        amount: settled.amount,
+        pinch_total: split.pinch_total,
+        to_treasury: split.to_treasury,
+        to_keepers: split.to_keepers,
+        to_commons: split.to_commons,
+        collateral_returned: settled.collateral_returned,
+        vested: settled.vested,
+        lien_paid: settled.lien_paid,
+    });
+
+    msg!(
+        "Handshake #{} claimed after review (milestone {}). {} to worker",
+        handshake.id,
+        milestone_index,
+        split.to_worker,
+    );
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// ============================================================================
+// Close Application — an applicant withdraws a bid or, once the bounty is
+// decided, reclaims the rent of their Application PDA
+//
+// Any collateral delegation `apply` left with the vault authority is
+// revoked, so a withdrawn or losing bid leaves nothing approved behind. The
+// rent returned includes the collateral vault deposit unless the bid was
+// selected (it paid for the vault then).
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{Application, Handshake};
+use crate::errors::SourError;
+use crate::escrow::MintTransfer;
+
+#[derive(Accounts)]
+pub struct CloseApplication<'info> {
+    #[account(
+        mut,
+        close = applicant,
+        seeds = [
+            Application::SEED_PREFIX,
+            application.handshake_id.to_le_bytes().as_ref(),
+            applicant.key().as_ref(),
+        ],
+        bump = application.bump,
+    )]
+    pub application: Account<'info, Application>,
+
+    /// The handshake applied to
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, application.handshake_id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// Applicant's token account — required when the bounty asks for
+    /// collateral, so its delegation can be revoked
+    #[account(
+        mut,
+        constraint = applicant_token_account.mint == handshake.mint,
+        constraint = applicant_token_account.owner == applicant.key(),
+    )]
+    pub applicant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Escrow mint
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vaults — the collateral delegate
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub applicant: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<CloseApplication>) -> Result<()> {
+    if ctx.accounts.handshake.worker_collateral > 0 {
+        let applicant_token_account = ctx
+            .accounts
+            .applicant_token_account
+            .as_ref()
+            .ok_or(SourError::CollateralAccountRequired)?;
+        // Only our own delegation — a later approval elsewhere replaced it
+        if applicant_token_account.delegate == COption::Some(ctx.accounts.vault_authority.key()) {
+            MintTransfer::new(&ctx.accounts.token_program, &ctx.accounts.mint, &[]).revoke(
+                &applicant_token_account.to_account_info(),
+                &ctx.accounts.applicant.to_account_info(),
+            )?;
+        }
+    }
+
+    msg!(
+        "Application to handshake #{} closed",
+        ctx.accounts.application.handshake_id
+    );
+    Ok(())
+}
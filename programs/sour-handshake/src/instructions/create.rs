@@ -1,13 +1,46 @@
 // ============================================================================
-// Create Handshake — escrow $SOUR into a PDA vault
+// Create Handshake — escrow $SOUR (or an allowlisted mint) into a PDA vault
+//
+// Milestone mode: pass a non-empty `milestones` list (amounts must sum to
+// `amount`) plus the `milestones` PDA; tranches are then delivered and
+// approved one at a time.
+//
+// Arbiter: optionally name a third party (and their fee) who may resolve a
+// dispute instead of the protocol authority. The worker agrees to these
+// terms by accepting.
+//
+// Open bounty: omit the `worker` account and the first eligible signer to
+// call `accept_handshake` becomes the worker. Eligibility may require a
+// minimum number of completed handshakes and/or the `bounty_allowlist` PDA.
+// With `by_application`, workers bid via `apply` and the creator picks one.
+//
+// Worker collateral: optionally require the worker to lock an amount of the
+// escrow mint on acceptance. It is returned on approval or mutual cancel and
+// forfeited to the creator (minus the config's Commons slice) on expiry,
+// withdrawal or a dispute ruled for the creator.
+//
+// Revisions: the creator may send a delivery back up to `max_revisions`
+// times (protocol default if omitted) before rejecting it via dispute.
+//
+// Vesting: optionally pay the worker through a payout stream (linear, or
+// cliff + linear) instead of in one lump. The stream PDA and its vault are
+// opened here; approval funds them and `withdraw_vested` drains them.
+//
+// Team payout: optionally split every worker payout across up to eight
+// payees by bps share. The named worker is the team lead: accepting binds
+// the team to the split, and the lead receives the rounding remainder.
 // ============================================================================
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::{Handshake, HandshakeStatus, ProtocolConfig};
+use crate::state::{
+    AllowedMint, BountyAllowlist, BountyTerms, CreateOptions, Handshake, HandshakeInit,
+    Milestone, MilestoneInput, Milestones, PayoutStream, ProtocolConfig, TeamPayees,
+};
 use crate::errors::SourError;
 use crate::events::HandshakeCreated;
+use crate::escrow::MintTransfer;
 
 #[derive(Accounts)]
 pub struct CreateHandshake<'info> {
@@ -30,16 +63,79 @@ pub struct CreateHandshake<'info> {
     )]
     pub handshake: Account<'info, Handshake>,
 
-    /// The escrow vault — PDA-owned token account holding $SOUR
+    /// Milestone schedule — only for milestone handshakes
     #[account(
         init,
         payer = creator,
-        token::mint = sour_mint,
+        space = 8 + Milestones::INIT_SPACE,
+        seeds = [
+            Milestones::SEED_PREFIX,
+            config.handshake_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
+    /// Workers allowed to accept — only for allowlisted open bounties
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BountyAllowlist::INIT_SPACE,
+        seeds = [
+            BountyAllowlist::SEED_PREFIX,
+            config.handshake_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub bounty_allowlist: Option<Account<'info, BountyAllowlist>>,
+
+    /// Payout stream — only for handshakes with a vested payout
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PayoutStream::INIT_SPACE,
+        seeds = [
+            PayoutStream::SEED_PREFIX,
+            config.handshake_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — holds the worker's payout while it vests
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"stream_vault", config.handshake_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Team payees — only for handshakes paying a team
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + TeamPayees::INIT_SPACE,
+        seeds = [
+            TeamPayees::SEED_PREFIX,
+            config.handshake_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub team: Option<Account<'info, TeamPayees>>,
+
+    /// The escrow vault — PDA-owned token account holding the escrow mint
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
         token::authority = vault_authority,
         seeds = [b"vault", config.handshake_count.to_le_bytes().as_ref()],
         bump,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
     /// PDA authority over the vault
     /// CHECK: PDA derived from seeds, no data needed
@@ -49,77 +145,275 @@ pub struct CreateHandshake<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    /// Creator's $SOUR token account (source of escrowed funds)
+    /// Creator's token account (source of escrowed funds)
     #[account(
         mut,
-        constraint = creator_token_account.mint == config.sour_mint,
+        constraint = creator_token_account.mint == mint.key(),
         constraint = creator_token_account.owner == creator.key(),
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// The worker's wallet address (Baker B) — not signing, just referenced
+    /// The worker's wallet address (Baker B) — not signing, just referenced.
+    /// Omit for an open bounty.
     /// CHECK: any valid pubkey, validated in handler
-    pub worker: UncheckedAccount<'info>,
+    pub worker: Option<UncheckedAccount<'info>>,
+
+    /// Escrow mint — $SOUR, or any mint with an enabled allowlist entry
+    pub mint: InterfaceAccount<'info, Mint>,
 
-    /// $SOUR token mint
-    /// CHECK: validated against config
-    #[account(address = config.sour_mint)]
-    pub sour_mint: UncheckedAccount<'info>,
+    /// Allowlist entry for `mint` — omit for $SOUR escrows
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, mint.key().as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
 
     #[account(mut)]
     pub creator: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
-    ctx: Context<CreateHandshake>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateHandshake<'info>>,
     description: String,
     amount: u64,
     deadline_ts: i64,
+    milestones: Vec<MilestoneInput>,
+    options: CreateOptions,
 ) -> Result<()> {
     // Validations
     require!(amount > 0, SourError::ZeroAmount);
     require!(description.len() <= 280, SourError::DescriptionTooLong);
-    require!(
-        ctx.accounts.worker.key() != ctx.accounts.creator.key(),
-        SourError::SelfHandshake
-    );
+
+    // Worker: named up front, or left open (Pubkey::default) for a bounty
+    let worker = ctx
+        .accounts
+        .worker
+        .as_ref()
+        .map_or(Pubkey::default(), |worker| worker.key());
+    require!(worker != ctx.accounts.creator.key(), SourError::SelfHandshake);
 
     let clock = Clock::get()?;
     require!(deadline_ts > clock.unix_timestamp, SourError::DeadlineInPast);
 
-    // Transfer $SOUR from creator to vault
-    let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.creator_token_account.to_account_info(),
-            to: ctx.accounts.vault.to_account_info(),
-            authority: ctx.accounts.creator.to_account_info(),
-        },
+    // Escrow mint: $SOUR, or an enabled allowlist entry
+    let mint_key = ctx.accounts.mint.key();
+    if mint_key != ctx.accounts.config.sour_mint {
+        let allowed_mint = ctx
+            .accounts
+            .allowed_mint
+            .as_ref()
+            .ok_or(SourError::MintNotAllowed)?;
+        require!(allowed_mint.enabled, SourError::MintNotAllowed);
+    } else {
+        require!(
+            ctx.accounts.allowed_mint.is_none(),
+            SourError::AllowedMintMismatch
+        );
+    }
+
+    // Review window: per-handshake override or the protocol default
+    let review_window_secs = options
+        .review_window_secs
+        .unwrap_or(ctx.accounts.config.review_window_secs);
+    require!(
+        ProtocolConfig::is_valid_review_window(review_window_secs),
+        SourError::InvalidReviewWindow
     );
-    token::transfer(transfer_ctx, amount)?;
+
+    // Revision limit: per-handshake override or the default, capped
+    let max_revisions = options
+        .max_revisions
+        .unwrap_or(Handshake::DEFAULT_MAX_REVISIONS);
+    require!(
+        max_revisions <= Handshake::MAX_REVISIONS,
+        SourError::InvalidRevisionLimit
+    );
+
+    // Vesting: stream accounts present iff vesting terms were given; not
+    // combined with milestones, which already pay out in stages
+    require!(
+        ctx.accounts.payout_stream.is_some() == options.vesting.is_some()
+            && ctx.accounts.stream_vault.is_some() == options.vesting.is_some(),
+        SourError::PayoutStreamMismatch
+    );
+    if let Some(terms) = &options.vesting {
+        require!(
+            PayoutStream::is_valid_terms(terms) && milestones.is_empty(),
+            SourError::InvalidVestingTerms
+        );
+    }
+
+    // Team payout: account present iff payees were given; led by a named
+    // worker and not combined with a vested payout
+    require!(
+        ctx.accounts.team.is_some() == options.team.is_some(),
+        SourError::TeamMismatch
+    );
+    if let Some(payees) = &options.team {
+        require!(
+            worker != Pubkey::default()
+                && options.vesting.is_none()
+                && TeamPayees::is_valid(payees),
+            SourError::InvalidTeam
+        );
+    }
+
+    // Arbiter: a third party, with a capped fee
+    let (arbiter, arbiter_fee_bps) = match options.arbiter {
+        Some(terms) => {
+            require!(
+                terms.arbiter != Pubkey::default()
+                    && terms.arbiter != ctx.accounts.creator.key()
+                    && terms.arbiter != worker
+                    && terms.fee_bps <= Handshake::MAX_ARBITER_FEE_BPS,
+                SourError::InvalidArbiter
+            );
+            (terms.arbiter, terms.fee_bps)
+        }
+        None => (Pubkey::default(), 0),
+    };
+
+    // Bounty eligibility: only for open handshakes; allowlist account
+    // present iff workers were listed
+    let bounty = options.bounty.unwrap_or(BountyTerms {
+        min_completed: 0,
+        allowlist: Vec::new(),
+        by_application: false,
+    });
+    let restricted =
+        bounty.min_completed > 0 || !bounty.allowlist.is_empty() || bounty.by_application;
+    require!(
+        (worker == Pubkey::default() || !restricted)
+            && bounty.allowlist.is_empty() == ctx.accounts.bounty_allowlist.is_none()
+            && bounty.allowlist.len() <= BountyAllowlist::MAX_WORKERS
+            && !bounty.allowlist.contains(&ctx.accounts.creator.key()),
+        SourError::InvalidBountyTerms
+    );
+
+    // Milestone schedule: account present iff tranches were given
+    require!(
+        milestones.is_empty() == ctx.accounts.milestones.is_none(),
+        SourError::MilestonesMismatch
+    );
+    require!(
+        milestones.len() <= Milestones::MAX_MILESTONES,
+        SourError::TooManyMilestones
+    );
+
+    let mut schedule = Vec::with_capacity(milestones.len());
+    let mut tranche_sum: u64 = 0;
+    let mut previous_deadline = clock.unix_timestamp;
+    for input in &milestones {
+        require!(input.amount > 0, SourError::MilestoneAmountMismatch);
+        let tranche_deadline = input.deadline_ts.unwrap_or(deadline_ts);
+        require!(
+            tranche_deadline > clock.unix_timestamp
+                && tranche_deadline >= previous_deadline
+                && tranche_deadline <= deadline_ts,
+            SourError::InvalidMilestoneDeadline
+        );
+        previous_deadline = tranche_deadline;
+        tranche_sum = tranche_sum
+            .checked_add(input.amount)
+            .ok_or(SourError::MathOverflow)?;
+        schedule.push(Milestone {
+            amount: input.amount,
+            deadline_ts: tranche_deadline,
+            delivered_at: 0,
+            paid_at: 0,
+        });
+    }
+    if !schedule.is_empty() {
+        require!(tranche_sum == amount, SourError::MilestoneAmountMismatch);
+    }
+
+    // Transfer escrow from creator to vault
+    MintTransfer::new(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.remaining_accounts,
+    )
+    .transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &[],
+        amount,
+    )?;
+
+    // Fee-on-transfer mints deliver less than was sent — escrow what arrived
+    ctx.accounts.vault.reload()?;
+    let received = ctx.accounts.vault.amount;
+    require!(received > 0, SourError::ZeroAmount);
+    let shortfall = amount.checked_sub(received).ok_or(SourError::MathOverflow)?;
+    if shortfall > 0 {
+        // The transfer fee comes out of the final tranche
+        if let Some(last) = schedule.last_mut() {
+            require!(last.amount > shortfall, SourError::MilestoneAmountMismatch);
+            last.amount -= shortfall;
+        }
+        msg!("Transfer fee withheld {}; escrowing {}", shortfall, received);
+    }
+    let amount = received;
 
     // Initialize handshake state
     let handshake = &mut ctx.accounts.handshake;
     let config = &mut ctx.accounts.config;
 
-    handshake.id = config.handshake_count;
-    handshake.creator = ctx.accounts.creator.key();
-    handshake.worker = ctx.accounts.worker.key();
-    handshake.amount = amount;
-    handshake.description = description.clone();
-    handshake.status = HandshakeStatus::Created;
-    handshake.created_at = clock.unix_timestamp;
-    handshake.deadline_ts = deadline_ts;
-    handshake.accepted_at = 0;
-    handshake.delivered_at = 0;
-    handshake.resolved_at = 0;
-    handshake.disputed_by = Pubkey::default();
-    handshake.vault_bump = ctx.bumps.vault_authority;
-    handshake.bump = ctx.bumps.handshake;
+    handshake.init(HandshakeInit {
+        id: config.handshake_count,
+        creator: ctx.accounts.creator.key(),
+        worker,
+        mint: mint_key,
+        amount,
+        description: description.clone(),
+        created_at: clock.unix_timestamp,
+        deadline_ts,
+        milestone_count: schedule.len() as u8,
+        review_window_secs,
+        arbiter,
+        arbiter_fee_bps,
+        min_completed: bounty.min_completed,
+        allowlisted: !bounty.allowlist.is_empty(),
+        by_application: bounty.by_application,
+        worker_collateral: options.worker_collateral.unwrap_or(0),
+        max_revisions,
+        vested_payout: options.vesting.is_some(),
+        team_payout: options.team.is_some(),
+        vault_bump: ctx.bumps.vault_authority,
+        bump: ctx.bumps.handshake,
+        ..HandshakeInit::default()
+    });
+
+    if let Some(milestones_account) = ctx.accounts.milestones.as_mut() {
+        milestones_account.handshake_id = handshake.id;
+        milestones_account.items = schedule;
+        milestones_account.bump = ctx.bumps.milestones.ok_or(SourError::MilestonesMismatch)?;
+    }
+
+    if let (Some(stream), Some(terms)) = (ctx.accounts.payout_stream.as_mut(), &options.vesting) {
+        stream.handshake_id = handshake.id;
+        stream.duration_secs = terms.duration_secs;
+        stream.cliff_secs = terms.cliff_secs;
+        stream.bump = ctx.bumps.payout_stream.ok_or(SourError::PayoutStreamMismatch)?;
+    }
+
+    if let (Some(team), Some(payees)) = (ctx.accounts.team.as_mut(), &options.team) {
+        team.handshake_id = handshake.id;
+        team.lead = worker;
+        team.payees = payees.clone();
+        team.bump = ctx.bumps.team.ok_or(SourError::TeamMismatch)?;
+    }
+
+    if let Some(allowlist) = ctx.accounts.bounty_allowlist.as_mut() {
+        allowlist.handshake_id = handshake.id;
+        allowlist.workers = bounty.allowlist;
+        allowlist.bump = ctx.bumps.bounty_allowlist.ok_or(SourError::InvalidBountyTerms)?;
+    }
 
     // Increment global counter
     config.handshake_count = config
@@ -131,15 +425,28 @@ pub fn handler(
         handshake_id: handshake.id,
         creator: handshake.creator,
         worker: handshake.worker,
+        mint: handshake.mint,
         amount,
         deadline_ts,
         description,
+        milestone_count: handshake.milestone_count,
+        arbiter,
+        arbiter_fee_bps,
+        min_completed: handshake.min_completed,
+        allowlisted: handshake.allowlisted,
+        by_application: handshake.by_application,
+        worker_collateral: handshake.worker_collateral,
+        max_revisions: handshake.max_revisions,
+        vesting_duration_secs: options.vesting.as_ref().map_or(0, |terms| terms.duration_secs),
+        vesting_cliff_secs: options.vesting.as_ref().map_or(0, |terms| terms.cliff_secs),
+        team_size: options.team.as_ref().map_or(0, |payees| payees.len() as u8),
     });
 
     msg!(
-        "Handshake #{} created: {} $SOUR escrowed",
+        "Handshake #{} created: {} of mint {} escrowed",
         handshake.id,
-        amount
+        amount,
+        handshake.mint
     );
     Ok(())
 }
@@ -0,0 +1,129 @@
+// ============================================================================
+// Create Invoice — Baker B bills a client before any funds move
+//
+// The worker names the client, mint, amount, deadline and description. The
+// invoice holds no tokens; the client turns it into an accepted handshake
+// with `fund_invoice`. Until then the worker may revoke it, and once the
+// deadline passes it can only be cleared. Invoices have their own ID
+// counter; the handshake gets a regular ID when funded.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{AllowedMint, Invoice, ProtocolConfig};
+use crate::errors::SourError;
+use crate::events::InvoiceCreated;
+
+#[derive(Accounts)]
+pub struct CreateInvoice<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = worker,
+        space = 8 + Invoice::INIT_SPACE,
+        seeds = [
+            Invoice::SEED_PREFIX,
+            config.invoice_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    /// The client's wallet address (Baker A) — not signing, just referenced
+    /// CHECK: any valid pubkey, validated in handler
+    pub creator: UncheckedAccount<'info>,
+
+    /// Mint the invoice is payable in — $SOUR, or an enabled allowlisted mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Allowlist entry for `mint` — omit for $SOUR invoices
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, mint.key().as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    #[account(mut)]
+    pub worker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateInvoice>,
+    description: String,
+    amount: u64,
+    deadline_ts: i64,
+) -> Result<()> {
+    require!(amount > 0, SourError::ZeroAmount);
+    require!(description.len() <= 280, SourError::DescriptionTooLong);
+
+    let creator = ctx.accounts.creator.key();
+    require!(
+        creator != ctx.accounts.worker.key(),
+        SourError::SelfHandshake
+    );
+
+    let clock = Clock::get()?;
+    require!(deadline_ts > clock.unix_timestamp, SourError::DeadlineInPast);
+
+    // Payable mint: $SOUR, or an enabled allowlist entry
+    let mint_key = ctx.accounts.mint.key();
+    if mint_key != ctx.accounts.config.sour_mint {
+        let allowed_mint = ctx
+            .accounts
+            .allowed_mint
+            .as_ref()
+            .ok_or(SourError::MintNotAllowed)?;
+        require!(allowed_mint.enabled, SourError::MintNotAllowed);
+    } else {
+        require!(
+            ctx.accounts.allowed_mint.is_none(),
+            SourError::AllowedMintMismatch
+        );
+    }
+
+    let invoice = &mut ctx.accounts.invoice;
+    let config = &mut ctx.accounts.config;
+
+    invoice.id = config.invoice_count;
+    invoice.worker = ctx.accounts.worker.key();
+    invoice.creator = creator;
+    invoice.mint = mint_key;
+    invoice.amount = amount;
+    invoice.deadline_ts = deadline_ts;
+    invoice.description = description.clone();
+    invoice.created_at = clock.unix_timestamp;
+    invoice.bump = ctx.bumps.invoice;
+
+    config.invoice_count = config
+        .invoice_count
+        .checked_add(1)
+        .ok_or(SourError::MathOverflow)?;
+
+    emit!(InvoiceCreated {
+        invoice_id: invoice.id,
+        worker: invoice.worker,
+        creator,
+        mint: mint_key,
+        amount,
+        deadline_ts,
+        description,
+    });
+
+    msg!(
+        "Invoice #{} issued: {} of mint {} billed to {}",
+        invoice.id,
+        amount,
+        mint_key,
+        creator
+    );
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// ============================================================================
+// Create Recurring — Baker A opens a retainer paid period by period
+//
+// Defines a per-period amount, a period length and a maximum number of
+// periods, and pre-funds the first `funded_periods` of them into the
+// recurring vault (more can be added with `fund_recurring`). Periods start
+// back to back once the worker accepts. Recurring handshakes have their own
+// ID counter, so a retainer uses one ID however many periods it runs.
+//
+// Fee-on-transfer mints: each funded period escrows an even share of what
+// actually reached the vault, so the transfer fee is spread across them.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{AllowedMint, ProtocolConfig, RecurringHandshake, RecurringStatus};
+use crate::errors::SourError;
+use crate::events::RecurringCreated;
+use crate::escrow::MintTransfer;
+
+#[derive(Accounts)]
+pub struct CreateRecurring<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + RecurringHandshake::INIT_SPACE,
+        seeds = [
+            RecurringHandshake::SEED_PREFIX,
+            config.recurring_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub recurring: Account<'info, RecurringHandshake>,
+
+    /// The recurring vault — holds the funded, unsettled periods
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"recurring_vault", config.recurring_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// PDA authority over the recurring vault
+    /// CHECK: PDA derived from seeds, no data needed
+    #[account(
+        seeds = [b"recurring_auth", config.recurring_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account (source of the funded periods)
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == mint.key(),
+        constraint = creator_token_account.owner == creator.key(),
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The worker's wallet address (Baker B) — not signing, just referenced
+    /// CHECK: any valid pubkey, validated in handler
+    pub worker: UncheckedAccount<'info>,
+
+    /// Escrow mint — $SOUR, or any mint with an enabled allowlist entry
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Allowlist entry for `mint` — omit for $SOUR escrows
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, mint.key().as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateRecurring<'info>>,
+    description: String,
+    amount_per_period: u64,
+    period_secs: i64,
+    max_periods: u16,
+    funded_periods: u16,
+) -> Result<()> {
+    require!(description.len() <= 280, SourError::DescriptionTooLong);
+    require!(
+        RecurringHandshake::is_valid_terms(amount_per_period, period_secs, max_periods),
+        SourError::InvalidRecurringTerms
+    );
+    require!(
+        funded_periods > 0 && funded_periods <= max_periods,
+        SourError::TooManyPeriods
+    );
+    let worker = ctx.accounts.worker.key();
+    require!(worker != ctx.accounts.creator.key(), SourError::SelfHandshake);
+
+    // Escrow mint: $SOUR, or an enabled allowlist entry
+    let mint_key = ctx.accounts.mint.key();
+    if mint_key != ctx.accounts.config.sour_mint {
+        let allowed_mint = ctx
+            .accounts
+            .allowed_mint
+            .as_ref()
+            .ok_or(SourError::MintNotAllowed)?;
+        require!(allowed_mint.enabled, SourError::MintNotAllowed);
+    } else {
+        require!(
+            ctx.accounts.allowed_mint.is_none(),
+            SourError::AllowedMintMismatch
+        );
+    }
+
+    let clock = Clock::get()?;
+    let recurring = &mut ctx.accounts.recurring;
+    let config = &mut ctx.accounts.config;
+
+    recurring.id = config.recurring_count;
+    recurring.creator = ctx.accounts.creator.key();
+    recurring.worker = worker;
+    recurring.mint = mint_key;
+    recurring.description = description.clone();
+    recurring.amount_per_period = amount_per_period;
+    recurring.period_secs = period_secs;
+    recurring.end_periods = max_periods;
+    recurring.funded_periods = 0;
+    recurring.settled_periods = 0;
+    recurring.periods = Vec::new();
+    recurring.status = RecurringStatus::Created;
+    recurring.created_at = clock.unix_timestamp;
+    recurring.started_at = 0;
+    recurring.delivery_hash = [0; 32];
+    recurring.vault_bump = ctx.bumps.vault_authority;
+    recurring.bump = ctx.bumps.recurring;
+
+    // Pre-fund the first periods
+    MintTransfer::new(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.remaining_accounts,
+    )
+    .transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &[],
+        recurring.amount_for(funded_periods)?,
+    )?;
+    ctx.accounts.vault.reload()?;
+    recurring.fund_periods(funded_periods, ctx.accounts.vault.amount)?;
+
+    config.recurring_count = config
+        .recurring_count
+        .checked_add(1)
+        .ok_or(SourError::MathOverflow)?;
+
+    emit!(RecurringCreated {
+        recurring_id: recurring.id,
+        creator: recurring.creator,
+        worker,
+        mint: mint_key,
+        amount_per_period,
+        period_secs,
+        max_periods,
+        funded_periods,
+        description,
+    });
+
+    msg!(
+        "Recurring handshake #{} created: {} x {} periods ({} funded)",
+        recurring.id,
+        amount_per_period,
+        max_periods,
+        funded_periods
+    );
+    Ok(())
+}
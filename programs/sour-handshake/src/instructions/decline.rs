@@ -0,0 +1,139 @@
+// ============================================================================
+// Decline Handshake — the named worker turns the job down before accepting
+//
+// The escrow goes straight back to the creator, no need to wait for them
+// to cancel. A vested handshake's payout stream is closed to the creator.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{Handshake, HandshakeStatus, PayoutStream};
+use crate::errors::SourError;
+use crate::events::HandshakeDeclined;
+use crate::escrow::{MintTransfer, VaultPayout};
+
+#[derive(Accounts)]
+pub struct DeclineHandshake<'info> {
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Created @ SourError::InvalidStatus,
+        constraint = handshake.worker == worker.key() @ SourError::NotWorker,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// The escrow vault
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account — receives the refund
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Payout stream — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator — receives the rent of the accounts closed here
+    /// CHECK: must match handshake.creator
+    #[account(mut, address = handshake.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub worker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, DeclineHandshake<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.payout_stream.is_some() == ctx.accounts.handshake.vested_payout
+            && ctx.accounts.stream_vault.is_some() == ctx.accounts.handshake.vested_payout,
+        SourError::PayoutStreamMismatch
+    );
+
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    // Refund full amount back to creator
+    let refunded = ctx.accounts.vault.amount;
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+    payout.transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        refunded,
+    )?;
+
+    // The payout never started streaming — hand back the stream's rent
+    if let Some(stream_vault) = &ctx.accounts.stream_vault {
+        payout.close_vault(
+            &stream_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
+    let handshake = &mut ctx.accounts.handshake;
+    handshake.status = HandshakeStatus::Cancelled;
+    handshake.resolved_at = clock.unix_timestamp;
+
+    emit!(HandshakeDeclined {
+        handshake_id: handshake.id,
+        worker: handshake.worker,
+        refunded,
+        declined_at: handshake.resolved_at,
+    });
+
+    msg!("Handshake #{} declined by worker. Full refund.", handshake.id);
+    Ok(())
+}
@@ -1,10 +1,14 @@
 // ============================================================================
-// Deliver — Baker B marks the work as delivered
+// Deliver — Baker B marks the work (or the open milestone) as delivered
+//
+// The delivery commits to a hash of what was handed over, plus an optional
+// URI to fetch it from. The latest one is kept on the handshake; each
+// delivery (per milestone and revision) is in `WorkDelivered`.
 // ============================================================================
 
 use anchor_lang::prelude::*;
 
-use crate::state::{Handshake, HandshakeStatus};
+use crate::state::{Handshake, HandshakeStatus, Milestones};
 use crate::errors::SourError;
 use crate::events::WorkDelivered;
 
@@ -19,22 +23,52 @@ pub struct Deliver<'info> {
     )]
     pub handshake: Account<'info, Handshake>,
 
+    /// Milestone schedule — required for milestone handshakes
+    #[account(
+        mut,
+        seeds = [Milestones::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
     pub worker: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<Deliver>) -> Result<()> {
+pub fn handler(ctx: Context<Deliver>, content_hash: [u8; 32], uri: Option<String>) -> Result<()> {
     let clock = Clock::get()?;
+    let uri = uri.unwrap_or_default();
+    require!(
+        uri.len() <= Handshake::MAX_DELIVERY_URI_LEN,
+        SourError::DeliveryUriTooLong
+    );
+
     let handshake = &mut ctx.accounts.handshake;
 
+    // Validates the schedule account for milestone handshakes
+    handshake.current_milestone(ctx.accounts.milestones.as_deref())?;
+    if let Some(milestones) = ctx.accounts.milestones.as_mut() {
+        milestones.items[handshake.current_milestone as usize].delivered_at = clock.unix_timestamp;
+    }
+
     handshake.status = HandshakeStatus::Delivered;
     handshake.delivered_at = clock.unix_timestamp;
+    handshake.delivery_hash = content_hash;
+    handshake.delivery_uri = uri.clone();
 
     emit!(WorkDelivered {
         handshake_id: handshake.id,
         worker: handshake.worker,
+        milestone_index: handshake.current_milestone,
+        revision: handshake.revision_count,
+        content_hash,
+        uri,
         delivered_at: handshake.delivered_at,
     });
 
-    msg!("Handshake #{} — work delivered", handshake.id);
+    msg!(
+        "Handshake #{} — work delivered (milestone {})",
+        handshake.id,
+        handshake.current_milestone
+    );
     Ok(())
 }
@@ -0,0 +1,60 @@
+// ============================================================================
+// Deliver Period — Baker B marks the running period's work as delivered
+//
+// Only while the period is running (between its start and the next
+// period's), and only for a funded period. Delivery is tracked per period,
+// so earlier periods still awaiting settlement do not hold up the next.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{RecurringHandshake, RecurringStatus};
+use crate::errors::SourError;
+use crate::events::PeriodDelivered;
+
+#[derive(Accounts)]
+pub struct DeliverPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [RecurringHandshake::SEED_PREFIX, recurring.id.to_le_bytes().as_ref()],
+        bump = recurring.bump,
+        constraint = recurring.status == RecurringStatus::Active @ SourError::InvalidStatus,
+        constraint = recurring.worker == worker.key() @ SourError::NotWorker,
+    )]
+    pub recurring: Account<'info, RecurringHandshake>,
+
+    pub worker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DeliverPeriod>, content_hash: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+    let recurring = &mut ctx.accounts.recurring;
+    let period = recurring.period_at(clock.unix_timestamp);
+
+    // An early-approved period has already settled
+    require!(
+        period >= recurring.settled_periods,
+        SourError::PeriodNotRunning
+    );
+    require!(
+        recurring.period(period)?.delivered_at == 0,
+        SourError::InvalidStatus
+    );
+
+    recurring.periods[period as usize].delivered_at = clock.unix_timestamp;
+    recurring.delivery_hash = content_hash;
+
+    emit!(PeriodDelivered {
+        recurring_id: recurring.id,
+        period,
+        content_hash,
+        delivered_at: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Recurring handshake #{} — period {} delivered",
+        recurring.id,
+        period
+    );
+    Ok(())
+}
@@ -1,12 +1,23 @@
 // ============================================================================
 // Dispute — either party raises a dispute
+//
+// Also opens the (empty) evidence log both sides append to with
+// `submit_evidence`; the disputer pays its rent.
+//
+// If the escrow mint has a dispute bond configured, the disputer deposits
+// it into the handshake's bond vault. The other party may match it with
+// `match_dispute_bond`; the ruling returns the winner's bond and sends the
+// loser's to the Commons treasury.
 // ============================================================================
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::{Handshake, HandshakeStatus, ProtocolConfig};
+use crate::state::{AllowedMint, EvidenceLog, Handshake, HandshakeStatus, ProtocolConfig};
 use crate::errors::SourError;
 use crate::events::HandshakeDisputed;
+use crate::escrow::MintTransfer;
+use crate::pinch::FeeTerms;
 
 #[derive(Accounts)]
 pub struct Dispute<'info> {
@@ -29,24 +40,120 @@ pub struct Dispute<'info> {
     )]
     pub handshake: Account<'info, Handshake>,
 
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// Evidence log for this dispute
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + EvidenceLog::INIT_SPACE,
+        seeds = [EvidenceLog::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub evidence_log: Account<'info, EvidenceLog>,
+
+    /// Bond vault — holds both parties' dispute bonds until the ruling
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"bond_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint — bonds are posted in the same mint
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the escrow and bond vaults
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Disputer's token account — source of the bond (omit if no bond is
+    /// configured for the mint)
+    #[account(
+        mut,
+        constraint = signer_token_account.mint == handshake.mint,
+        constraint = signer_token_account.owner == signer.key(),
+    )]
+    pub signer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     /// Must be either creator or worker
+    #[account(mut)]
     pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Dispute>) -> Result<()> {
-    let handshake = &mut ctx.accounts.handshake;
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Dispute<'info>>) -> Result<()> {
     let signer_key = ctx.accounts.signer.key();
 
     // Only creator or worker can dispute
     require!(
-        signer_key == handshake.creator || signer_key == handshake.worker,
+        signer_key == ctx.accounts.handshake.creator || signer_key == ctx.accounts.handshake.worker,
         SourError::NotParticipant
     );
 
     let clock = Clock::get()?;
 
+    // -----------------------------------------------------------------------
+    // Post the dispute bond configured for the escrow mint
+    // -----------------------------------------------------------------------
+    let bond = FeeTerms::for_mint(
+        &ctx.accounts.handshake.mint,
+        &ctx.accounts.config,
+        ctx.accounts.allowed_mint.as_deref(),
+    )?
+    .dispute_bond;
+
+    let mut bond_posted = 0;
+    if bond > 0 {
+        let signer_token_account = ctx
+            .accounts
+            .signer_token_account
+            .as_ref()
+            .ok_or(SourError::BondRequired)?;
+        MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        )
+        .transfer(
+            &signer_token_account.to_account_info(),
+            &ctx.accounts.bond_vault.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &[],
+            bond,
+        )?;
+
+        // Fee-on-transfer mints deliver less — record what arrived
+        ctx.accounts.bond_vault.reload()?;
+        bond_posted = ctx.accounts.bond_vault.amount;
+    }
+
+    let handshake = &mut ctx.accounts.handshake;
     handshake.status = HandshakeStatus::Disputed;
     handshake.disputed_by = signer_key;
+    handshake.dispute_bond = bond_posted;
+    handshake.counter_bond = 0;
+
+    let evidence_log = &mut ctx.accounts.evidence_log;
+    evidence_log.handshake_id = handshake.id;
+    evidence_log.entries = Vec::new();
+    evidence_log.closed = false;
+    evidence_log.bump = ctx.bumps.evidence_log;
 
     // Update global stats
     let config = &mut ctx.accounts.config;
@@ -58,9 +165,15 @@ pub fn handler(ctx: Context<Dispute>) -> Result<()> {
     emit!(HandshakeDisputed {
         handshake_id: handshake.id,
         disputed_by: signer_key,
+        bond: bond_posted,
         disputed_at: clock.unix_timestamp,
     });
 
-    msg!("Handshake #{} disputed by {}", handshake.id, signer_key);
+    msg!(
+        "Handshake #{} disputed by {} (bond: {})",
+        handshake.id,
+        signer_key,
+        bond_posted
+    );
     Ok(())
 }
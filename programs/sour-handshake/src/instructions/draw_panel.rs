@@ -0,0 +1,149 @@
+// ============================================================================
+// Draw Panel — seat the arbiters for an opened dispute case
+//
+// Permissionless. The draw is seeded with the hash of the first block at or
+// after the case's `draw_slot`, read from the SlotHashes sysvar. That hash
+// did not exist when the case was opened, so neither party could steer the
+// draw; once it exists, whoever calls this gets the same panel.
+//
+// SlotHashes only keeps the last 512 slots. If the draw slot has fallen out
+// of the window, the case is re-armed to a fresh future slot instead.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+use crate::state::{ArbiterRegistry, DisputeCase, Handshake, HandshakeStatus, PanelSeat};
+use crate::errors::SourError;
+use crate::events::{DisputeCaseOpened, PanelDrawn};
+
+#[derive(Accounts)]
+pub struct DrawPanel<'info> {
+    #[account(
+        seeds = [ArbiterRegistry::SEED_PREFIX],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Disputed @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        seeds = [DisputeCase::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = dispute_case.bump,
+        constraint = !dispute_case.is_drawn() @ SourError::PanelAlreadyDrawn,
+    )]
+    pub dispute_case: Account<'info, DisputeCase>,
+
+    /// CHECK: the SlotHashes sysvar, read raw (too large to deserialize)
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+/// Hash of the earliest block at or after `slot`, or `None` if SlotHashes
+/// no longer covers it. Layout: u64 count, then (slot u64, hash [u8; 32])
+/// entries, newest first.
+fn block_hash_from(data: &[u8], slot: u64) -> Result<Option<[u8; 32]>> {
+    const ENTRY: usize = 8 + 32;
+    let read_u64 = |bytes: &[u8]| -> Result<u64> {
+        Ok(u64::from_le_bytes(
+            bytes.try_into().map_err(|_| ProgramError::InvalidAccountData)?,
+        ))
+    };
+    let count = read_u64(data.get(..8).ok_or(ProgramError::InvalidAccountData)?)? as usize;
+
+    let mut found = None;
+    for i in 0..count {
+        let entry = data
+            .get(8 + i * ENTRY..8 + (i + 1) * ENTRY)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let entry_slot = read_u64(&entry[..8])?;
+        if entry_slot < slot {
+            // The previous entry (if any) is the first block after `slot`
+            return found.map(Some).ok_or(SourError::DrawSlotPending.into());
+        }
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&entry[8..]);
+        if entry_slot == slot {
+            return Ok(Some(hash));
+        }
+        found = Some(hash);
+    }
+    // Every entry is newer than `slot` — it has aged out of the window
+    Ok(None)
+}
+
+pub fn handler(ctx: Context<DrawPanel>) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake = &ctx.accounts.handshake;
+    let dispute_case = &mut ctx.accounts.dispute_case;
+    require!(clock.slot > dispute_case.draw_slot, SourError::DrawSlotPending);
+
+    let block_hash = {
+        let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        block_hash_from(&data, dispute_case.draw_slot)?
+    };
+
+    let Some(block_hash) = block_hash else {
+        let draw_slot = clock
+            .slot
+            .checked_add(DisputeCase::DRAW_DELAY_SLOTS)
+            .ok_or(SourError::MathOverflow)?;
+        dispute_case.draw_slot = draw_slot;
+
+        emit!(DisputeCaseOpened {
+            handshake_id: handshake.id,
+            draw_slot,
+        });
+
+        msg!(
+            "Dispute case #{}: draw slot aged out, re-armed to slot {}",
+            handshake.id,
+            draw_slot
+        );
+        return Ok(());
+    };
+
+    let digest = hashv(&[handshake.id.to_le_bytes().as_ref(), block_hash.as_ref()]);
+    let mut seed_bytes = [0u8; 8];
+    seed_bytes.copy_from_slice(&digest.to_bytes()[..8]);
+    let registry = &ctx.accounts.registry;
+    let panel = registry.draw_panel(
+        u64::from_le_bytes(seed_bytes),
+        &[handshake.creator, handshake.worker, handshake.arbiter],
+    )?;
+
+    let voting_ends_at = clock
+        .unix_timestamp
+        .checked_add(registry.voting_window_secs)
+        .ok_or(SourError::MathOverflow)?;
+
+    dispute_case.panel = panel
+        .iter()
+        .map(|arbiter| PanelSeat {
+            arbiter: *arbiter,
+            vote: None,
+        })
+        .collect();
+    dispute_case.voting_ends_at = voting_ends_at;
+
+    emit!(PanelDrawn {
+        handshake_id: handshake.id,
+        panel,
+        voting_ends_at,
+    });
+
+    msg!(
+        "Dispute case #{}: {} arbiters drawn, voting until {}",
+        handshake.id,
+        dispute_case.panel.len(),
+        voting_ends_at
+    );
+    Ok(())
+}
@@ -0,0 +1,301 @@
+// ============================================================================
+// Expire Handshake — permissionless crank once the deadline has passed
+//
+// Applies to handshakes still in Created (never accepted) or Accepted
+// (never delivered). The escrow goes back to the creator; whoever cranks
+// the expiry may claim a small tip (config.crank_tip_bps) out of it.
+//
+// Milestone handshakes expire on the open tranche's deadline; only the
+// unpaid remainder is refunded.
+//
+// An accepted handshake that expires forfeits the worker's collateral to
+// the creator, minus the config's Commons slice.
+//
+// The collateral vault and a vested handshake's payout stream are closed,
+// their rent returned to the creator.
+//
+// A parent's subcontract lien is released (the worker earned nothing to pay
+// it from) and the subcontract cancelled.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    AllowedMint, Handshake, HandshakeStatus, Milestones, PayoutStream, ProtocolConfig,
+};
+use crate::errors::SourError;
+use crate::events::HandshakeExpired;
+use crate::pinch::{bps_of, collateral_commons};
+use crate::escrow::{MintTransfer, VaultPayout};
+use crate::settlement::settle_lien;
+
+#[derive(Accounts)]
+pub struct ExpireHandshake<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = (
+            handshake.status == HandshakeStatus::Created ||
+            handshake.status == HandshakeStatus::Accepted
+        ) @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// Milestone schedule — required for milestone handshakes
+    #[account(
+        seeds = [Milestones::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
+    /// The escrow vault
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Allowlist entry for the escrow mint — only needed (for non-$SOUR
+    /// escrows) when collateral is forfeited with a Commons slice
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account — receives the refund
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral vault — required when the handshake holds worker
+    /// collateral; closed to the creator
+    #[account(
+        mut,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Subcontract carved out of this handshake — required while its lien
+    /// is outstanding
+    #[account(
+        mut,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            handshake.child_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump = subcontract.bump,
+    )]
+    pub subcontract: Option<Account<'info, Handshake>>,
+
+    /// Commons treasury — receives its slice of forfeited collateral
+    #[account(mut)]
+    pub commons_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payout stream — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator — receives the rent of the accounts closed here
+    /// CHECK: must match handshake.creator
+    #[account(mut, address = handshake.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Cranker's token account — receives the tip (omit to skip the tip)
+    #[account(
+        mut,
+        constraint = cranker_token_account.mint == handshake.mint,
+        constraint = cranker_token_account.owner == cranker.key(),
+    )]
+    pub cranker_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Anyone — bots, the creator, or the worker
+    pub cranker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExpireHandshake<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake = &ctx.accounts.handshake;
+
+    let deadline_ts = handshake.current_deadline(ctx.accounts.milestones.as_deref())?;
+    require!(
+        clock.unix_timestamp > deadline_ts,
+        SourError::DeadlineNotReached
+    );
+    require!(
+        ctx.accounts.collateral_vault.is_some() == (handshake.collateral_held > 0),
+        SourError::CollateralVaultMismatch
+    );
+    require!(
+        ctx.accounts.payout_stream.is_some() == handshake.vested_payout
+            && ctx.accounts.stream_vault.is_some() == handshake.vested_payout,
+        SourError::PayoutStreamMismatch
+    );
+
+    // -----------------------------------------------------------------------
+    // Split vault balance into crank tip + creator refund
+    // -----------------------------------------------------------------------
+    let vault_balance = ctx.accounts.vault.amount;
+    let cranker_key = ctx.accounts.cranker.key();
+
+    // No tip when the creator cranks their own refund
+    let crank_tip = if ctx.accounts.cranker_token_account.is_some()
+        && cranker_key != handshake.creator
+    {
+        bps_of(vault_balance, ctx.accounts.config.crank_tip_bps)?
+    } else {
+        0
+    };
+
+    let refund_amount = vault_balance
+        .checked_sub(crank_tip)
+        .ok_or(SourError::MathOverflow)?;
+
+    // Build PDA signer seeds
+    let id_bytes = handshake.id.to_le_bytes();
+    let vault_bump = handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+
+    // -----------------------------------------------------------------------
+    // 1. Pay the crank tip
+    // -----------------------------------------------------------------------
+    if let Some(cranker_token_account) = &ctx.accounts.cranker_token_account {
+        payout.transfer(&cranker_token_account.to_account_info(), crank_tip)?;
+    }
+
+    // -----------------------------------------------------------------------
+    // 2. Refund the rest to the creator
+    // -----------------------------------------------------------------------
+    payout.transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        refund_amount,
+    )?;
+
+    // -----------------------------------------------------------------------
+    // 3. Forfeit the worker's collateral (accepted, never delivered)
+    // -----------------------------------------------------------------------
+    let collateral_forfeited = ctx.accounts.handshake.take_collateral();
+    if let Some(collateral_vault) = &ctx.accounts.collateral_vault {
+        let commons_treasury = collateral_commons(
+            &ctx.accounts.handshake.mint,
+            &ctx.accounts.config,
+            ctx.accounts.allowed_mint.as_deref(),
+            ctx.accounts
+                .commons_treasury
+                .as_ref()
+                .map(|account| account.to_account_info()),
+        )?;
+        payout.forfeit_collateral(
+            &collateral_vault.to_account_info(),
+            collateral_forfeited,
+            &ctx.accounts.creator_token_account.to_account_info(),
+            commons_treasury.as_ref(),
+            ctx.accounts.config.collateral_commons_bps,
+        )?;
+        payout.close_vault(
+            &collateral_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
+    // The payout never started streaming — hand back the stream's rent
+    if let Some(stream_vault) = &ctx.accounts.stream_vault {
+        payout.close_vault(
+            &stream_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
+    // Nothing is left to fund a subcontract with
+    settle_lien(
+        &mut ctx.accounts.handshake,
+        ctx.accounts.subcontract.as_deref_mut(),
+        None,
+        &payout,
+        None,
+        false,
+        clock.unix_timestamp,
+    )?;
+
+    // Update state
+    let handshake = &mut ctx.accounts.handshake;
+    handshake.status = HandshakeStatus::Expired;
+    handshake.resolved_at = clock.unix_timestamp;
+
+    emit!(HandshakeExpired {
+        handshake_id: handshake.id,
+        creator: handshake.creator,
+        cranker: cranker_key,
+        refunded: refund_amount,
+        crank_tip,
+        collateral_forfeited,
+        expired_at: handshake.resolved_at,
+    });
+
+    msg!(
+        "Handshake #{} expired. {} refunded, {} crank tip",
+        handshake.id,
+        refund_amount,
+        crank_tip
+    );
+    Ok(())
+}
@@ -0,0 +1,262 @@
+// ============================================================================
+// Finalize Dispute — execute the panel's ruling
+//
+// Permissionless crank. Once every panel member has voted, or the voting
+// window has closed with a quorum (strict majority) of votes, the median
+// worker share is paid out through the same path as `resolve_dispute`.
+// Panels take no arbiter fee. The collateral vault and a vested handshake's
+// payout stream are closed to the creator.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    AllowedMint, DisputeCase, Handshake, HandshakeStatus, PayoutStream, ProtocolConfig,
+    TeamPayees,
+};
+use crate::errors::SourError;
+use crate::events::DisputeFinalized;
+use crate::escrow::{MintTransfer, TeamPayout, VaultPayout};
+use crate::ruling::{self, RulingAccounts};
+
+#[derive(Accounts)]
+pub struct FinalizeDispute<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Disputed @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        seeds = [DisputeCase::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = dispute_case.bump,
+        constraint = !dispute_case.finalized @ SourError::CaseFinalized,
+        constraint = dispute_case.is_drawn() @ SourError::PanelNotDrawn,
+    )]
+    pub dispute_case: Account<'info, DisputeCase>,
+
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        mut,
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// Escrow vault
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bond vault opened by `dispute`
+    #[account(
+        mut,
+        seeds = [b"bond_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Collateral vault — required when the handshake holds worker
+    /// collateral; closed to the creator
+    #[account(
+        mut,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Team payees — required when the worker's payout is split across a
+    /// team (payee token accounts lead the remaining accounts)
+    #[account(
+        seeds = [TeamPayees::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = team.bump,
+    )]
+    pub team: Option<Account<'info, TeamPayees>>,
+
+    /// Subcontract carved out of this handshake — required while its lien
+    /// is outstanding
+    #[account(
+        mut,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            handshake.child_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump = subcontract.bump,
+    )]
+    pub subcontract: Option<Account<'info, Handshake>>,
+
+    /// Subcontract's escrow vault — receives the lien when the worker's
+    /// share covers it
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.child_id.unwrap_or_default().to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub subcontract_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payout stream — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator — receives the rent of the accounts closed here
+    /// CHECK: must match handshake.creator
+    #[account(mut, address = handshake.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Creator's token account — receives the refunded portion
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Worker's token account — receives the worker's portion
+    #[account(
+        mut,
+        constraint = worker_token_account.mint == handshake.mint,
+        constraint = worker_token_account.owner == handshake.worker,
+    )]
+    pub worker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Keepers pool
+    #[account(mut)]
+    pub keepers_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Commons treasury
+    #[account(mut)]
+    pub commons_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Buyback+LP treasury — receives 50% of Pinch
+    #[account(mut)]
+    pub buyback_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeDispute<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let dispute_case = &ctx.accounts.dispute_case;
+
+    let votes_cast = dispute_case.votes_cast();
+    require!(
+        votes_cast == dispute_case.panel.len()
+            || clock.unix_timestamp > dispute_case.voting_ends_at,
+        SourError::VotingOpen
+    );
+    require!(
+        votes_cast >= dispute_case.quorum(),
+        SourError::QuorumNotReached
+    );
+    let ruling = dispute_case.median_ruling()?;
+    let worker_share_bps = ruling.worker_share_bps()?;
+    let resolver = dispute_case.key();
+
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    let accounts = ctx.accounts;
+    let (team, hook_accounts) =
+        TeamPayout::split(accounts.team.as_deref(), ctx.remaining_accounts)?;
+    ruling::execute(
+        RulingAccounts {
+            config: &mut accounts.config,
+            handshake: &mut accounts.handshake,
+            allowed_mint: accounts.allowed_mint.as_deref_mut(),
+            vault_balance: accounts.vault.amount,
+            payout: VaultPayout {
+                token: MintTransfer::new(
+                    &accounts.token_program,
+                    &accounts.mint,
+                    hook_accounts,
+                ),
+                vault: accounts.vault.to_account_info(),
+                vault_authority: accounts.vault_authority.to_account_info(),
+                signer_seeds,
+            },
+            creator_token_account: accounts.creator_token_account.to_account_info(),
+            worker_token_account: accounts.worker_token_account.to_account_info(),
+            team,
+            keepers_pool: accounts.keepers_pool.to_account_info(),
+            commons_treasury: accounts.commons_treasury.to_account_info(),
+            buyback_treasury: accounts.buyback_treasury.to_account_info(),
+            arbiter_token_account: None,
+            bond_vault: accounts.bond_vault.to_account_info(),
+            collateral_vault: accounts
+                .collateral_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            subcontract: accounts.subcontract.as_deref_mut(),
+            subcontract_vault: accounts
+                .subcontract_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            payout_stream: accounts.payout_stream.as_deref(),
+            stream_vault: accounts
+                .stream_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            creator: accounts.creator.to_account_info(),
+        },
+        ruling,
+        resolver,
+        0,
+    )?;
+
+    let dispute_case = &mut accounts.dispute_case;
+    dispute_case.finalized = true;
+
+    emit!(DisputeFinalized {
+        handshake_id: dispute_case.handshake_id,
+        votes_cast: votes_cast as u8,
+        worker_share_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,196 @@
+// ============================================================================
+// Fund Invoice — the invoiced client escrows the amount and the handshake
+// starts out Accepted
+//
+// Opens the vault, moves the billed amount in and creates a regular
+// handshake (next handshake ID) with the invoice's worker, mint, deadline
+// and description, on protocol-default terms. The worker already agreed by
+// issuing the invoice, so there is no separate acceptance. The invoice PDA
+// is closed and its rent returned to the worker.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    AllowedMint, Handshake, HandshakeInit, HandshakeStatus, Invoice, ProtocolConfig,
+};
+use crate::errors::SourError;
+use crate::events::InvoiceFunded;
+use crate::escrow::MintTransfer;
+
+#[derive(Accounts)]
+pub struct FundInvoice<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        close = worker,
+        seeds = [Invoice::SEED_PREFIX, invoice.id.to_le_bytes().as_ref()],
+        bump = invoice.bump,
+        constraint = invoice.creator == creator.key() @ SourError::NotCreator,
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Handshake::INIT_SPACE,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            config.handshake_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// The escrow vault — PDA-owned token account holding the escrow mint
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"vault", config.handshake_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA derived from seeds, no data needed
+    #[account(
+        seeds = [b"vault_auth", config.handshake_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account (source of escrowed funds)
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == mint.key(),
+        constraint = creator_token_account.owner == creator.key(),
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Issuer of the invoice — becomes the worker and receives the rent
+    /// CHECK: must match invoice.worker
+    #[account(mut, address = invoice.worker)]
+    pub worker: UncheckedAccount<'info>,
+
+    /// Mint the invoice is payable in
+    #[account(address = invoice.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Allowlist entry for `mint` — omit for $SOUR invoices
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, mint.key().as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, FundInvoice<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let invoice = &ctx.accounts.invoice;
+    require!(
+        clock.unix_timestamp < invoice.deadline_ts,
+        SourError::InvoiceExpired
+    );
+
+    // The mint must still be allowed — the allowlist entry may have been
+    // disabled since the invoice was issued
+    if invoice.mint != ctx.accounts.config.sour_mint {
+        let allowed_mint = ctx
+            .accounts
+            .allowed_mint
+            .as_ref()
+            .ok_or(SourError::MintNotAllowed)?;
+        require!(allowed_mint.enabled, SourError::MintNotAllowed);
+    } else {
+        require!(
+            ctx.accounts.allowed_mint.is_none(),
+            SourError::AllowedMintMismatch
+        );
+    }
+
+    // Transfer escrow from creator to vault
+    MintTransfer::new(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.remaining_accounts,
+    )
+    .transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &[],
+        invoice.amount,
+    )?;
+
+    // Fee-on-transfer mints deliver less than was sent — escrow what arrived
+    ctx.accounts.vault.reload()?;
+    let amount = ctx.accounts.vault.amount;
+    require!(amount > 0, SourError::ZeroAmount);
+    if amount < invoice.amount {
+        msg!(
+            "Transfer fee withheld {}; escrowing {}",
+            invoice.amount - amount,
+            amount
+        );
+    }
+
+    // Initialize handshake state — accepted on protocol-default terms
+    let handshake = &mut ctx.accounts.handshake;
+    let config = &mut ctx.accounts.config;
+
+    handshake.init(HandshakeInit {
+        id: config.handshake_count,
+        creator: invoice.creator,
+        worker: invoice.worker,
+        mint: invoice.mint,
+        amount,
+        description: invoice.description.clone(),
+        status: HandshakeStatus::Accepted,
+        created_at: clock.unix_timestamp,
+        deadline_ts: invoice.deadline_ts,
+        accepted_at: clock.unix_timestamp,
+        review_window_secs: config.review_window_secs,
+        vault_bump: ctx.bumps.vault_authority,
+        bump: ctx.bumps.handshake,
+        ..HandshakeInit::default()
+    });
+
+    // Increment global counter
+    config.handshake_count = config
+        .handshake_count
+        .checked_add(1)
+        .ok_or(SourError::MathOverflow)?;
+
+    emit!(InvoiceFunded {
+        invoice_id: invoice.id,
+        handshake_id: handshake.id,
+        creator: handshake.creator,
+        worker: handshake.worker,
+        amount,
+        accepted_at: handshake.accepted_at,
+    });
+
+    msg!(
+        "Invoice #{} funded as handshake #{}: {} escrowed",
+        invoice.id,
+        handshake.id,
+        amount
+    );
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// ============================================================================
+// Fund Recurring — Baker A pre-funds further periods of a retainer
+//
+// Funding is capped at the retainer's remaining periods, so a stopped
+// retainer cannot be topped up past its end. The new periods escrow what
+// actually reached the vault (fee-on-transfer mints deliver less).
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{RecurringHandshake, RecurringStatus};
+use crate::errors::SourError;
+use crate::events::RecurringFunded;
+use crate::escrow::MintTransfer;
+
+#[derive(Accounts)]
+pub struct FundRecurring<'info> {
+    #[account(
+        mut,
+        seeds = [RecurringHandshake::SEED_PREFIX, recurring.id.to_le_bytes().as_ref()],
+        bump = recurring.bump,
+        constraint = matches!(
+            recurring.status,
+            RecurringStatus::Created | RecurringStatus::Active
+        ) @ SourError::InvalidStatus,
+        constraint = recurring.creator == creator.key() @ SourError::NotCreator,
+    )]
+    pub recurring: Account<'info, RecurringHandshake>,
+
+    /// The recurring vault
+    #[account(
+        mut,
+        seeds = [b"recurring_vault", recurring.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = recurring.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Creator's token account — source of the funding
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == recurring.mint,
+        constraint = creator_token_account.owner == creator.key(),
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub creator: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FundRecurring<'info>>,
+    periods: u16,
+) -> Result<()> {
+    let recurring = &mut ctx.accounts.recurring;
+    let funded_periods = recurring
+        .funded_periods
+        .checked_add(periods)
+        .ok_or(SourError::MathOverflow)?;
+    require!(
+        periods > 0 && funded_periods <= recurring.end_periods,
+        SourError::TooManyPeriods
+    );
+
+    let vault_before = ctx.accounts.vault.amount;
+    MintTransfer::new(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.remaining_accounts,
+    )
+    .transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &[],
+        recurring.amount_for(periods)?,
+    )?;
+    ctx.accounts.vault.reload()?;
+    let amount = ctx
+        .accounts
+        .vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(SourError::MathOverflow)?;
+    recurring.fund_periods(periods, amount)?;
+
+    emit!(RecurringFunded {
+        recurring_id: recurring.id,
+        periods,
+        funded_periods,
+        amount,
+    });
+
+    msg!(
+        "Recurring handshake #{} funded through period {}",
+        recurring.id,
+        funded_periods
+    );
+    Ok(())
+}
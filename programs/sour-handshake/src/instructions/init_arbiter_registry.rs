@@ -0,0 +1,56 @@
+// ============================================================================
+// Initialize Arbiter Registry — one-time setup of the dispute panel pool
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{ArbiterRegistry, ProtocolConfig};
+use crate::errors::SourError;
+
+#[derive(Accounts)]
+pub struct InitializeArbiterRegistry<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ SourError::NotAuthority,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ArbiterRegistry::INIT_SPACE,
+        seeds = [ArbiterRegistry::SEED_PREFIX],
+        bump,
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeArbiterRegistry>,
+    panel_size: u8,
+    voting_window_secs: i64,
+) -> Result<()> {
+    require!(
+        ArbiterRegistry::is_valid_panel(panel_size, voting_window_secs),
+        SourError::InvalidPanelConfig
+    );
+
+    let registry = &mut ctx.accounts.registry;
+    registry.arbiters = Vec::new();
+    registry.panel_size = panel_size;
+    registry.voting_window_secs = voting_window_secs;
+    registry.bump = ctx.bumps.registry;
+
+    msg!(
+        "Arbiter registry initialized. Panel: {}, voting window: {}s",
+        panel_size,
+        voting_window_secs
+    );
+    Ok(())
+}
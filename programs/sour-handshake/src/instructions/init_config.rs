@@ -3,10 +3,9 @@
 // ============================================================================
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 
 use crate::state::ProtocolConfig;
-use crate::errors::SourError;
 use crate::events::ConfigInitialized;
 
 #[derive(Accounts)]
@@ -21,7 +20,7 @@ pub struct InitializeConfig<'info> {
     pub config: Account<'info, ProtocolConfig>,
 
     /// The $SOUR token mint
-    pub sour_mint: Account<'info, Mint>,
+    pub sour_mint: InterfaceAccount<'info, Mint>,
 
     /// Token account that receives the Keepers' share of Pinch fees
     /// CHECK: validated by the authority; must be a token account for sour_mint
@@ -50,14 +49,9 @@ pub fn handler(
     treasury_share_bps: u16,
     keepers_share_bps: u16,
     commons_share_bps: u16,
+    crank_tip_bps: u16,
+    review_window_secs: i64,
 ) -> Result<()> {
-    // Validate: pinch fee must be reasonable (max 50%)
-    require!(pinch_bps <= 5000, SourError::InvalidPinchBps);
-
-    // Validate: fee shares must sum to 10000 bps (100%)
-    let share_sum = treasury_share_bps as u32 + keepers_share_bps as u32 + commons_share_bps as u32;
-    require!(share_sum == 10_000, SourError::InvalidFeeShares);
-
     let config = &mut ctx.accounts.config;
     config.authority = ctx.accounts.authority.key();
     config.sour_mint = ctx.accounts.sour_mint.key();
@@ -75,6 +69,18 @@ pub fn handler(
     config.total_completed = 0;
     config.total_disputed = 0;
     config.bump = ctx.bumps.config;
+    config.pending_authority = Pubkey::default();
+    config.crank_tip_bps = crank_tip_bps;
+    config.review_window_secs = review_window_secs;
+    config.dispute_bond = 0; // set later via update_config
+    config.collateral_commons_bps = 0;
+    config.bonus_pinch_bps = 0;
+    config.recurring_count = 0;
+    config.invoice_count = 0;
+
+    // Validate: Pinch rate, fee shares, crank tip, review window, collateral
+    // slice, bonus Pinch
+    config.validate()?;
 
     emit!(ConfigInitialized {
         authority: config.authority,
@@ -0,0 +1,43 @@
+// ============================================================================
+// Initialize Worker Record — open a wallet's track record
+//
+// Approvals and claims credit completed handshakes to the record (opening
+// it first if needed) and withdrawals are logged against it; open bounties
+// read it to check eligibility. Anyone may pay to open one.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::WorkerRecord;
+
+#[derive(Accounts)]
+pub struct InitializeWorkerRecord<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WorkerRecord::INIT_SPACE,
+        seeds = [WorkerRecord::SEED_PREFIX, worker.key().as_ref()],
+        bump,
+    )]
+    pub worker_record: Account<'info, WorkerRecord>,
+
+    /// The worker the record belongs to
+    /// CHECK: any wallet; only used as a seed
+    pub worker: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeWorkerRecord>) -> Result<()> {
+    let record = &mut ctx.accounts.worker_record;
+    record.worker = ctx.accounts.worker.key();
+    record.completed = 0;
+    record.withdrawals = 0;
+    record.bump = ctx.bumps.worker_record;
+
+    msg!("Worker record opened for {}", record.worker);
+    Ok(())
+}
@@ -0,0 +1,99 @@
+// ============================================================================
+// Match Dispute Bond — the other party matches the disputer's bond
+//
+// Optional. Matching puts the same stake on the other side, so the party the
+// ruling goes against forfeits theirs to the Commons treasury either way.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{Handshake, HandshakeStatus};
+use crate::errors::SourError;
+use crate::events::DisputeBondMatched;
+use crate::escrow::MintTransfer;
+
+#[derive(Accounts)]
+pub struct MatchDisputeBond<'info> {
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Disputed @ SourError::InvalidStatus,
+        constraint = handshake.dispute_bond > 0 && handshake.counter_bond == 0
+            @ SourError::BondNotMatchable,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// Bond vault opened by `dispute`
+    #[account(
+        mut,
+        seeds = [b"bond_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Matching party's token account — source of the bond
+    #[account(
+        mut,
+        constraint = signer_token_account.mint == handshake.mint,
+        constraint = signer_token_account.owner == signer.key(),
+    )]
+    pub signer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The participant who did not raise the dispute
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, MatchDisputeBond<'info>>) -> Result<()> {
+    let handshake = &ctx.accounts.handshake;
+    let signer_key = ctx.accounts.signer.key();
+
+    // Only the disputer's counterparty can match
+    require!(
+        (signer_key == handshake.creator || signer_key == handshake.worker)
+            && signer_key != handshake.disputed_by,
+        SourError::NotParticipant
+    );
+
+    let balance_before = ctx.accounts.bond_vault.amount;
+    MintTransfer::new(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.remaining_accounts,
+    )
+    .transfer(
+        &ctx.accounts.signer_token_account.to_account_info(),
+        &ctx.accounts.bond_vault.to_account_info(),
+        &ctx.accounts.signer.to_account_info(),
+        &[],
+        handshake.dispute_bond,
+    )?;
+
+    // Fee-on-transfer mints deliver less — record what arrived
+    ctx.accounts.bond_vault.reload()?;
+    let matched = ctx
+        .accounts
+        .bond_vault
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(SourError::MathOverflow)?;
+
+    let handshake = &mut ctx.accounts.handshake;
+    handshake.counter_bond = matched;
+
+    emit!(DisputeBondMatched {
+        handshake_id: handshake.id,
+        matched_by: signer_key,
+        bond: matched,
+    });
+
+    msg!("Dispute bond on handshake #{} matched: {}", handshake.id, matched);
+    Ok(())
+}
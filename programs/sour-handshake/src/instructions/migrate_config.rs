@@ -0,0 +1,93 @@
+// ============================================================================
+// Migrate Config — grow a config created before the appended fields
+//
+// `ProtocolConfig` only ever gains fields at the end, so an account created
+// by an earlier deployment keeps every byte it already has. This reallocates
+// it to the current size (the authority tops up the rent), leaves the new
+// fields zeroed — no pending handover, free disputes, no Commons slice,
+// feeless bonuses, fresh recurring/invoice counters — and sets the two that
+// have no safe zero: the crank tip and the review window.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::state::ProtocolConfig;
+use crate::errors::SourError;
+use crate::events::ConfigMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// Config on the pre-migration layout
+    /// CHECK: PDA seeds verified; owner, discriminator, size and authority
+    /// checked in the handler
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// The protocol authority — pays the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<MigrateConfig>,
+    crank_tip_bps: u16,
+    review_window_secs: i64,
+) -> Result<()> {
+    let config = ctx.accounts.config.to_account_info();
+    require!(
+        config.owner == &crate::ID && config.data_len() == ProtocolConfig::LEGACY_SPACE,
+        SourError::ConfigNotMigratable
+    );
+    {
+        // The authority sits at the same offset in both layouts
+        let data = config.try_borrow_data()?;
+        require!(
+            data[..8] == ProtocolConfig::DISCRIMINATOR,
+            SourError::ConfigNotMigratable
+        );
+        let authority = Pubkey::try_from(&data[8..40]).map_err(|_| SourError::ConfigNotMigratable)?;
+        require_keys_eq!(authority, ctx.accounts.authority.key(), SourError::NotAuthority);
+    }
+
+    // Top up the rent, then grow the account; new bytes are zeroed
+    let space = 8 + ProtocolConfig::INIT_SPACE;
+    let shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(config.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: config.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    config.realloc(space, true)?;
+
+    let mut migrated = ProtocolConfig::try_deserialize(&mut &config.try_borrow_data()?[..])?;
+    migrated.crank_tip_bps = crank_tip_bps;
+    migrated.review_window_secs = review_window_secs;
+    migrated.validate()?;
+    migrated.try_serialize(&mut &mut config.try_borrow_mut_data()?[..])?;
+
+    emit!(ConfigMigrated {
+        authority: migrated.authority,
+        crank_tip_bps,
+        review_window_secs,
+    });
+
+    msg!("Config migrated to {} bytes", space);
+    Ok(())
+}
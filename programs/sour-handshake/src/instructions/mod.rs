@@ -3,15 +3,97 @@ pub mod create;
 pub mod accept;
 pub mod deliver;
 pub mod approve;
+pub mod claim;
 pub mod dispute;
 pub mod cancel;
 pub mod resolve;
+pub mod expire;
+pub mod add_allowed_mint;
+pub mod update_allowed_mint;
+pub mod update_config;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod migrate_config;
+pub mod init_arbiter_registry;
+pub mod register_arbiter;
+pub mod remove_arbiter;
+pub mod open_dispute_case;
+pub mod draw_panel;
+pub mod cast_vote;
+pub mod finalize_dispute;
+pub mod submit_evidence;
+pub mod match_dispute_bond;
+pub mod init_worker_record;
+pub mod apply;
+pub mod select_application;
+pub mod close_application;
+pub mod propose_amendment;
+pub mod accept_amendment;
+pub mod cancel_amendment;
+pub mod propose_mutual_cancel;
+pub mod accept_mutual_cancel;
+pub mod withdraw_mutual_cancel;
+pub mod decline;
+pub mod withdraw;
+pub mod request_revision;
+pub mod withdraw_vested;
+pub mod create_recurring;
+pub mod accept_recurring;
+pub mod fund_recurring;
+pub mod deliver_period;
+pub mod settle_period;
+pub mod stop_recurring;
+pub mod create_invoice;
+pub mod fund_invoice;
+pub mod revoke_invoice;
+pub mod subcontract;
 
 pub use init_config::*;
 pub use create::*;
 pub use accept::*;
 pub use deliver::*;
 pub use approve::*;
+pub use claim::*;
 pub use dispute::*;
 pub use cancel::*;
 pub use resolve::*;
+pub use expire::*;
+pub use add_allowed_mint::*;
+pub use update_allowed_mint::*;
+pub use update_config::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use migrate_config::*;
+pub use init_arbiter_registry::*;
+pub use register_arbiter::*;
+pub use remove_arbiter::*;
+pub use open_dispute_case::*;
+pub use draw_panel::*;
+pub use cast_vote::*;
+pub use finalize_dispute::*;
+pub use submit_evidence::*;
+pub use match_dispute_bond::*;
+pub use init_worker_record::*;
+pub use apply::*;
+pub use select_application::*;
+pub use close_application::*;
+pub use propose_amendment::*;
+pub use accept_amendment::*;
+pub use cancel_amendment::*;
+pub use propose_mutual_cancel::*;
+pub use accept_mutual_cancel::*;
+pub use withdraw_mutual_cancel::*;
+pub use decline::*;
+pub use withdraw::*;
+pub use request_revision::*;
+pub use withdraw_vested::*;
+pub use create_recurring::*;
+pub use accept_recurring::*;
+pub use fund_recurring::*;
+pub use deliver_period::*;
+pub use settle_period::*;
+pub use stop_recurring::*;
+pub use create_invoice::*;
+pub use fund_invoice::*;
+pub use revoke_invoice::*;
+pub use subcontract::*;
@@ -0,0 +1,103 @@
+// ============================================================================
+// Open Dispute Case — commit a disputed handshake to panel arbitration
+//
+// Creator or worker only, and only for handshakes without an agreed
+// arbiter — one party cannot override the arbiter both sides chose. Opening
+// fixes a future slot; `draw_panel` later seats the arbiters from that
+// slot's block hash, which neither party can see (or pick a transaction time
+// against) when the case is opened. The registry skips the two parties.
+//
+// Opening the case closes the evidence log, so the panel rules on a fixed
+// record, and from then on only the panel can settle the dispute —
+// `resolve_dispute` is rejected while a case exists, unless the case stalls
+// (see `DisputeCase::is_stalled`).
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{ArbiterRegistry, DisputeCase, EvidenceLog, Handshake, HandshakeStatus};
+use crate::errors::SourError;
+use crate::events::DisputeCaseOpened;
+
+#[derive(Accounts)]
+pub struct OpenDisputeCase<'info> {
+    #[account(
+        seeds = [ArbiterRegistry::SEED_PREFIX],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Disputed @ SourError::InvalidStatus,
+        constraint = payer.key() == handshake.creator
+            || payer.key() == handshake.worker @ SourError::NotParticipant,
+        constraint = handshake.arbiter == Pubkey::default() @ SourError::ArbiterAgreed,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DisputeCase::INIT_SPACE,
+        seeds = [DisputeCase::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub dispute_case: Account<'info, DisputeCase>,
+
+    /// Evidence log — closed once the case is opened
+    #[account(
+        mut,
+        seeds = [EvidenceLog::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = evidence_log.bump,
+    )]
+    pub evidence_log: Account<'info, EvidenceLog>,
+
+    /// Creator or worker
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<OpenDisputeCase>) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake = &ctx.accounts.handshake;
+
+    // Checked now so a case never waits on a draw that cannot succeed
+    let excluded = [handshake.creator, handshake.worker, handshake.arbiter];
+    require!(
+        ctx.accounts.registry.eligible(&excluded).len()
+            >= ctx.accounts.registry.panel_size as usize,
+        SourError::PanelUnavailable
+    );
+
+    let draw_slot = clock
+        .slot
+        .checked_add(DisputeCase::DRAW_DELAY_SLOTS)
+        .ok_or(SourError::MathOverflow)?;
+
+    let dispute_case = &mut ctx.accounts.dispute_case;
+    dispute_case.handshake_id = handshake.id;
+    dispute_case.panel = Vec::new();
+    dispute_case.opened_at = clock.unix_timestamp;
+    dispute_case.draw_slot = draw_slot;
+    dispute_case.voting_ends_at = 0;
+    dispute_case.finalized = false;
+    dispute_case.bump = ctx.bumps.dispute_case;
+
+    ctx.accounts.evidence_log.closed = true;
+
+    emit!(DisputeCaseOpened {
+        handshake_id: handshake.id,
+        draw_slot,
+    });
+
+    msg!(
+        "Dispute case #{} opened. Panel drawn from slot {}",
+        handshake.id,
+        draw_slot
+    );
+    Ok(())
+}
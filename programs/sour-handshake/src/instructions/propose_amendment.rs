@@ -0,0 +1,114 @@
+// ============================================================================
+// Propose Amendment — either party proposes a new deadline and/or amount
+//
+// The counterparty applies it with `accept_amendment`. When the creator
+// proposes a larger escrow, the top-up is approved (delegated) to the vault
+// authority now and pulled only if the worker accepts.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{Amendment, Handshake, HandshakeStatus};
+use crate::errors::SourError;
+use crate::events::AmendmentProposed;
+use crate::escrow::MintTransfer;
+
+#[derive(Accounts)]
+pub struct ProposeAmendment<'info> {
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = matches!(
+            handshake.status,
+            HandshakeStatus::Accepted | HandshakeStatus::Delivered
+        ) @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Amendment::INIT_SPACE,
+        seeds = [Amendment::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub amendment: Account<'info, Amendment>,
+
+    /// Creator's token account — required when the creator proposes a
+    /// larger escrow
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Escrow mint
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault — delegate for a creator's top-up
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeAmendment>,
+    deadline_ts: Option<i64>,
+    amount: Option<u64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake = &ctx.accounts.handshake;
+    let proposer = ctx.accounts.proposer.key();
+    require!(
+        proposer == handshake.creator || proposer == handshake.worker,
+        SourError::NotParticipant
+    );
+
+    let amendment = &mut ctx.accounts.amendment;
+    amendment.handshake_id = handshake.id;
+    amendment.proposer = proposer;
+    amendment.deadline_ts = deadline_ts;
+    amendment.amount = amount;
+    amendment.proposed_at = clock.unix_timestamp;
+    amendment.bump = ctx.bumps.amendment;
+    amendment.check(handshake, clock.unix_timestamp)?;
+
+    // Creator offering more: pre-authorize the top-up so the worker's
+    // acceptance can pull it without the creator signing again
+    let top_up = amount.unwrap_or(handshake.amount).saturating_sub(handshake.amount);
+    if proposer == handshake.creator && top_up > 0 {
+        let creator_token_account = ctx
+            .accounts
+            .creator_token_account
+            .as_ref()
+            .ok_or(SourError::TopUpAccountRequired)?;
+        MintTransfer::new(&ctx.accounts.token_program, &ctx.accounts.mint, &[]).approve(
+            &creator_token_account.to_account_info(),
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.proposer.to_account_info(),
+            top_up,
+        )?;
+    }
+
+    emit!(AmendmentProposed {
+        handshake_id: handshake.id,
+        proposer,
+        deadline_ts,
+        amount,
+    });
+
+    msg!("Amendment proposed for handshake #{}", handshake.id);
+    Ok(())
+}
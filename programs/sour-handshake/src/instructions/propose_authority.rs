@@ -0,0 +1,39 @@
+// ============================================================================
+// Propose Authority — step 1 of the authority handover
+//
+// The current authority nominates a successor; nothing changes until the
+// successor signs `accept_authority`. Proposing Pubkey::default() cancels
+// a pending handover.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::ProtocolConfig;
+use crate::errors::SourError;
+use crate::events::AuthorityProposed;
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ SourError::NotAuthority,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_authority = new_authority;
+
+    emit!(AuthorityProposed {
+        authority: config.authority,
+        pending_authority: new_authority,
+    });
+
+    msg!("Authority handover proposed to {}", new_authority);
+    Ok(())
+}
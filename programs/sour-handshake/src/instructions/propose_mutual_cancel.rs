@@ -0,0 +1,70 @@
+// ============================================================================
+// Propose Mutual Cancel — either party offers to walk away from an accepted
+// handshake, splitting the remaining escrow by `worker_share_bps`
+//
+// The other party settles it with `accept_mutual_cancel`; either side may
+// withdraw or reject it with `withdraw_mutual_cancel`.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{CancelProposal, Handshake, HandshakeStatus};
+use crate::errors::SourError;
+use crate::events::MutualCancelProposed;
+
+#[derive(Accounts)]
+pub struct ProposeMutualCancel<'info> {
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = matches!(
+            handshake.status,
+            HandshakeStatus::Accepted | HandshakeStatus::Delivered
+        ) @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + CancelProposal::INIT_SPACE,
+        seeds = [CancelProposal::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub cancel_proposal: Account<'info, CancelProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeMutualCancel>, worker_share_bps: u16) -> Result<()> {
+    let handshake = &ctx.accounts.handshake;
+    let proposer = ctx.accounts.proposer.key();
+    require!(
+        proposer == handshake.creator || proposer == handshake.worker,
+        SourError::NotParticipant
+    );
+    require!(worker_share_bps <= 10_000, SourError::InvalidCancelSplit);
+
+    let proposal = &mut ctx.accounts.cancel_proposal;
+    proposal.handshake_id = handshake.id;
+    proposal.proposer = proposer;
+    proposal.worker_share_bps = worker_share_bps;
+    proposal.proposed_at = Clock::get()?.unix_timestamp;
+    proposal.bump = ctx.bumps.cancel_proposal;
+
+    emit!(MutualCancelProposed {
+        handshake_id: handshake.id,
+        proposer,
+        worker_share_bps,
+    });
+
+    msg!(
+        "Mutual cancel proposed for handshake #{}: {}bps to worker",
+        handshake.id,
+        worker_share_bps
+    );
+    Ok(())
+}
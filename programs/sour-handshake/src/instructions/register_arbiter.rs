@@ -0,0 +1,50 @@
+// ============================================================================
+// Register Arbiter — authority adds a key to the dispute panel pool
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{ArbiterRegistry, ProtocolConfig};
+use crate::errors::SourError;
+use crate::events::ArbiterRegistryUpdated;
+
+#[derive(Accounts)]
+pub struct RegisterArbiter<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ SourError::NotAuthority,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ArbiterRegistry::SEED_PREFIX],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RegisterArbiter>, arbiter: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    require!(
+        !registry.arbiters.contains(&arbiter),
+        SourError::ArbiterAlreadyRegistered
+    );
+    require!(
+        registry.arbiters.len() < ArbiterRegistry::MAX_ARBITERS,
+        SourError::RegistryFull
+    );
+    registry.arbiters.push(arbiter);
+
+    emit!(ArbiterRegistryUpdated {
+        arbiter,
+        registered: true,
+        arbiter_count: registry.arbiters.len() as u8,
+    });
+
+    msg!("Arbiter {} registered", arbiter);
+    Ok(())
+}
@@ -0,0 +1,50 @@
+// ============================================================================
+// Remove Arbiter — authority drops a key from the dispute panel pool
+//
+// Removal only affects future panels; cases already opened keep their
+// drawn members.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{ArbiterRegistry, ProtocolConfig};
+use crate::errors::SourError;
+use crate::events::ArbiterRegistryUpdated;
+
+#[derive(Accounts)]
+pub struct RemoveArbiter<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ SourError::NotAuthority,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ArbiterRegistry::SEED_PREFIX],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveArbiter>, arbiter: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    let index = registry
+        .arbiters
+        .iter()
+        .position(|registered| *registered == arbiter)
+        .ok_or(SourError::ArbiterNotRegistered)?;
+    registry.arbiters.remove(index);
+
+    emit!(ArbiterRegistryUpdated {
+        arbiter,
+        registered: false,
+        arbiter_count: registry.arbiters.len() as u8,
+    });
+
+    msg!("Arbiter {} removed", arbiter);
+    Ok(())
+}
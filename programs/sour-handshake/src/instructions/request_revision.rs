@@ -0,0 +1,113 @@
+// ============================================================================
+// Request Revision — the creator sends a delivery back instead of disputing
+//
+// From Delivered, returns the handshake (or the open milestone) to Accepted
+// and records a hash of the off-chain feedback. Limited to the handshake's
+// `max_revisions`; once used up, a rejected delivery must go through
+// `dispute`. Only allowed during the review window, so a worker who is
+// already entitled to claim cannot be sent back.
+//
+// The creator may push the deadline out at the same time, and must do so if
+// less than `MIN_REVISION_SECS` is left — the worker always gets a real
+// chance to rework, so a revision cannot be used to send back a late
+// delivery and then expire the handshake to claim the worker's collateral.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{Handshake, HandshakeStatus, Milestones};
+use crate::errors::SourError;
+use crate::events::RevisionRequested;
+
+#[derive(Accounts)]
+pub struct RequestRevision<'info> {
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Delivered @ SourError::InvalidStatus,
+        constraint = handshake.creator == creator.key() @ SourError::NotCreator,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// Milestone schedule — required for milestone handshakes
+    #[account(
+        mut,
+        seeds = [Milestones::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<RequestRevision>,
+    feedback_hash: [u8; 32],
+    deadline_ts: Option<i64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake = &mut ctx.accounts.handshake;
+
+    require!(
+        handshake.revision_count < handshake.max_revisions,
+        SourError::RevisionsExhausted
+    );
+    let review_ends_at = handshake
+        .delivered_at
+        .checked_add(handshake.review_window_secs)
+        .ok_or(SourError::MathOverflow)?;
+    require!(
+        clock.unix_timestamp < review_ends_at,
+        SourError::ReviewWindowClosed
+    );
+
+    // Optional extension of the deadline for the reworked delivery
+    let current_deadline = handshake.current_deadline(ctx.accounts.milestones.as_deref())?;
+    if let Some(deadline_ts) = deadline_ts {
+        require!(
+            deadline_ts > current_deadline,
+            SourError::InvalidRevisionDeadline
+        );
+        if let Some(milestones) = ctx.accounts.milestones.as_mut() {
+            milestones.extend(handshake.current_milestone, deadline_ts)?;
+        }
+        handshake.deadline_ts = handshake.deadline_ts.max(deadline_ts);
+    }
+    let new_deadline = handshake.current_deadline(ctx.accounts.milestones.as_deref())?;
+    let rework_until = clock
+        .unix_timestamp
+        .checked_add(Handshake::MIN_REVISION_SECS)
+        .ok_or(SourError::MathOverflow)?;
+    require!(
+        new_deadline >= rework_until,
+        SourError::InvalidRevisionDeadline
+    );
+
+    // Back to work on the same tranche
+    if let Some(milestones) = ctx.accounts.milestones.as_mut() {
+        milestones.items[handshake.current_milestone as usize].delivered_at = 0;
+    }
+    handshake.status = HandshakeStatus::Accepted;
+    handshake.delivered_at = 0;
+    handshake.revision_count += 1;
+    handshake.feedback_hash = feedback_hash;
+
+    emit!(RevisionRequested {
+        handshake_id: handshake.id,
+        milestone_index: handshake.current_milestone,
+        feedback_hash,
+        revision_count: handshake.revision_count,
+        max_revisions: handshake.max_revisions,
+        deadline_ts: new_deadline,
+        requested_at: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Handshake #{} — revision {}/{} requested",
+        handshake.id,
+        handshake.revision_count,
+        handshake.max_revisions
+    );
+    Ok(())
+}
@@ -1,17 +1,36 @@
 // ============================================================================
-// Resolve Dispute — authority decides outcome
+// Resolve Dispute — the handshake's arbiter (or the protocol authority as a
+// fallback) decides the outcome
 //
 // Ruling:
-//   0 = Refund creator (full amount returned, no fee)
-//   1 = Pay worker (full amount minus Pinch fee)
+//   Refund    = creator gets everything back, no fee
+//   PayWorker = worker gets everything minus the Pinch fee
+//   Split     = worker gets `worker_share_bps` of the escrow (minus Pinch,
+//               exactly as `approve`), the creator is refunded the rest
+//
+// When the agreed arbiter resolves, their `arbiter_fee_bps` comes off the
+// top of the disputed escrow before the split.
+//
+// Once `open_dispute_case` has put the dispute before a panel, only
+// `finalize_dispute` can settle it — unless the case stalls (no panel can
+// be drawn, none was drawn in time, or voting closed short of a quorum),
+// when the arbiter and then the authority take it back.
+//
+// Payout itself lives in `ruling.rs`, shared with `finalize_dispute`; it
+// also closes the collateral vault and a vested handshake's payout stream to
+// the creator.
 // ============================================================================
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::{Handshake, HandshakeStatus, ProtocolConfig};
+use crate::state::{
+    AllowedMint, ArbiterRegistry, DisputeCase, DisputeRuling, Handshake, HandshakeStatus,
+    PayoutStream, ProtocolConfig, TeamPayees,
+};
 use crate::errors::SourError;
-use crate::events::DisputeResolved;
+use crate::escrow::{MintTransfer, TeamPayout, VaultPayout};
+use crate::ruling::{self, RulingAccounts};
 
 #[derive(Accounts)]
 pub struct ResolveDispute<'info> {
@@ -19,7 +38,6 @@ pub struct ResolveDispute<'info> {
         mut,
         seeds = [ProtocolConfig::SEED_PREFIX],
         bump = config.bump,
-        constraint = config.authority == authority.key() @ SourError::NotAuthority,
     )]
     pub config: Account<'info, ProtocolConfig>,
 
@@ -28,16 +46,52 @@ pub struct ResolveDispute<'info> {
         seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
         bump = handshake.bump,
         constraint = handshake.status == HandshakeStatus::Disputed @ SourError::InvalidStatus,
+        constraint = handshake.is_resolver(&resolver.key(), &config) @ SourError::NotResolver,
     )]
     pub handshake: Account<'info, Handshake>,
 
+    /// Panel case PDA — must not exist, or must have stalled
+    /// CHECK: PDA seeds verified; read as a `DisputeCase` when it exists
+    #[account(
+        seeds = [DisputeCase::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub dispute_case: UncheckedAccount<'info>,
+
+    /// Arbiter registry — required when a panel case exists
+    #[account(
+        seeds = [ArbiterRegistry::SEED_PREFIX],
+        bump = registry.bump,
+    )]
+    pub registry: Option<Account<'info, ArbiterRegistry>>,
+
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        mut,
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
     /// Escrow vault
     #[account(
         mut,
         seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
         bump,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Bond vault opened by `dispute`
+    #[account(
+        mut,
+        seeds = [b"bond_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// PDA authority over the vault
     /// CHECK: PDA seeds verified
@@ -47,57 +101,144 @@ pub struct ResolveDispute<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    /// Creator's token account — receives refund if ruling = 0
+    /// Collateral vault — required when the handshake holds worker
+    /// collateral; closed to the creator
     #[account(
         mut,
-        constraint = creator_token_account.mint == config.sour_mint,
-        constraint = creator_token_account.owner == handshake.creator,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Team payees — required when the worker's payout is split across a
+    /// team (payee token accounts lead the remaining accounts)
+    #[account(
+        seeds = [TeamPayees::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = team.bump,
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub team: Option<Account<'info, TeamPayees>>,
 
-    /// Worker's token account — receives payment if ruling = 1
+    /// Subcontract carved out of this handshake — required while its lien
+    /// is outstanding
     #[account(
         mut,
-        constraint = worker_token_account.mint == config.sour_mint,
-        constraint = worker_token_account.owner == handshake.worker,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            handshake.child_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump = subcontract.bump,
     )]
-    pub worker_token_account: Account<'info, TokenAccount>,
+    pub subcontract: Option<Account<'info, Handshake>>,
 
-    /// Keepers pool
+    /// Subcontract's escrow vault — receives the lien when the worker's
+    /// share covers it
     #[account(
         mut,
-        constraint = keepers_pool.key() == config.keepers_pool,
+        seeds = [b"vault", handshake.child_id.unwrap_or_default().to_le_bytes().as_ref()],
+        bump,
     )]
-    pub keepers_pool: Account<'info, TokenAccount>,
+    pub subcontract_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// Commons treasury
+    /// Payout stream — required when the handshake vests its payout;
+    /// closed to the creator
     #[account(
         mut,
-        constraint = commons_treasury.key() == config.commons_treasury,
+        close = creator,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
     )]
-    pub commons_treasury: Account<'info, TokenAccount>,
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator — receives the rent of the accounts closed here
+    /// CHECK: must match handshake.creator
+    #[account(mut, address = handshake.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Creator's token account — receives the refunded portion
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Worker's token account — receives the worker's portion
+    #[account(
+        mut,
+        constraint = worker_token_account.mint == handshake.mint,
+        constraint = worker_token_account.owner == handshake.worker,
+    )]
+    pub worker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Keepers pool
+    #[account(mut)]
+    pub keepers_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Commons treasury
+    #[account(mut)]
+    pub commons_treasury: InterfaceAccount<'info, TokenAccount>,
 
     /// Buyback+LP treasury — receives 50% of Pinch
+    #[account(mut)]
+    pub buyback_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Arbiter's token account — receives the arbiter fee (required when the
+    /// arbiter resolves a handshake with a non-zero fee)
     #[account(
         mut,
-        constraint = buyback_treasury.key() == config.buyback_treasury,
+        constraint = arbiter_token_account.mint == handshake.mint,
+        constraint = arbiter_token_account.owner == handshake.arbiter,
     )]
-    pub buyback_treasury: Account<'info, TokenAccount>,
+    pub arbiter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// Protocol authority (resolver)
-    pub authority: Signer<'info>,
+    /// The handshake's arbiter, or the protocol authority
+    pub resolver: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<ResolveDispute>, ruling: u8) -> Result<()> {
-    require!(ruling <= 1, SourError::InvalidRuling);
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ResolveDispute<'info>>,
+    ruling: DisputeRuling,
+) -> Result<()> {
+    // A panel case holds the dispute until it stalls
+    if !ctx.accounts.dispute_case.data_is_empty() {
+        let dispute_case =
+            DisputeCase::try_deserialize(&mut &ctx.accounts.dispute_case.try_borrow_data()?[..])?;
+        let registry = ctx
+            .accounts
+            .registry
+            .as_ref()
+            .ok_or(SourError::DisputeCaseOpen)?;
+        let handshake = &ctx.accounts.handshake;
+        require!(
+            dispute_case.is_stalled(
+                registry,
+                &[handshake.creator, handshake.worker, handshake.arbiter],
+                Clock::get()?.unix_timestamp,
+            ),
+            SourError::DisputeCaseOpen
+        );
+    }
 
-    let clock = Clock::get()?;
-    let handshake_id = ctx.accounts.handshake.id;
-    let amount = ctx.accounts.handshake.amount;
+    // Arbiter fee applies only when the arbiter itself resolves
+    let resolver = ctx.accounts.resolver.key();
+    let arbiter_fee_bps = if resolver == ctx.accounts.handshake.arbiter {
+        ctx.accounts.handshake.arbiter_fee_bps
+    } else {
+        0
+    };
 
-    let id_bytes = handshake_id.to_le_bytes();
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
     let vault_bump = ctx.accounts.handshake.vault_bump;
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"vault_auth",
@@ -105,151 +246,54 @@ pub fn handler(ctx: Context<ResolveDispute>, ruling: u8) -> Result<()> {
         &[vault_bump],
     ]];
 
-    if ruling == 0 {
-        // ===================================================================
-        // RULING 0: Refund creator — full amount, no fee
-        // ===================================================================
-        let vault_balance = ctx.accounts.vault.amount;
-        if vault_balance > 0 {
-            let transfer_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.creator_token_account.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                },
-                signer_seeds,
-            );
-            token::transfer(transfer_ctx, vault_balance)?;
-        }
-        msg!("Dispute #{} resolved: REFUND to creator", handshake_id);
-    } else {
-        // ===================================================================
-        // RULING 1: Pay worker — apply Pinch fee
-        // ===================================================================
-        let config = &ctx.accounts.config;
-
-        let pinch_total = (amount as u128)
-            .checked_mul(config.pinch_bps as u128)
-            .ok_or(SourError::MathOverflow)?
-            .checked_div(10_000)
-            .ok_or(SourError::MathOverflow)? as u64;
-
-        let burn_amount = (pinch_total as u128)
-            .checked_mul(config.treasury_share_bps as u128)
-            .ok_or(SourError::MathOverflow)?
-            .checked_div(10_000)
-            .ok_or(SourError::MathOverflow)? as u64;
-
-        let keepers_amount = (pinch_total as u128)
-            .checked_mul(config.keepers_share_bps as u128)
-            .ok_or(SourError::MathOverflow)?
-            .checked_div(10_000)
-            .ok_or(SourError::MathOverflow)? as u64;
-
-        let commons_amount = pinch_total
-            .checked_sub(burn_amount)
-            .ok_or(SourError::MathOverflow)?
-            .checked_sub(keepers_amount)
-            .ok_or(SourError::MathOverflow)?;
-
-        let worker_amount = amount
-            .checked_sub(pinch_total)
-            .ok_or(SourError::MathOverflow)?;
-
-        // Transfer to worker
-        if worker_amount > 0 {
-            let ctx_transfer = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.worker_token_account.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                },
-                signer_seeds,
-            );
-            token::transfer(ctx_transfer, worker_amount)?;
-        }
-
-        // Transfer to buyback+LP treasury
-        if burn_amount > 0 {
-            let treasury_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.buyback_treasury.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                },
+    let accounts = ctx.accounts;
+    let (team, hook_accounts) =
+        TeamPayout::split(accounts.team.as_deref(), ctx.remaining_accounts)?;
+    ruling::execute(
+        RulingAccounts {
+            config: &mut accounts.config,
+            handshake: &mut accounts.handshake,
+            allowed_mint: accounts.allowed_mint.as_deref_mut(),
+            vault_balance: accounts.vault.amount,
+            payout: VaultPayout {
+                token: MintTransfer::new(
+                    &accounts.token_program,
+                    &accounts.mint,
+                    hook_accounts,
+                ),
+                vault: accounts.vault.to_account_info(),
+                vault_authority: accounts.vault_authority.to_account_info(),
                 signer_seeds,
-            );
-            token::transfer(treasury_ctx, burn_amount)?;
-        }
-
-        // To keepers
-        if keepers_amount > 0 {
-            let ctx_keepers = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.keepers_pool.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                },
-                signer_seeds,
-            );
-            token::transfer(ctx_keepers, keepers_amount)?;
-        }
-
-        // To commons
-        if commons_amount > 0 {
-            let ctx_commons = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.commons_treasury.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                },
-                signer_seeds,
-            );
-            token::transfer(ctx_commons, commons_amount)?;
-        }
-
-        // Update config stats
-        let config = &mut ctx.accounts.config;
-        config.total_completed = config
-            .total_completed
-            .checked_add(1)
-            .ok_or(SourError::MathOverflow)?;
-        config.total_to_treasury = config
-            .total_to_treasury
-            .checked_add(burn_amount)
-            .ok_or(SourError::MathOverflow)?;
-        config.total_to_keepers = config
-            .total_to_keepers
-            .checked_add(keepers_amount)
-            .ok_or(SourError::MathOverflow)?;
-        config.total_to_commons = config
-            .total_to_commons
-            .checked_add(commons_amount)
-            .ok_or(SourError::MathOverflow)?;
-
-        msg!(
-            "Dispute #{} resolved: PAY worker. {} paid, {} to treasury",
-            handshake_id,
-            worker_amount,
-            burn_amount,
-        );
-    }
-
-    // Update handshake state
-    let handshake = &mut ctx.accounts.handshake;
-    handshake.status = HandshakeStatus::Resolved;
-    handshake.resolved_at = clock.unix_timestamp;
-
-    emit!(DisputeResolved {
-        handshake_id: handshake.id,
+            },
+            creator_token_account: accounts.creator_token_account.to_account_info(),
+            worker_token_account: accounts.worker_token_account.to_account_info(),
+            team,
+            keepers_pool: accounts.keepers_pool.to_account_info(),
+            commons_treasury: accounts.commons_treasury.to_account_info(),
+            buyback_treasury: accounts.buyback_treasury.to_account_info(),
+            bond_vault: accounts.bond_vault.to_account_info(),
+            collateral_vault: accounts
+                .collateral_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            subcontract: accounts.subcontract.as_deref_mut(),
+            subcontract_vault: accounts
+                .subcontract_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            payout_stream: accounts.payout_stream.as_deref(),
+            stream_vault: accounts
+                .stream_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            creator: accounts.creator.to_account_info(),
+            arbiter_token_account: accounts
+                .arbiter_token_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+        },
         ruling,
-        resolved_at: handshake.resolved_at,
-    });
-
-    Ok(())
+        resolver,
+        arbiter_fee_bps,
+    )
 }
@@ -0,0 +1,51 @@
+// ============================================================================
+// Revoke Invoice — the worker withdraws an unfunded invoice, or anyone
+// clears one whose deadline has passed
+//
+// Invoices hold no tokens, so this only closes the PDA; the rent always
+// goes back to the worker who paid it.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::Invoice;
+use crate::errors::SourError;
+use crate::events::InvoiceRevoked;
+
+#[derive(Accounts)]
+pub struct RevokeInvoice<'info> {
+    #[account(
+        mut,
+        close = worker,
+        seeds = [Invoice::SEED_PREFIX, invoice.id.to_le_bytes().as_ref()],
+        bump = invoice.bump,
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    /// Issuer of the invoice — receives the rent
+    /// CHECK: must match invoice.worker
+    #[account(mut, address = invoice.worker)]
+    pub worker: UncheckedAccount<'info>,
+
+    /// The worker, or anyone once the invoice has expired
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevokeInvoice>) -> Result<()> {
+    let invoice = &ctx.accounts.invoice;
+    let signer = ctx.accounts.signer.key();
+    let expired = Clock::get()?.unix_timestamp > invoice.deadline_ts;
+    require!(
+        signer == invoice.worker || expired,
+        SourError::InvoiceNotRevocable
+    );
+
+    emit!(InvoiceRevoked {
+        invoice_id: invoice.id,
+        revoked_by: signer,
+        expired,
+    });
+
+    msg!("Invoice #{} revoked", invoice.id);
+    Ok(())
+}
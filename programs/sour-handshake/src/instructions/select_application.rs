@@ -0,0 +1,256 @@
+// ============================================================================
+// Select Application — the creator hires one applicant on an open bounty
+//
+// Binds the applicant as worker and adopts their bid: the escrow is topped
+// up from the creator (or the difference refunded), the deadline moves to
+// the proposed one (milestone tranches shift by the same amount) and the
+// handshake goes straight to Accepted. Applicants close their own PDAs
+// with `close_application` to reclaim rent.
+//
+// The creator names the bid they expect (amount and deadline); selection
+// fails if the applicant has since replaced their application.
+//
+// Worker collateral is pulled from the applicant's token account through
+// the delegation made in `apply`. The creator opens the collateral vault
+// and is repaid its rent from the deposit the applicant left with the
+// application.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{Application, Handshake, HandshakeStatus, Milestones};
+use crate::errors::SourError;
+use crate::events::ApplicationSelected;
+use crate::escrow::{MintTransfer, VaultPayout};
+
+#[derive(Accounts)]
+pub struct SelectApplication<'info> {
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Created @ SourError::InvalidStatus,
+        constraint = handshake.is_open() @ SourError::InvalidStatus,
+        constraint = handshake.creator == creator.key() @ SourError::NotCreator,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// The chosen bid
+    #[account(
+        mut,
+        seeds = [
+            Application::SEED_PREFIX,
+            handshake.id.to_le_bytes().as_ref(),
+            application.applicant.as_ref(),
+        ],
+        bump = application.bump,
+    )]
+    pub application: Account<'info, Application>,
+
+    /// Milestone schedule — required for milestone handshakes
+    #[account(
+        mut,
+        seeds = [Milestones::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, Milestones>>,
+
+    /// The escrow vault
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Collateral vault — only for handshakes that require worker collateral
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Applicant's token account — source of the delegated collateral
+    #[account(
+        mut,
+        constraint = applicant_token_account.mint == handshake.mint,
+        constraint = applicant_token_account.owner == application.applicant,
+    )]
+    pub applicant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator's token account — funds a top-up or receives the difference
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == creator.key(),
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SelectApplication<'info>>,
+    expected_amount: u64,
+    expected_deadline_ts: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let bid_amount = ctx.accounts.application.amount;
+    let bid_deadline = ctx.accounts.application.deadline_ts;
+    require!(
+        bid_amount == expected_amount && bid_deadline == expected_deadline_ts,
+        SourError::BidMismatch
+    );
+    require!(
+        bid_deadline > clock.unix_timestamp,
+        SourError::InvalidApplication
+    );
+
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+
+    // Match the escrow to the bid
+    let escrowed = ctx.accounts.handshake.amount;
+    let mut topped_up = 0;
+    let mut refunded = 0;
+    if bid_amount > escrowed {
+        let before = ctx.accounts.vault.amount;
+        payout.token.transfer(
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            &[],
+            bid_amount - escrowed,
+        )?;
+        // Fee-on-transfer mints deliver less than was sent
+        ctx.accounts.vault.reload()?;
+        topped_up = ctx
+            .accounts
+            .vault
+            .amount
+            .checked_sub(before)
+            .ok_or(SourError::MathOverflow)?;
+    } else if bid_amount < escrowed {
+        refunded = escrowed - bid_amount;
+        payout.transfer(
+            &ctx.accounts.creator_token_account.to_account_info(),
+            refunded,
+        )?;
+    }
+
+    // Pull the applicant's delegated collateral
+    let worker_collateral = ctx.accounts.handshake.worker_collateral;
+    require!(
+        ctx.accounts.collateral_vault.is_some() == (worker_collateral > 0),
+        SourError::CollateralVaultMismatch
+    );
+    if let Some(collateral_vault) = ctx.accounts.collateral_vault.as_mut() {
+        let applicant_token_account = ctx
+            .accounts
+            .applicant_token_account
+            .as_ref()
+            .ok_or(SourError::CollateralAccountRequired)?;
+        payout.token.transfer(
+            &applicant_token_account.to_account_info(),
+            &collateral_vault.to_account_info(),
+            &ctx.accounts.vault_authority.to_account_info(),
+            signer_seeds,
+            worker_collateral,
+        )?;
+        // Fee-on-transfer mints deliver less than was sent — hold what arrived
+        collateral_vault.reload()?;
+        ctx.accounts.handshake.collateral_held = collateral_vault.amount;
+
+        // The applicant's deposit repays the vault's rent
+        let collateral_rent = std::mem::take(&mut ctx.accounts.application.collateral_rent);
+        let application = ctx.accounts.application.to_account_info();
+        let creator = ctx.accounts.creator.to_account_info();
+        **application.try_borrow_mut_lamports()? = application
+            .lamports()
+            .checked_sub(collateral_rent)
+            .ok_or(SourError::MathOverflow)?;
+        **creator.try_borrow_mut_lamports()? = creator
+            .lamports()
+            .checked_add(collateral_rent)
+            .ok_or(SourError::MathOverflow)?;
+    }
+
+    // Milestone bids only push the deadline out; the whole schedule moves
+    // with it so every tranche keeps the applicant's proposed slack
+    let handshake = &mut ctx.accounts.handshake;
+    require!(
+        ctx.accounts.milestones.is_some() == (handshake.milestone_count > 0),
+        SourError::MilestonesMismatch
+    );
+    if let Some(milestones) = ctx.accounts.milestones.as_mut() {
+        let delta_secs = bid_deadline
+            .checked_sub(handshake.deadline_ts)
+            .ok_or(SourError::MathOverflow)?;
+        milestones.shift(delta_secs)?;
+    }
+
+    handshake.amount = escrowed
+        .checked_add(topped_up)
+        .ok_or(SourError::MathOverflow)?
+        .checked_sub(refunded)
+        .ok_or(SourError::MathOverflow)?;
+    handshake.deadline_ts = bid_deadline;
+    handshake.worker = ctx.accounts.application.applicant;
+    handshake.status = HandshakeStatus::Accepted;
+    handshake.accepted_at = clock.unix_timestamp;
+
+    emit!(ApplicationSelected {
+        handshake_id: handshake.id,
+        worker: handshake.worker,
+        amount: handshake.amount,
+        deadline_ts: handshake.deadline_ts,
+        topped_up,
+        refunded,
+        collateral: handshake.collateral_held,
+    });
+
+    msg!(
+        "Handshake #{} hired {} at {}",
+        handshake.id,
+        handshake.worker,
+        handshake.amount
+    );
+    Ok(())
+}
@@ -0,0 +1,207 @@
+// ============================================================================
+// Settle Period — pay out (or refund) the current period of a retainer
+//
+// Periods settle in order, each for the escrow it received when funded.
+//
+//   Delivered   → worker paid with the standard Pinch split. The creator
+//                 may settle at once (approval); anyone may once the
+//                 protocol review window after delivery has passed.
+//   Undelivered → the period's escrow is refunded to the creator; anyone
+//                 may settle once the period has ended.
+//
+// Settling the last period of the run completes the retainer and returns
+// any leftover escrow to the creator.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{AllowedMint, ProtocolConfig, RecurringHandshake, RecurringStatus};
+use crate::errors::SourError;
+use crate::events::PeriodSettled;
+use crate::pinch::{FeeTerms, PinchSplit};
+use crate::escrow::{MintTransfer, VaultPayout};
+
+#[derive(Accounts)]
+pub struct SettlePeriod<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [RecurringHandshake::SEED_PREFIX, recurring.id.to_le_bytes().as_ref()],
+        bump = recurring.bump,
+        constraint = recurring.status == RecurringStatus::Active @ SourError::InvalidStatus,
+    )]
+    pub recurring: Account<'info, RecurringHandshake>,
+
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        mut,
+        seeds = [AllowedMint::SEED_PREFIX, recurring.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// The recurring vault
+    #[account(
+        mut,
+        seeds = [b"recurring_vault", recurring.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = recurring.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the recurring vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"recurring_auth", recurring.id.to_le_bytes().as_ref()],
+        bump = recurring.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Worker's token account — receives a delivered period's payout
+    #[account(
+        mut,
+        constraint = worker_token_account.mint == recurring.mint,
+        constraint = worker_token_account.owner == recurring.worker,
+    )]
+    pub worker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Creator's token account — receives refunds
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == recurring.mint,
+        constraint = creator_token_account.owner == recurring.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Keepers pool — receives 30% of Pinch
+    #[account(mut)]
+    pub keepers_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Commons treasury — receives 20% of Pinch
+    #[account(mut)]
+    pub commons_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Buyback+LP treasury — receives 50% of Pinch
+    #[account(mut)]
+    pub buyback_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// The creator, or anyone once the period is settleable
+    pub settler: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SettlePeriod<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let recurring = &ctx.accounts.recurring;
+    let period = recurring.settled_periods;
+    let funded = recurring.period(period)?;
+    let delivered = funded.delivered_at > 0;
+
+    // Who may settle, and when
+    let settleable = if delivered {
+        let review_ends_at = funded
+            .delivered_at
+            .checked_add(ctx.accounts.config.review_window_secs)
+            .ok_or(SourError::MathOverflow)?;
+        ctx.accounts.settler.key() == recurring.creator || clock.unix_timestamp >= review_ends_at
+    } else {
+        clock.unix_timestamp >= recurring.period_start(period + 1)?
+    };
+    require!(settleable, SourError::PeriodNotSettleable);
+
+    let id_bytes = recurring.id.to_le_bytes();
+    let vault_bump = recurring.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"recurring_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+
+    let amount = funded.escrowed;
+    let mut split = PinchSplit::default();
+    let mut refunded = 0;
+    if delivered {
+        let fee_terms = FeeTerms::for_mint(
+            &recurring.mint,
+            &ctx.accounts.config,
+            ctx.accounts.allowed_mint.as_deref(),
+        )?;
+        fee_terms.check_destinations(
+            &ctx.accounts.keepers_pool.key(),
+            &ctx.accounts.commons_treasury.key(),
+            &ctx.accounts.buyback_treasury.key(),
+        )?;
+        split = PinchSplit::compute(amount, &fee_terms)?;
+        payout.pay_split(
+            &split,
+            &ctx.accounts.worker_token_account.to_account_info(),
+            None,
+            &ctx.accounts.buyback_treasury.to_account_info(),
+            &ctx.accounts.keepers_pool.to_account_info(),
+            &ctx.accounts.commons_treasury.to_account_info(),
+        )?;
+        split.record(&mut ctx.accounts.config, ctx.accounts.allowed_mint.as_deref_mut())?;
+    } else {
+        refunded = amount;
+        payout.transfer(&ctx.accounts.creator_token_account.to_account_info(), amount)?;
+    }
+
+    let recurring = &mut ctx.accounts.recurring;
+    recurring.settled_periods += 1;
+
+    // Last period of the run: complete and return any leftover escrow
+    let completed = recurring.settled_periods >= recurring.end_periods;
+    if completed {
+        recurring.status = RecurringStatus::Completed;
+        ctx.accounts.vault.reload()?;
+        let leftover = ctx.accounts.vault.amount;
+        payout.transfer(&ctx.accounts.creator_token_account.to_account_info(), leftover)?;
+        refunded = refunded
+            .checked_add(leftover)
+            .ok_or(SourError::MathOverflow)?;
+    }
+
+    emit!(PeriodSettled {
+        recurring_id: recurring.id,
+        period,
+        settled_by: ctx.accounts.settler.key(),
+        delivered,
+        to_worker: split.to_worker,
+        pinch_total: split.pinch_total,
+        to_treasury: split.to_treasury,
+        to_keepers: split.to_keepers,
+        to_commons: split.to_commons,
+        refunded,
+        completed,
+    });
+
+    msg!(
+        "Recurring handshake #{} — period {} settled ({} to worker, {} refunded)",
+        recurring.id,
+        period,
+        split.to_worker,
+        refunded
+    );
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// ============================================================================
+// Stop Recurring — either side stops a retainer from renewing
+//
+// Before acceptance this cancels the retainer and refunds the creator in
+// full. Afterwards the run is cut short at the end of the period currently
+// running: periods already started still deliver and settle as usual, and
+// escrow funded for later periods goes back to the creator at once.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{RecurringHandshake, RecurringStatus};
+use crate::errors::SourError;
+use crate::events::RecurringStopped;
+use crate::escrow::{MintTransfer, VaultPayout};
+
+#[derive(Accounts)]
+pub struct StopRecurring<'info> {
+    #[account(
+        mut,
+        seeds = [RecurringHandshake::SEED_PREFIX, recurring.id.to_le_bytes().as_ref()],
+        bump = recurring.bump,
+        constraint = matches!(
+            recurring.status,
+            RecurringStatus::Created | RecurringStatus::Active
+        ) @ SourError::InvalidStatus,
+    )]
+    pub recurring: Account<'info, RecurringHandshake>,
+
+    /// The recurring vault
+    #[account(
+        mut,
+        seeds = [b"recurring_vault", recurring.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = recurring.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the recurring vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"recurring_auth", recurring.id.to_le_bytes().as_ref()],
+        bump = recurring.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account — receives the refund
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == recurring.mint,
+        constraint = creator_token_account.owner == recurring.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Creator or worker
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, StopRecurring<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let signer = ctx.accounts.signer.key();
+    let recurring = &mut ctx.accounts.recurring;
+    require!(
+        signer == recurring.creator || signer == recurring.worker,
+        SourError::NotParticipant
+    );
+
+    // New end of the run: nothing after the running period, nothing unfunded
+    let end_periods = if recurring.status == RecurringStatus::Created {
+        0
+    } else {
+        let running = recurring.period_at(clock.unix_timestamp).saturating_add(1);
+        running
+            .max(recurring.settled_periods)
+            .min(recurring.end_periods)
+            .min(recurring.funded_periods)
+    };
+
+    // Keep escrow for the periods still to settle, refund the rest
+    let kept = recurring.escrowed_between(recurring.settled_periods, end_periods)?;
+    let refunded = ctx.accounts.vault.amount.saturating_sub(kept);
+
+    let id_bytes = recurring.id.to_le_bytes();
+    let vault_bump = recurring.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"recurring_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+    VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    }
+    .transfer(&ctx.accounts.creator_token_account.to_account_info(), refunded)?;
+
+    recurring.end_periods = end_periods;
+    recurring.funded_periods = end_periods;
+    recurring.periods.truncate(end_periods as usize);
+    let completed = recurring.settled_periods >= end_periods;
+    if recurring.status == RecurringStatus::Created {
+        recurring.status = RecurringStatus::Cancelled;
+    } else if completed {
+        recurring.status = RecurringStatus::Completed;
+    }
+
+    emit!(RecurringStopped {
+        recurring_id: recurring.id,
+        stopped_by: signer,
+        end_periods,
+        refunded,
+        completed,
+        stopped_at: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Recurring handshake #{} stopped after {} periods; {} refunded",
+        recurring.id,
+        end_periods,
+        refunded
+    );
+    Ok(())
+}
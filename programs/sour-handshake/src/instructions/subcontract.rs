@@ -0,0 +1,194 @@
+// ============================================================================
+// Subcontract — Baker B carves part of an accepted handshake out to another
+// worker
+//
+// Creates a child handshake (next handshake ID) with the parent's worker as
+// its creator and the subcontractor as its worker, in the parent's mint.
+// The child is not funded up front: its escrow is a lien on the parent,
+// paid out of the parent's worker share into the child's vault when the
+// parent is approved (or claimed after review). Until then the child can be
+// accepted and worked on but not approved. The lien is capped at the
+// parent's expected worker payout, and parent and child record each
+// other's IDs.
+//
+// One subcontract per handshake. Milestone and team handshakes cannot be
+// subcontracted — a team's lien would come out of every payee's share.
+// The subcontractor must be named: the child is never an open bounty.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{AllowedMint, Handshake, HandshakeInit, HandshakeStatus, ProtocolConfig};
+use crate::errors::SourError;
+use crate::events::SubcontractCreated;
+use crate::pinch::{FeeTerms, PinchSplit};
+
+#[derive(Accounts)]
+pub struct Subcontract<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// The handshake being subcontracted
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, parent.id.to_le_bytes().as_ref()],
+        bump = parent.bump,
+        constraint = parent.status == HandshakeStatus::Accepted @ SourError::InvalidStatus,
+        constraint = parent.worker == worker.key() @ SourError::NotWorker,
+    )]
+    pub parent: Account<'info, Handshake>,
+
+    /// Allowlist entry for the escrow mint — omit for $SOUR escrows
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, parent.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    #[account(
+        init,
+        payer = worker,
+        space = 8 + Handshake::INIT_SPACE,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            config.handshake_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// The subcontract's escrow vault — empty until the parent pays the lien
+    #[account(
+        init,
+        payer = worker,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"vault", config.handshake_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA derived from seeds, no data needed
+    #[account(
+        seeds = [b"vault_auth", config.handshake_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// The subcontractor's wallet address — not signing, just referenced
+    /// CHECK: any pubkey but the default and the worker, validated in handler
+    pub subcontractor: UncheckedAccount<'info>,
+
+    /// Escrow mint of the parent
+    #[account(address = parent.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The parent's worker — creator of the subcontract
+    #[account(mut)]
+    pub worker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<Subcontract>,
+    description: String,
+    amount: u64,
+    deadline_ts: i64,
+) -> Result<()> {
+    require!(description.len() <= 280, SourError::DescriptionTooLong);
+
+    let subcontractor = ctx.accounts.subcontractor.key();
+    require!(
+        subcontractor != ctx.accounts.worker.key(),
+        SourError::SelfHandshake
+    );
+    require!(
+        subcontractor != Pubkey::default(),
+        SourError::InvalidSubcontract
+    );
+
+    // One subcontract per handshake, not for milestone or team handshakes,
+    // and not while this handshake still awaits its own lien
+    let parent = &ctx.accounts.parent;
+    require!(parent.child_id.is_none(), SourError::SubcontractExists);
+    require!(
+        parent.milestone_count == 0 && !parent.team_payout && parent.lien == 0,
+        SourError::InvalidSubcontract
+    );
+
+    // Lien: within the parent's expected worker payout; deadline: within
+    // the parent's
+    let fee_terms = FeeTerms::for_mint(
+        &parent.mint,
+        &ctx.accounts.config,
+        ctx.accounts.allowed_mint.as_deref(),
+    )?;
+    let expected_payout = PinchSplit::compute(parent.remaining()?, &fee_terms)?.to_worker;
+    let clock = Clock::get()?;
+    require!(
+        amount > 0
+            && amount <= expected_payout
+            && deadline_ts > clock.unix_timestamp
+            && deadline_ts <= parent.deadline_ts,
+        SourError::InvalidSubcontract
+    );
+
+    // Initialize the child handshake — Created, awaiting the lien
+    let handshake = &mut ctx.accounts.handshake;
+    let config = &mut ctx.accounts.config;
+
+    handshake.init(HandshakeInit {
+        id: config.handshake_count,
+        creator: parent.worker,
+        worker: subcontractor,
+        mint: parent.mint,
+        amount,
+        description: description.clone(),
+        created_at: clock.unix_timestamp,
+        deadline_ts,
+        review_window_secs: config.review_window_secs,
+        parent_id: Some(parent.id),
+        lien: amount,
+        vault_bump: ctx.bumps.vault_authority,
+        bump: ctx.bumps.handshake,
+        ..HandshakeInit::default()
+    });
+
+    // Link the parent to its child and record what it owes
+    let parent = &mut ctx.accounts.parent;
+    parent.child_id = Some(handshake.id);
+    parent.lien = amount;
+
+    // Increment global counter
+    config.handshake_count = config
+        .handshake_count
+        .checked_add(1)
+        .ok_or(SourError::MathOverflow)?;
+
+    emit!(SubcontractCreated {
+        parent_id: parent.id,
+        handshake_id: handshake.id,
+        creator: handshake.creator,
+        worker: subcontractor,
+        lien: amount,
+        deadline_ts,
+        description,
+    });
+
+    msg!(
+        "Handshake #{} subcontracted as #{}: lien of {}",
+        parent.id,
+        handshake.id,
+        amount
+    );
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// ============================================================================
+// Submit Evidence — a participant appends to the dispute's evidence log
+//
+// Only while the handshake is Disputed and before a resolution starts
+// (a panel is drawn). Each side may submit up to 4 entries; the content
+// itself lives off-chain, the log keeps its hash and location.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{EvidenceEntry, EvidenceLog, Handshake, HandshakeStatus};
+use crate::errors::SourError;
+use crate::events::EvidenceSubmitted;
+
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info> {
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Disputed @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        seeds = [EvidenceLog::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = evidence_log.bump,
+        constraint = !evidence_log.closed @ SourError::EvidenceClosed,
+    )]
+    pub evidence_log: Account<'info, EvidenceLog>,
+
+    /// Must be either creator or worker
+    pub submitter: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SubmitEvidence>, content_hash: [u8; 32], uri: String) -> Result<()> {
+    let handshake = &ctx.accounts.handshake;
+    let submitter = ctx.accounts.submitter.key();
+
+    // Only creator or worker can submit
+    require!(
+        submitter == handshake.creator || submitter == handshake.worker,
+        SourError::NotParticipant
+    );
+    require!(
+        uri.len() <= EvidenceLog::MAX_URI_LEN,
+        SourError::EvidenceUriTooLong
+    );
+
+    let evidence_log = &mut ctx.accounts.evidence_log;
+    require!(
+        evidence_log.entries_by(&submitter) < EvidenceLog::MAX_ENTRIES_PER_SIDE,
+        SourError::EvidenceLimitReached
+    );
+
+    let clock = Clock::get()?;
+    let index = evidence_log.entries.len() as u8;
+    evidence_log.entries.push(EvidenceEntry {
+        submitter,
+        content_hash,
+        uri: uri.clone(),
+        submitted_at: clock.unix_timestamp,
+    });
+
+    emit!(EvidenceSubmitted {
+        handshake_id: handshake.id,
+        submitter,
+        index,
+        content_hash,
+        uri,
+        submitted_at: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Evidence #{} submitted on handshake #{} by {}",
+        index,
+        handshake.id,
+        submitter
+    );
+    Ok(())
+}
@@ -0,0 +1,94 @@
+// ============================================================================
+// Update Allowed Mint — authority adjusts or disables an allowlist entry
+//
+// Disabling only blocks new handshakes; escrows already open in the mint
+// keep settling against the entry's fee terms.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::{AllowedMint, ProtocolConfig};
+use crate::errors::SourError;
+use crate::events::AllowedMintUpdated;
+
+#[derive(Accounts)]
+pub struct UpdateAllowedMint<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ SourError::NotAuthority,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [AllowedMint::SEED_PREFIX, allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    /// New Keepers pool — omit to keep the current one
+    #[account(constraint = keepers_pool.mint == allowed_mint.mint @ SourError::InvalidFeeDestination)]
+    pub keepers_pool: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// New Commons treasury — omit to keep the current one
+    #[account(constraint = commons_treasury.mint == allowed_mint.mint @ SourError::InvalidFeeDestination)]
+    pub commons_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// New Buyback+LP treasury — omit to keep the current one
+    #[account(constraint = buyback_treasury.mint == allowed_mint.mint @ SourError::InvalidFeeDestination)]
+    pub buyback_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateAllowedMint>,
+    new_pinch_bps: Option<u16>,
+    enabled: Option<bool>,
+    new_dispute_bond: Option<u64>,
+) -> Result<()> {
+    let allowed_mint = &mut ctx.accounts.allowed_mint;
+
+    if let Some(bps) = new_pinch_bps {
+        require!(bps <= 5000, SourError::InvalidPinchBps);
+        allowed_mint.pinch_bps = bps;
+    }
+
+    if let Some(enabled) = enabled {
+        allowed_mint.enabled = enabled;
+    }
+
+    if let Some(bond) = new_dispute_bond {
+        allowed_mint.dispute_bond = bond;
+    }
+
+    if let Some(keepers_pool) = &ctx.accounts.keepers_pool {
+        allowed_mint.keepers_pool = keepers_pool.key();
+    }
+    if let Some(commons_treasury) = &ctx.accounts.commons_treasury {
+        allowed_mint.commons_treasury = commons_treasury.key();
+    }
+    if let Some(buyback_treasury) = &ctx.accounts.buyback_treasury {
+        allowed_mint.buyback_treasury = buyback_treasury.key();
+    }
+
+    emit!(AllowedMintUpdated {
+        mint: allowed_mint.mint,
+        enabled: allowed_mint.enabled,
+        pinch_bps: allowed_mint.pinch_bps,
+        dispute_bond: allowed_mint.dispute_bond,
+        keepers_pool: allowed_mint.keepers_pool,
+        commons_treasury: allowed_mint.commons_treasury,
+        buyback_treasury: allowed_mint.buyback_treasury,
+    });
+
+    msg!(
+        "Allowed mint {} updated — enabled: {}, Pinch: {}bps",
+        allowed_mint.mint,
+        allowed_mint.enabled,
+        allowed_mint.pinch_bps
+    );
+    Ok(())
+}
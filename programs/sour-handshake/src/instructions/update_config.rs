@@ -0,0 +1,110 @@
+// ============================================================================
+// Update Config — authority adjusts fees, shares, dispute bond and fee
+// destinations
+//
+// Omitted arguments / accounts keep their current value; the resulting
+// config is re-validated as a whole (shares must still sum to 100%).
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::{ConfigUpdate, ProtocolConfig};
+use crate::errors::SourError;
+use crate::events::ConfigUpdated;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ SourError::NotAuthority,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// New Keepers pool — omit to keep the current one
+    #[account(constraint = keepers_pool.mint == config.sour_mint @ SourError::InvalidFeeDestination)]
+    pub keepers_pool: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// New Commons treasury — omit to keep the current one
+    #[account(constraint = commons_treasury.mint == config.sour_mint @ SourError::InvalidFeeDestination)]
+    pub commons_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// New Buyback+LP treasury — omit to keep the current one
+    #[account(constraint = buyback_treasury.mint == config.sour_mint @ SourError::InvalidFeeDestination)]
+    pub buyback_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(bps) = update.pinch_bps {
+        config.pinch_bps = bps;
+    }
+    if let Some(bps) = update.treasury_share_bps {
+        config.treasury_share_bps = bps;
+    }
+    if let Some(bps) = update.keepers_share_bps {
+        config.keepers_share_bps = bps;
+    }
+    if let Some(bps) = update.commons_share_bps {
+        config.commons_share_bps = bps;
+    }
+    if let Some(bps) = update.crank_tip_bps {
+        config.crank_tip_bps = bps;
+    }
+    if let Some(secs) = update.review_window_secs {
+        config.review_window_secs = secs;
+    }
+    if let Some(bond) = update.dispute_bond {
+        config.dispute_bond = bond;
+    }
+    if let Some(bps) = update.collateral_commons_bps {
+        config.collateral_commons_bps = bps;
+    }
+    if let Some(bps) = update.bonus_pinch_bps {
+        config.bonus_pinch_bps = bps;
+    }
+
+    if let Some(keepers_pool) = &ctx.accounts.keepers_pool {
+        config.keepers_pool = keepers_pool.key();
+    }
+    if let Some(commons_treasury) = &ctx.accounts.commons_treasury {
+        config.commons_treasury = commons_treasury.key();
+    }
+    if let Some(buyback_treasury) = &ctx.accounts.buyback_treasury {
+        config.buyback_treasury = buyback_treasury.key();
+    }
+
+    // Validate: Pinch rate, fee shares, crank tip, review window, collateral
+    // slice, bonus Pinch
+    config.validate()?;
+
+    emit!(ConfigUpdated {
+        authority: config.authority,
+        pinch_bps: config.pinch_bps,
+        treasury_share_bps: config.treasury_share_bps,
+        keepers_share_bps: config.keepers_share_bps,
+        commons_share_bps: config.commons_share_bps,
+        crank_tip_bps: config.crank_tip_bps,
+        review_window_secs: config.review_window_secs,
+        dispute_bond: config.dispute_bond,
+        collateral_commons_bps: config.collateral_commons_bps,
+        bonus_pinch_bps: config.bonus_pinch_bps,
+        keepers_pool: config.keepers_pool,
+        commons_treasury: config.commons_treasury,
+        buyback_treasury: config.buyback_treasury,
+    });
+
+    msg!(
+        "SOUR Protocol config updated. Pinch: {}bps ({}/{}/{})",
+        config.pinch_bps,
+        config.treasury_share_bps,
+        config.keepers_share_bps,
+        config.commons_share_bps
+    );
+    Ok(())
+}
@@ -0,0 +1,247 @@
+// ============================================================================
+// Withdraw From Handshake — the worker backs out after accepting
+//
+// The escrow still in the vault goes back to the creator (tranches already
+// approved stay paid) and the withdrawal is logged on the worker's record,
+// which is therefore required. Any worker collateral is forfeited as on
+// expiry: to the creator, minus the config's Commons slice.
+//
+// The collateral vault and a vested handshake's payout stream are closed,
+// their rent returned to the creator.
+//
+// A parent's subcontract lien is released (the worker earns nothing to pay
+// it from) and the subcontract cancelled.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    AllowedMint, Handshake, HandshakeStatus, PayoutStream, ProtocolConfig, WorkerRecord,
+};
+use crate::errors::SourError;
+use crate::events::WorkerWithdrew;
+use crate::pinch::collateral_commons;
+use crate::escrow::{MintTransfer, VaultPayout};
+use crate::settlement::settle_lien;
+
+#[derive(Accounts)]
+pub struct WithdrawFromHandshake<'info> {
+    #[account(
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Accepted @ SourError::InvalidStatus,
+        constraint = handshake.worker == worker.key() @ SourError::NotWorker,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    /// Worker's track record — the withdrawal is logged here
+    #[account(
+        mut,
+        seeds = [WorkerRecord::SEED_PREFIX, worker.key().as_ref()],
+        bump = worker_record.bump,
+    )]
+    pub worker_record: Account<'info, WorkerRecord>,
+
+    /// Allowlist entry for the escrow mint — only needed (for non-$SOUR
+    /// escrows) when collateral is forfeited with a Commons slice
+    #[account(
+        seeds = [AllowedMint::SEED_PREFIX, handshake.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+
+    /// The escrow vault
+    #[account(
+        mut,
+        seeds = [b"vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vault
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Creator's token account — receives the refund
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == handshake.mint,
+        constraint = creator_token_account.owner == handshake.creator,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral vault — required when the handshake holds worker
+    /// collateral; closed to the creator
+    #[account(
+        mut,
+        seeds = [b"collateral_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collateral_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Subcontract carved out of this handshake — required while its lien
+    /// is outstanding
+    #[account(
+        mut,
+        seeds = [
+            Handshake::SEED_PREFIX,
+            handshake.child_id.unwrap_or_default().to_le_bytes().as_ref(),
+        ],
+        bump = subcontract.bump,
+    )]
+    pub subcontract: Option<Account<'info, Handshake>>,
+
+    /// Commons treasury — receives its slice of forfeited collateral
+    #[account(mut)]
+    pub commons_treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payout stream — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        close = creator,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+    )]
+    pub payout_stream: Option<Account<'info, PayoutStream>>,
+
+    /// Stream vault — required when the handshake vests its payout;
+    /// closed to the creator
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator — receives the rent of the accounts closed here
+    /// CHECK: must match handshake.creator
+    #[account(mut, address = handshake.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub worker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawFromHandshake<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let handshake = &ctx.accounts.handshake;
+    require!(
+        ctx.accounts.collateral_vault.is_some() == (handshake.collateral_held > 0),
+        SourError::CollateralVaultMismatch
+    );
+    require!(
+        ctx.accounts.payout_stream.is_some() == handshake.vested_payout
+            && ctx.accounts.stream_vault.is_some() == handshake.vested_payout,
+        SourError::PayoutStreamMismatch
+    );
+
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    // Refund what is left in the vault to the creator
+    let refunded = ctx.accounts.vault.amount;
+    let payout = VaultPayout {
+        token: MintTransfer::new(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            ctx.remaining_accounts,
+        ),
+        vault: ctx.accounts.vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+    payout.transfer(
+        &ctx.accounts.creator_token_account.to_account_info(),
+        refunded,
+    )?;
+
+    // Walking away forfeits the worker's collateral
+    let collateral_forfeited = ctx.accounts.handshake.take_collateral();
+    if let Some(collateral_vault) = &ctx.accounts.collateral_vault {
+        let commons_treasury = collateral_commons(
+            &ctx.accounts.handshake.mint,
+            &ctx.accounts.config,
+            ctx.accounts.allowed_mint.as_deref(),
+            ctx.accounts
+                .commons_treasury
+                .as_ref()
+                .map(|account| account.to_account_info()),
+        )?;
+        payout.forfeit_collateral(
+            &collateral_vault.to_account_info(),
+            collateral_forfeited,
+            &ctx.accounts.creator_token_account.to_account_info(),
+            commons_treasury.as_ref(),
+            ctx.accounts.config.collateral_commons_bps,
+        )?;
+        payout.close_vault(
+            &collateral_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
+    // The payout never started streaming — hand back the stream's rent
+    if let Some(stream_vault) = &ctx.accounts.stream_vault {
+        payout.close_vault(
+            &stream_vault.to_account_info(),
+            &ctx.accounts.creator_token_account.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
+    // Nothing is left to fund a subcontract with
+    settle_lien(
+        &mut ctx.accounts.handshake,
+        ctx.accounts.subcontract.as_deref_mut(),
+        None,
+        &payout,
+        None,
+        false,
+        clock.unix_timestamp,
+    )?;
+
+    let record = &mut ctx.accounts.worker_record;
+    record.record_withdrawal()?;
+
+    let handshake = &mut ctx.accounts.handshake;
+    handshake.status = HandshakeStatus::Cancelled;
+    handshake.resolved_at = clock.unix_timestamp;
+
+    emit!(WorkerWithdrew {
+        handshake_id: handshake.id,
+        worker: handshake.worker,
+        refunded,
+        withdrawals: record.withdrawals,
+        collateral_forfeited,
+        withdrawn_at: handshake.resolved_at,
+    });
+
+    msg!("Worker withdrew from handshake #{}. {} refunded.", handshake.id, refunded);
+    Ok(())
+}
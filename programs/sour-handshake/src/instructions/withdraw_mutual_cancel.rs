@@ -0,0 +1,52 @@
+// ============================================================================
+// Withdraw Mutual Cancel — the proposer withdraws, or the counterparty
+// rejects, a pending cancel proposal. Rent goes back to the proposer.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{CancelProposal, Handshake};
+use crate::errors::SourError;
+use crate::events::MutualCancelWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawMutualCancel<'info> {
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [CancelProposal::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = cancel_proposal.bump,
+    )]
+    pub cancel_proposal: Account<'info, CancelProposal>,
+
+    /// The proposer — receives the proposal's rent back
+    /// CHECK: address checked against the proposal
+    #[account(mut, address = cancel_proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Creator or worker
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawMutualCancel>) -> Result<()> {
+    let handshake = &ctx.accounts.handshake;
+    let signer_key = ctx.accounts.signer.key();
+    require!(
+        signer_key == handshake.creator || signer_key == handshake.worker,
+        SourError::NotParticipant
+    );
+
+    emit!(MutualCancelWithdrawn {
+        handshake_id: handshake.id,
+        withdrawn_by: signer_key,
+    });
+
+    msg!("Mutual cancel for handshake #{} withdrawn", handshake.id);
+    Ok(())
+}
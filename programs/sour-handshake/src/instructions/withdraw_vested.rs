@@ -0,0 +1,120 @@
+// ============================================================================
+// Withdraw Vested — the worker pulls whatever has vested from the payout
+// stream
+//
+// Callable any time after approval; nothing is released before the cliff,
+// then the payout unlocks linearly until the end of the vesting period.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{Handshake, HandshakeStatus, PayoutStream};
+use crate::errors::SourError;
+use crate::events::VestedWithdrawn;
+use crate::escrow::MintTransfer;
+
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    #[account(
+        seeds = [Handshake::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.bump,
+        constraint = handshake.status == HandshakeStatus::Approved @ SourError::InvalidStatus,
+    )]
+    pub handshake: Account<'info, Handshake>,
+
+    #[account(
+        mut,
+        seeds = [PayoutStream::SEED_PREFIX, handshake.id.to_le_bytes().as_ref()],
+        bump = payout_stream.bump,
+        constraint = payout_stream.worker == worker.key() @ SourError::NotWorker,
+    )]
+    pub payout_stream: Account<'info, PayoutStream>,
+
+    /// Stream vault holding the unvested and unwithdrawn payout
+    #[account(
+        mut,
+        seeds = [b"stream_vault", handshake.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stream_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow mint (needed by `transfer_checked`)
+    #[account(address = handshake.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA authority over the vaults
+    /// CHECK: PDA seeds verified
+    #[account(
+        seeds = [b"vault_auth", handshake.id.to_le_bytes().as_ref()],
+        bump = handshake.vault_bump,
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Worker's token account — receives the vested tokens
+    #[account(
+        mut,
+        constraint = worker_token_account.mint == handshake.mint,
+        constraint = worker_token_account.owner == worker.key(),
+    )]
+    pub worker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub worker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawVested<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let stream = &ctx.accounts.payout_stream;
+    let amount = stream
+        .vested(clock.unix_timestamp)?
+        .checked_sub(stream.withdrawn)
+        .ok_or(SourError::MathOverflow)?;
+    require!(amount > 0, SourError::NothingVested);
+
+    let id_bytes = ctx.accounts.handshake.id.to_le_bytes();
+    let vault_bump = ctx.accounts.handshake.vault_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_auth",
+        id_bytes.as_ref(),
+        &[vault_bump],
+    ]];
+
+    MintTransfer::new(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.remaining_accounts,
+    )
+    .transfer(
+        &ctx.accounts.stream_vault.to_account_info(),
+        &ctx.accounts.worker_token_account.to_account_info(),
+        &ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+        amount,
+    )?;
+
+    let stream = &mut ctx.accounts.payout_stream;
+    stream.withdrawn = stream
+        .withdrawn
+        .checked_add(amount)
+        .ok_or(SourError::MathOverflow)?;
+
+    emit!(VestedWithdrawn {
+        handshake_id: stream.handshake_id,
+        worker: stream.worker,
+        amount,
+        withdrawn: stream.withdrawn,
+        total: stream.total,
+        withdrawn_at: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Handshake #{} — {} vested withdrawn ({}/{})",
+        stream.handshake_id,
+        amount,
+        stream.withdrawn,
+        stream.total
+    );
+    Ok(())
+}
@@ -4,16 +4,28 @@
 // ============================================================================
 //
 // Flow:
-//   Baker A creates a handshake (escrows $SOUR into PDA vault)
-//   Baker B accepts the handshake
-//   Baker B delivers work
-//   Baker A approves → funds released, Pinch fee applied:
+//   Baker A creates a handshake (escrows $SOUR — or an allowlisted mint
+//     such as USDC, classic SPL or Token-2022 — into PDA vault),
+//     optionally split into up to 8 milestone tranches
+//   Baker B accepts the handshake (or, for an open bounty, the first
+//     eligible Baker to accept becomes Baker B)
+//   Baker B delivers work (one milestone at a time)
+//   Baker A approves → funds released (per tranche), Pinch fee applied:
 //     - 50% of fee sent to Treasury PDA (batched → buyback + LP)
 //     - 30% of fee to Keepers pool
 //     - 20% of fee to Commons treasury
+//   (if Baker A stays silent past the review window, anyone may call
+//    claim_after_review to release the same split to Baker B)
 //
 //   OR: either party disputes → enters dispute resolution
-//   OR: creator cancels (only before acceptance)
+//       (handshake arbiter / authority rules, or a panel drawn from the
+//        arbiter registry votes and anyone cranks finalize_dispute)
+//   OR: creator cancels (only before acceptance), or both parties agree
+//       to cancel later with a negotiated split
+//   OR: Baker B declines (before accepting) or withdraws (after), and
+//       Baker A is refunded
+//   OR: deadline passes before delivery → anyone cranks expire_handshake,
+//       escrow refunded to Baker A (minus a small crank tip)
 // ============================================================================
 
 use anchor_lang::prelude::*;
@@ -22,8 +34,13 @@ pub mod state;
 pub mod instructions;
 pub mod errors;
 pub mod events;
+pub mod pinch;
+pub mod escrow;
+pub mod ruling;
+pub mod settlement;
 
 use instructions::*;
+use state::{ConfigUpdate, CreateOptions, DisputeRuling, MilestoneInput};
 
 declare_id!("HUAq4NFymfn4hNvs7RMNCC5uFEoRctkWDWCA9G7prxeF");
 
@@ -31,51 +48,342 @@ declare_id!("HUAq4NFymfn4hNvs7RMNCC5uFEoRctkWDWCA9G7prxeF");
 pub mod sour_handshake {
     use super::*;
 
-    /// Create a new handshake — escrow $SOUR tokens into a PDA vault.
+    /// Create a new handshake — escrow $SOUR (or an allowlisted mint) into
+    /// a PDA vault.
     /// Only the initiator (Baker A) can call this.
-    pub fn create_handshake(
-        ctx: Context<CreateHandshake>,
+    /// Pass milestone tranches (summing to `amount`) to pay out in stages,
+    /// and optionally an arbiter (with fee) to resolve disputes. Omit the
+    /// worker to post an open bounty, and set `worker_collateral` to have the
+    /// worker lock a stake on acceptance. `max_revisions` caps revision
+    /// requests; `vesting` streams the worker's payout after approval, and
+    /// `team` splits it across payees led by the worker.
+    pub fn create_handshake<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateHandshake<'info>>,
         description: String,
         amount: u64,
         deadline_ts: i64,
+        milestones: Vec<MilestoneInput>,
+        options: CreateOptions, // review window, arbiter, bounty eligibility
     ) -> Result<()> {
-        instructions::create::handler(ctx, description, amount, deadline_ts)
+        instructions::create::handler(ctx, description, amount, deadline_ts, milestones, options)
     }
 
-    /// Baker B accepts the handshake, committing to deliver.
-    pub fn accept_handshake(ctx: Context<AcceptHandshake>) -> Result<()> {
+    /// Baker B accepts the handshake, committing to deliver (and to the
+    /// arbiter named at creation, if any). On an open bounty the first
+    /// eligible signer becomes the worker. Locks the worker collateral, if
+    /// one is required.
+    pub fn accept_handshake<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptHandshake<'info>>,
+    ) -> Result<()> {
         instructions::accept::handler(ctx)
     }
 
-    /// Baker B marks work as delivered.
-    pub fn deliver(ctx: Context<Deliver>) -> Result<()> {
-        instructions::deliver::handler(ctx)
+    /// Open a worker's track record (completed handshakes). Permissionless.
+    pub fn initialize_worker_record(ctx: Context<InitializeWorkerRecord>) -> Result<()> {
+        instructions::init_worker_record::handler(ctx)
+    }
+
+    /// Bid on an open bounty with a proposed amount, deadline and pitch hash.
+    /// Any required worker collateral is delegated to the vault authority.
+    pub fn apply(
+        ctx: Context<Apply>,
+        amount: u64,
+        deadline_ts: i64,
+        pitch_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::apply::handler(ctx, amount, deadline_ts, pitch_hash)
+    }
+
+    /// Creator hires an applicant: binds the worker, matches the escrow to
+    /// the bid (top-up or partial refund), pulls the applicant's collateral
+    /// and moves to Accepted. `expected_amount` / `expected_deadline_ts` pin
+    /// the bid the creator reviewed.
+    pub fn select_application<'info>(
+        ctx: Context<'_, '_, '_, 'info, SelectApplication<'info>>,
+        expected_amount: u64,
+        expected_deadline_ts: i64,
+    ) -> Result<()> {
+        instructions::select_application::handler(ctx, expected_amount, expected_deadline_ts)
+    }
+
+    /// Applicant closes their application, reclaims its rent and revokes
+    /// any collateral delegation.
+    pub fn close_application(ctx: Context<CloseApplication>) -> Result<()> {
+        instructions::close_application::handler(ctx)
+    }
+
+    /// Either party proposes a new deadline and/or escrow amount.
+    pub fn propose_amendment(
+        ctx: Context<ProposeAmendment>,
+        deadline_ts: Option<i64>, // None = unchanged
+        amount: Option<u64>,      // None = unchanged
+    ) -> Result<()> {
+        instructions::propose_amendment::handler(ctx, deadline_ts, amount)
     }
 
-    /// Baker A approves delivery — releases funds minus the Pinch fee.
+    /// The other party accepts the amendment — applied atomically, with the
+    /// escrow topped up from or refunded to the creator.
+    pub fn accept_amendment<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptAmendment<'info>>,
+    ) -> Result<()> {
+        instructions::accept_amendment::handler(ctx)
+    }
+
+    /// Withdraw or reject a pending amendment.
+    pub fn cancel_amendment(ctx: Context<CancelAmendment>) -> Result<()> {
+        instructions::cancel_amendment::handler(ctx)
+    }
+
+    /// Either party offers to cancel an accepted handshake, splitting the
+    /// remaining escrow by `worker_share_bps`.
+    pub fn propose_mutual_cancel(
+        ctx: Context<ProposeMutualCancel>,
+        worker_share_bps: u16, // 0 = full refund, 10000 = all to worker
+    ) -> Result<()> {
+        instructions::propose_mutual_cancel::handler(ctx, worker_share_bps)
+    }
+
+    /// The other party co-signs the cancel: worker share paid minus Pinch
+    /// (directly, even on a vested handshake), the rest refunded to the
+    /// creator.
+    pub fn accept_mutual_cancel<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptMutualCancel<'info>>,
+    ) -> Result<()> {
+        instructions::accept_mutual_cancel::handler(ctx)
+    }
+
+    /// Withdraw or reject a pending cancel proposal.
+    pub fn withdraw_mutual_cancel(ctx: Context<WithdrawMutualCancel>) -> Result<()> {
+        instructions::withdraw_mutual_cancel::handler(ctx)
+    }
+
+    /// The named worker turns the job down (before accepting) — the
+    /// creator is refunded at once.
+    pub fn decline_handshake<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeclineHandshake<'info>>,
+    ) -> Result<()> {
+        instructions::decline::handler(ctx)
+    }
+
+    /// The worker backs out after accepting — the creator is refunded and
+    /// the withdrawal is logged on the worker's record. Any worker collateral
+    /// is forfeited to the creator.
+    pub fn withdraw_from_handshake<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFromHandshake<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw::handler(ctx)
+    }
+
+    /// Baker B marks work (or the open milestone) as delivered, committing
+    /// to a content hash and optional URI of the deliverable.
+    pub fn deliver(
+        ctx: Context<Deliver>,
+        content_hash: [u8; 32],
+        uri: Option<String>,
+    ) -> Result<()> {
+        instructions::deliver::handler(ctx, content_hash, uri)
+    }
+
+    /// Baker A approves delivery — releases funds (or the open milestone
+    /// tranche) minus the Pinch fee.
     /// Pinch (2%): 50% treasury (buyback+LP) + 30% keepers + 20% commons
-    pub fn approve(ctx: Context<Approve>) -> Result<()> {
-        instructions::approve::handler(ctx)
+    /// Pass a `bonus` to pay the worker extra straight from the creator's
+    /// token account (charged the config's bonus Pinch rate).
+    pub fn approve<'info>(
+        ctx: Context<'_, '_, '_, 'info, Approve<'info>>,
+        bonus: Option<u64>,
+    ) -> Result<()> {
+        instructions::approve::handler(ctx, bonus)
+    }
+
+    /// Baker A sends a delivery back for rework (up to the handshake's
+    /// `max_revisions`), recording a feedback hash and optionally extending
+    /// the deadline. At least a day must be left to rework.
+    pub fn request_revision(
+        ctx: Context<RequestRevision>,
+        feedback_hash: [u8; 32],
+        deadline_ts: Option<i64>,
+    ) -> Result<()> {
+        instructions::request_revision::handler(ctx, feedback_hash, deadline_ts)
+    }
+
+    /// Worker (or any crank) releases payment once the review window after
+    /// delivery has passed without approval or dispute. Same Pinch split.
+    pub fn claim_after_review<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAfterReview<'info>>,
+    ) -> Result<()> {
+        instructions::claim::handler(ctx)
+    }
+
+    /// Worker withdraws whatever has vested from a vested payout's stream.
+    pub fn withdraw_vested<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawVested<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_vested::handler(ctx)
     }
 
-    /// Either party raises a dispute.
-    pub fn dispute(ctx: Context<Dispute>) -> Result<()> {
+    /// Either party raises a dispute (opens the evidence log and posts the
+    /// mint's dispute bond, if any).
+    pub fn dispute<'info>(ctx: Context<'_, '_, '_, 'info, Dispute<'info>>) -> Result<()> {
         instructions::dispute::handler(ctx)
     }
 
+    /// The other party matches the disputer's bond (optional).
+    pub fn match_dispute_bond<'info>(
+        ctx: Context<'_, '_, '_, 'info, MatchDisputeBond<'info>>,
+    ) -> Result<()> {
+        instructions::match_dispute_bond::handler(ctx)
+    }
+
+    /// Either party appends a content hash + URI to the dispute's evidence
+    /// log (up to 4 entries each, until a panel is drawn).
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        instructions::submit_evidence::handler(ctx, content_hash, uri)
+    }
+
     /// Creator cancels a handshake (only before it's accepted).
-    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+    pub fn cancel<'info>(ctx: Context<'_, '_, '_, 'info, Cancel<'info>>) -> Result<()> {
         instructions::cancel::handler(ctx)
     }
 
-    /// Resolve a dispute (authority only — community multisig in v2).
-    pub fn resolve_dispute(
-        ctx: Context<ResolveDispute>,
-        ruling: u8, // 0 = refund creator, 1 = pay worker
+    /// Expire a handshake past its deadline (Created or Accepted) — refunds
+    /// the creator. Permissionless; the cranker earns a small tip. Worker
+    /// collateral on an accepted handshake is forfeited to the creator.
+    pub fn expire_handshake<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExpireHandshake<'info>>,
+    ) -> Result<()> {
+        instructions::expire::handler(ctx)
+    }
+
+    /// Resolve a dispute (the handshake's arbiter, or the protocol authority
+    /// as a fallback). Rejected once `open_dispute_case` has handed the
+    /// dispute to a panel, until that case stalls.
+    /// Refund, pay the worker, or split the escrow by `worker_share_bps`.
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveDispute<'info>>,
+        ruling: DisputeRuling, // Refund, PayWorker or Split { worker_share_bps }
     ) -> Result<()> {
         instructions::resolve::handler(ctx, ruling)
     }
 
+    /// Open a recurring handshake (retainer): a per-period amount, period
+    /// length and maximum number of periods, pre-funding the first
+    /// `funded_periods`.
+    pub fn create_recurring<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateRecurring<'info>>,
+        description: String,
+        amount_per_period: u64,
+        period_secs: i64,
+        max_periods: u16,
+        funded_periods: u16,
+    ) -> Result<()> {
+        instructions::create_recurring::handler(
+            ctx,
+            description,
+            amount_per_period,
+            period_secs,
+            max_periods,
+            funded_periods,
+        )
+    }
+
+    /// Worker accepts a recurring handshake; the first period starts.
+    pub fn accept_recurring(ctx: Context<AcceptRecurring>) -> Result<()> {
+        instructions::accept_recurring::handler(ctx)
+    }
+
+    /// Creator pre-funds further periods of a recurring handshake.
+    pub fn fund_recurring<'info>(
+        ctx: Context<'_, '_, '_, 'info, FundRecurring<'info>>,
+        periods: u16,
+    ) -> Result<()> {
+        instructions::fund_recurring::handler(ctx, periods)
+    }
+
+    /// Worker delivers the running period, committing to a content hash.
+    /// Earlier periods awaiting settlement do not block it.
+    pub fn deliver_period(ctx: Context<DeliverPeriod>, content_hash: [u8; 32]) -> Result<()> {
+        instructions::deliver_period::handler(ctx, content_hash)
+    }
+
+    /// Settle the current period: pay the worker (Pinch split) if delivered,
+    /// otherwise refund the creator once the period has ended.
+    pub fn settle_period<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettlePeriod<'info>>,
+    ) -> Result<()> {
+        instructions::settle_period::handler(ctx)
+    }
+
+    /// Either party stops a recurring handshake from renewing after the
+    /// running period; later funded periods are refunded.
+    pub fn stop_recurring<'info>(
+        ctx: Context<'_, '_, '_, 'info, StopRecurring<'info>>,
+    ) -> Result<()> {
+        instructions::stop_recurring::handler(ctx)
+    }
+
+    /// Baker B bills a client: an unfunded invoice with amount, deadline and
+    /// description.
+    pub fn create_invoice(
+        ctx: Context<CreateInvoice>,
+        description: String,
+        amount: u64,
+        deadline_ts: i64,
+    ) -> Result<()> {
+        instructions::create_invoice::handler(ctx, description, amount, deadline_ts)
+    }
+
+    /// The invoiced client funds it — escrow goes into a new vault and the
+    /// handshake starts out Accepted.
+    pub fn fund_invoice<'info>(
+        ctx: Context<'_, '_, '_, 'info, FundInvoice<'info>>,
+    ) -> Result<()> {
+        instructions::fund_invoice::handler(ctx)
+    }
+
+    /// The worker revokes an unfunded invoice, or anyone clears it once its
+    /// deadline has passed (rent back to the worker).
+    pub fn revoke_invoice(ctx: Context<RevokeInvoice>) -> Result<()> {
+        instructions::revoke_invoice::handler(ctx)
+    }
+
+    /// Baker B carves part of an accepted handshake out to a subcontractor.
+    /// The child is funded by a lien paid out of the parent's worker share
+    /// when the parent is approved; if the parent ends without paying it,
+    /// the lien is released and the child cancelled.
+    pub fn subcontract(
+        ctx: Context<Subcontract>,
+        description: String,
+        amount: u64,
+        deadline_ts: i64,
+    ) -> Result<()> {
+        instructions::subcontract::handler(ctx, description, amount, deadline_ts)
+    }
+
+    /// Allowlist a non-$SOUR escrow mint with its own Pinch rate and fee
+    /// destinations (authority only).
+    pub fn add_allowed_mint(
+        ctx: Context<AddAllowedMint>,
+        pinch_bps: u16,
+        dispute_bond: u64, // in the mint's smallest units, 0 = free disputes
+    ) -> Result<()> {
+        instructions::add_allowed_mint::handler(ctx, pinch_bps, dispute_bond)
+    }
+
+    /// Adjust, re-point or disable an allowlisted mint (authority only).
+    pub fn update_allowed_mint(
+        ctx: Context<UpdateAllowedMint>,
+        new_pinch_bps: Option<u16>,
+        enabled: Option<bool>,
+        new_dispute_bond: Option<u64>,
+    ) -> Result<()> {
+        instructions::update_allowed_mint::handler(ctx, new_pinch_bps, enabled, new_dispute_bond)
+    }
+
     /// Initialize the protocol config (one-time setup).
     pub fn initialize_config(
         ctx: Context<InitializeConfig>,
@@ -83,6 +391,8 @@ pub mod sour_handshake {
         treasury_share_bps: u16,  // 5000 = 50% of fee → buyback+LP
         keepers_share_bps: u16,   // 3000 = 30% of fee
         commons_share_bps: u16,   // 2000 = 20% of fee
+        crank_tip_bps: u16,       // 10 = 0.10% of an expired escrow
+        review_window_secs: i64,  // 259200 = 3 days to review a delivery
     ) -> Result<()> {
         instructions::init_config::handler(
             ctx,
@@ -90,6 +400,81 @@ pub mod sour_handshake {
             treasury_share_bps,
             keepers_share_bps,
             commons_share_bps,
+            crank_tip_bps,
+            review_window_secs,
         )
     }
+
+    /// Update fees, fee shares, crank tip, review window, dispute bond,
+    /// Commons slice of forfeited collateral, bonus Pinch rate or fee
+    /// destinations (authority only). Omitted values are left unchanged.
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        instructions::update_config::handler(ctx, update)
+    }
+
+    /// Nominate a new protocol authority (step 1 of 2, authority only).
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    /// The nominated authority takes over (step 2 of 2).
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Grow a config created before the appended fields to the current
+    /// layout, setting the crank tip and review window (authority only).
+    pub fn migrate_config(
+        ctx: Context<MigrateConfig>,
+        crank_tip_bps: u16,
+        review_window_secs: i64,
+    ) -> Result<()> {
+        instructions::migrate_config::handler(ctx, crank_tip_bps, review_window_secs)
+    }
+
+    /// Create the arbiter registry that dispute panels are drawn from
+    /// (one-time setup, authority only).
+    pub fn initialize_arbiter_registry(
+        ctx: Context<InitializeArbiterRegistry>,
+        panel_size: u8,          // odd, at most 7
+        voting_window_secs: i64, // 259200 = 3 days to vote
+    ) -> Result<()> {
+        instructions::init_arbiter_registry::handler(ctx, panel_size, voting_window_secs)
+    }
+
+    /// Add an arbiter to the registry (authority only).
+    pub fn register_arbiter(ctx: Context<RegisterArbiter>, arbiter: Pubkey) -> Result<()> {
+        instructions::register_arbiter::handler(ctx, arbiter)
+    }
+
+    /// Remove an arbiter from the registry (authority only).
+    pub fn remove_arbiter(ctx: Context<RemoveArbiter>, arbiter: Pubkey) -> Result<()> {
+        instructions::remove_arbiter::handler(ctx, arbiter)
+    }
+
+    /// Put a disputed handshake without an agreed arbiter before an arbiter
+    /// panel (creator or worker). The panel is drawn later from a future
+    /// slot's block hash.
+    pub fn open_dispute_case(ctx: Context<OpenDisputeCase>) -> Result<()> {
+        instructions::open_dispute_case::handler(ctx)
+    }
+
+    /// Seat the panel for an opened dispute case once its draw slot has
+    /// passed. Permissionless.
+    pub fn draw_panel(ctx: Context<DrawPanel>) -> Result<()> {
+        instructions::draw_panel::handler(ctx)
+    }
+
+    /// A panel member votes: refund, pay worker, or split.
+    pub fn cast_vote(ctx: Context<CastVote>, ruling: DisputeRuling) -> Result<()> {
+        instructions::cast_vote::handler(ctx, ruling)
+    }
+
+    /// Execute the panel's median ruling once everyone has voted or the
+    /// window has closed with a quorum. Permissionless.
+    pub fn finalize_dispute<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeDispute<'info>>,
+    ) -> Result<()> {
+        instructions::finalize_dispute::handler(ctx)
+    }
 }
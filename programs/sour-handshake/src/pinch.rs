@@ -0,0 +1,181 @@
+// ============================================================================
+// Pinch — fee split shared by every instruction that pays the worker
+//
+// Pinch Fee Flow (default 2% of the released amount):
+//   50% → Treasury PDA (batched → SOUR buyback + Protocol-Owned LP)
+//   30% → Keepers pool (holder rewards)
+//   20% → Commons treasury (community fund, absorbs rounding dust)
+//
+// $SOUR escrows use the rate and pools in ProtocolConfig; every other
+// mint uses its own AllowedMint entry (share split still from config).
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{AllowedMint, ProtocolConfig};
+use crate::errors::SourError;
+
+/// Pinch rate, share table, fee destinations and dispute bond for one
+/// escrow mint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeTerms {
+    pub pinch_bps: u16,
+    pub treasury_share_bps: u16,
+    pub keepers_share_bps: u16,
+    pub keepers_pool: Pubkey,
+    pub commons_treasury: Pubkey,
+    pub buyback_treasury: Pubkey,
+    pub dispute_bond: u64,
+}
+
+impl FeeTerms {
+    /// Resolve the fee terms for `mint`. $SOUR must not pass an allowlist
+    /// entry; any other mint must pass the entry for that mint.
+    pub fn for_mint(
+        mint: &Pubkey,
+        config: &ProtocolConfig,
+        allowed_mint: Option<&AllowedMint>,
+    ) -> Result<Self> {
+        let (pinch_bps, keepers_pool, commons_treasury, buyback_treasury, dispute_bond) =
+            match allowed_mint {
+                None if *mint == config.sour_mint => (
+                    config.pinch_bps,
+                    config.keepers_pool,
+                    config.commons_treasury,
+                    config.buyback_treasury,
+                    config.dispute_bond,
+                ),
+                Some(entry) if *mint != config.sour_mint && entry.mint == *mint => (
+                    entry.pinch_bps,
+                    entry.keepers_pool,
+                    entry.commons_treasury,
+                    entry.buyback_treasury,
+                    entry.dispute_bond,
+                ),
+                _ => return err!(SourError::AllowedMintMismatch),
+            };
+
+        Ok(Self {
+            pinch_bps,
+            treasury_share_bps: config.treasury_share_bps,
+            keepers_share_bps: config.keepers_share_bps,
+            keepers_pool,
+            commons_treasury,
+            buyback_treasury,
+            dispute_bond,
+        })
+    }
+
+    /// Ensure the passed fee accounts are the ones configured for this mint.
+    pub fn check_destinations(
+        &self,
+        keepers_pool: &Pubkey,
+        commons_treasury: &Pubkey,
+        buyback_treasury: &Pubkey,
+    ) -> Result<()> {
+        require_keys_eq!(*keepers_pool, self.keepers_pool, SourError::InvalidFeeDestination);
+        require_keys_eq!(*commons_treasury, self.commons_treasury, SourError::InvalidFeeDestination);
+        require_keys_eq!(*buyback_treasury, self.buyback_treasury, SourError::InvalidFeeDestination);
+        Ok(())
+    }
+}
+
+/// Commons destination for forfeited worker collateral: None when the
+/// config takes no Commons slice, otherwise `commons_treasury`, which must be
+/// the mint's configured Commons treasury.
+pub fn collateral_commons<'info>(
+    mint: &Pubkey,
+    config: &ProtocolConfig,
+    allowed_mint: Option<&AllowedMint>,
+    commons_treasury: Option<AccountInfo<'info>>,
+) -> Result<Option<AccountInfo<'info>>> {
+    if config.collateral_commons_bps == 0 {
+        return Ok(None);
+    }
+    let commons_treasury = commons_treasury.ok_or(SourError::InvalidFeeDestination)?;
+    let terms = FeeTerms::for_mint(mint, config, allowed_mint)?;
+    require_keys_eq!(
+        *commons_treasury.key,
+        terms.commons_treasury,
+        SourError::InvalidFeeDestination
+    );
+    Ok(Some(commons_treasury))
+}
+
+/// How a released amount is divided between the worker and the Pinch pools
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PinchSplit {
+    pub to_worker: u64,
+    pub pinch_total: u64,
+    pub to_treasury: u64,
+    pub to_keepers: u64,
+    pub to_commons: u64,
+}
+
+impl PinchSplit {
+    /// Split `amount` using the mint's Pinch rate and the share table.
+    pub fn compute(amount: u64, terms: &FeeTerms) -> Result<Self> {
+        let pinch_total = bps_of(amount, terms.pinch_bps)?;
+        let to_treasury = bps_of(pinch_total, terms.treasury_share_bps)?;
+        let to_keepers = bps_of(pinch_total, terms.keepers_share_bps)?;
+
+        // Commons gets the remainder to avoid rounding dust
+        let to_commons = pinch_total
+            .checked_sub(to_treasury)
+            .ok_or(SourError::MathOverflow)?
+            .checked_sub(to_keepers)
+            .ok_or(SourError::MathOverflow)?;
+
+        let to_worker = amount
+            .checked_sub(pinch_total)
+            .ok_or(SourError::MathOverflow)?;
+
+        Ok(Self {
+            to_worker,
+            pinch_total,
+            to_treasury,
+            to_keepers,
+            to_commons,
+        })
+    }
+
+    /// Add this split to the lifetime fee totals — the allowlist entry's
+    /// for non-$SOUR mints, otherwise the protocol config's.
+    pub fn record(
+        &self,
+        config: &mut ProtocolConfig,
+        allowed_mint: Option<&mut AllowedMint>,
+    ) -> Result<()> {
+        let (total_to_treasury, total_to_keepers, total_to_commons) = match allowed_mint {
+            Some(entry) => (
+                &mut entry.total_to_treasury,
+                &mut entry.total_to_keepers,
+                &mut entry.total_to_commons,
+            ),
+            None => (
+                &mut config.total_to_treasury,
+                &mut config.total_to_keepers,
+                &mut config.total_to_commons,
+            ),
+        };
+        *total_to_treasury = total_to_treasury
+            .checked_add(self.to_treasury)
+            .ok_or(SourError::MathOverflow)?;
+        *total_to_keepers = total_to_keepers
+            .checked_add(self.to_keepers)
+            .ok_or(SourError::MathOverflow)?;
+        *total_to_commons = total_to_commons
+            .checked_add(self.to_commons)
+            .ok_or(SourError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// `amount * bps / 10_000`, rounded down.
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    Ok((amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(SourError::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(SourError::MathOverflow)? as u64)
+}
@@ -0,0 +1,256 @@
+// ============================================================================
+// Ruling — pays out a dispute ruling from the escrow vault
+//
+// Shared by `resolve_dispute` (arbiter / authority) and `finalize_dispute`
+// (arbiter panel) so every ruling settles the same way:
+//   1. arbiter fee off the top (when applicable)
+//   2. worker share of the rest, minus the Pinch fee (exactly as `approve`,
+//      split across the payees for a team handshake); a parent's
+//      subcontract lien is paid out of it when the share covers it, and
+//      released otherwise
+//   3. creator refunded what is left, fee-free
+//   4. dispute bonds: the favoured party's bond is returned, the other
+//      party's goes to the Commons treasury (an even split returns both)
+//   5. worker collateral: forfeited to the creator (minus the Commons
+//      slice) when the ruling favours the creator, otherwise returned
+//   6. the collateral vault and a vested handshake's payout stream are
+//      closed, their rent returned to the creator. A stream only starts on
+//      approval, so a ruled worker share is paid directly, not vested
+//
+// Only the escrow still in the vault is at stake — milestones already
+// approved and paid are not clawed back.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    AllowedMint, DisputeRuling, Handshake, HandshakeStatus, PayoutStream, ProtocolConfig,
+};
+use crate::errors::SourError;
+use crate::events::DisputeResolved;
+use crate::pinch::{bps_of, FeeTerms, PinchSplit};
+use crate::escrow::{TeamPayout, VaultPayout};
+use crate::settlement::settle_lien;
+
+/// Everything a ruling touches, borrowed from the calling instruction
+pub struct RulingAccounts<'a, 'info> {
+    pub config: &'a mut ProtocolConfig,
+    pub handshake: &'a mut Handshake,
+    pub allowed_mint: Option<&'a mut AllowedMint>,
+    pub vault_balance: u64,
+    pub payout: VaultPayout<'a, 'info>,
+    pub creator_token_account: AccountInfo<'info>,
+    pub worker_token_account: AccountInfo<'info>,
+    pub team: Option<TeamPayout<'a, 'info>>,
+    pub keepers_pool: AccountInfo<'info>,
+    pub commons_treasury: AccountInfo<'info>,
+    pub buyback_treasury: AccountInfo<'info>,
+    pub arbiter_token_account: Option<AccountInfo<'info>>,
+    pub bond_vault: AccountInfo<'info>,
+    pub collateral_vault: Option<AccountInfo<'info>>,
+    pub subcontract: Option<&'a mut Handshake>,
+    pub subcontract_vault: Option<AccountInfo<'info>>,
+    pub payout_stream: Option<&'a PayoutStream>,
+    pub stream_vault: Option<AccountInfo<'info>>,
+    pub creator: AccountInfo<'info>,
+}
+
+/// Execute `ruling` and mark the handshake Resolved. `arbiter_fee_bps` is
+/// taken off the top and paid to `arbiter_token_account`.
+pub fn execute(
+    accounts: RulingAccounts,
+    ruling: DisputeRuling,
+    resolver: Pubkey,
+    arbiter_fee_bps: u16,
+) -> Result<()> {
+    let RulingAccounts {
+        config,
+        handshake,
+        allowed_mint,
+        vault_balance,
+        payout,
+        creator_token_account,
+        worker_token_account,
+        team,
+        keepers_pool,
+        commons_treasury,
+        buyback_treasury,
+        arbiter_token_account,
+        bond_vault,
+        collateral_vault,
+        subcontract,
+        subcontract_vault,
+        payout_stream,
+        stream_vault,
+        creator,
+    } = accounts;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        team.is_some() == handshake.team_payout,
+        SourError::TeamMismatch
+    );
+    require!(
+        collateral_vault.is_some() == (handshake.collateral_held > 0),
+        SourError::CollateralVaultMismatch
+    );
+    require!(
+        payout_stream.is_some() == handshake.vested_payout
+            && stream_vault.is_some() == handshake.vested_payout,
+        SourError::PayoutStreamMismatch
+    );
+    let worker_share_bps = ruling.worker_share_bps()?;
+    let amount = handshake.remaining()?;
+
+    // Pinch rate + fee destinations for the escrow mint
+    let fee_terms = FeeTerms::for_mint(&handshake.mint, config, allowed_mint.as_deref())?;
+    fee_terms.check_destinations(
+        keepers_pool.key,
+        commons_treasury.key,
+        buyback_treasury.key,
+    )?;
+
+    let arbiter_fee = bps_of(amount, arbiter_fee_bps)?;
+    let disputed = amount
+        .checked_sub(arbiter_fee)
+        .ok_or(SourError::MathOverflow)?;
+
+    // Worker portion pays Pinch; the creator's refund (plus any dust left in
+    // the vault) is fee-free
+    let to_worker_gross = bps_of(disputed, worker_share_bps)?;
+    let mut split = PinchSplit::compute(to_worker_gross, &fee_terms)?;
+    let refunded = vault_balance
+        .checked_sub(arbiter_fee)
+        .and_then(|left| left.checked_sub(to_worker_gross))
+        .ok_or(SourError::InsufficientEscrow)?;
+
+    // 1. Arbiter fee
+    if arbiter_fee > 0 {
+        let arbiter_token_account = arbiter_token_account
+            .as_ref()
+            .ok_or(SourError::InvalidFeeDestination)?;
+        payout.transfer(arbiter_token_account, arbiter_fee)?;
+    }
+
+    // 2. Subcontract lien, then the worker portion, minus Pinch
+    settle_lien(
+        handshake,
+        subcontract,
+        subcontract_vault.as_ref(),
+        &payout,
+        Some(&mut split),
+        false,
+        now,
+    )?;
+    payout.pay_split(
+        &split,
+        &worker_token_account,
+        team.as_ref(),
+        &buyback_treasury,
+        &keepers_pool,
+        &commons_treasury,
+    )?;
+
+    // 3. Refund the rest to the creator
+    payout.transfer(&creator_token_account, refunded)?;
+
+    // 4. Dispute bonds
+    let disputer_is_worker = handshake.disputed_by == handshake.worker;
+    let mut bonds_returned: u64 = 0;
+    let mut bonds_forfeited: u64 = 0;
+    for (bond, owner_is_worker) in [
+        (handshake.dispute_bond, disputer_is_worker),
+        (handshake.counter_bond, !disputer_is_worker),
+    ] {
+        if bond == 0 {
+            continue;
+        }
+        let favoured = match worker_share_bps.cmp(&5_000) {
+            std::cmp::Ordering::Greater => owner_is_worker,
+            std::cmp::Ordering::Less => !owner_is_worker,
+            std::cmp::Ordering::Equal => true,
+        };
+        let to = if favoured {
+            bonds_returned = bonds_returned
+                .checked_add(bond)
+                .ok_or(SourError::MathOverflow)?;
+            if owner_is_worker {
+                &worker_token_account
+            } else {
+                &creator_token_account
+            }
+        } else {
+            bonds_forfeited = bonds_forfeited
+                .checked_add(bond)
+                .ok_or(SourError::MathOverflow)?;
+            &commons_treasury
+        };
+        payout.transfer_from(&bond_vault, to, bond)?;
+    }
+
+    // 5. Worker collateral
+    let collateral = handshake.take_collateral();
+    let (mut collateral_returned, mut collateral_forfeited) = (0, 0);
+    if let Some(collateral_vault) = &collateral_vault {
+        if worker_share_bps < 5_000 {
+            payout.forfeit_collateral(
+                collateral_vault,
+                collateral,
+                &creator_token_account,
+                Some(&commons_treasury),
+                config.collateral_commons_bps,
+            )?;
+            collateral_forfeited = collateral;
+        } else {
+            payout.transfer_from(collateral_vault, &worker_token_account, collateral)?;
+            collateral_returned = collateral;
+        }
+        payout.close_vault(collateral_vault, &creator_token_account, &creator)?;
+    }
+
+    // 6. The payout never started streaming — hand back the stream's rent
+    if let Some(stream_vault) = &stream_vault {
+        payout.close_vault(stream_vault, &creator_token_account, &creator)?;
+    }
+
+    // Update config stats — any paid share counts as completed work
+    if to_worker_gross > 0 {
+        config.total_completed = config
+            .total_completed
+            .checked_add(1)
+            .ok_or(SourError::MathOverflow)?;
+        split.record(config, allowed_mint)?;
+    }
+
+    // Update handshake state
+    handshake.status = HandshakeStatus::Resolved;
+    handshake.resolved_at = now;
+    handshake.released = handshake
+        .released
+        .checked_add(to_worker_gross)
+        .ok_or(SourError::MathOverflow)?;
+
+    emit!(DisputeResolved {
+        handshake_id: handshake.id,
+        resolver,
+        worker_share_bps,
+        arbiter_fee,
+        to_worker: split.to_worker,
+        pinch_total: split.pinch_total,
+        refunded,
+        bonds_returned,
+        bonds_forfeited,
+        collateral_returned,
+        collateral_forfeited,
+        resolved_at: handshake.resolved_at,
+    });
+
+    msg!(
+        "Dispute #{} resolved: {}bps to worker. {} paid, {} refunded",
+        handshake.id,
+        worker_share_bps,
+        split.to_worker,
+        refunded
+    );
+    Ok(())
+}
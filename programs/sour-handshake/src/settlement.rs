@@ -0,0 +1,223 @@
+// ============================================================================
+// Settlement — releases the open tranche of a delivered handshake
+//
+// Shared by `approve` (creator) and `claim_after_review` (worker or crank)
+// so every release settles the same way:
+//   1. a parent's subcontract lien is paid out of the worker's share into
+//      the child's vault (the child escrows what actually arrived)
+//   2. the worker's share, minus the Pinch fee, goes to the worker, the
+//      team's payees, or the payout stream for a vested payout
+//   3. the tranche is released; the last one completes the handshake,
+//      returns any worker collateral and credits the worker record
+//   4. a vested payout starts streaming whatever reached the stream vault
+//
+// `settle_lien` is also how every other exit of a parent (ruling, mutual
+// cancel, withdrawal, expiry) deals with an outstanding lien: paid out of
+// the worker's share while the child is live and it covers the lien,
+// otherwise released and the child cancelled.
+// ============================================================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    AllowedMint, Handshake, Milestones, PayoutStream, ProtocolConfig, WorkerRecord,
+};
+use crate::errors::SourError;
+use crate::events::LienSettled;
+use crate::pinch::{FeeTerms, PinchSplit};
+use crate::escrow::{token_balance, TeamPayout, VaultPayout};
+
+/// Everything a release touches, borrowed from the calling instruction
+pub struct SettlementAccounts<'a, 'info> {
+    pub config: &'a mut ProtocolConfig,
+    pub handshake: &'a mut Handshake,
+    pub milestones: Option<&'a mut Milestones>,
+    pub allowed_mint: Option<&'a mut AllowedMint>,
+    pub payout: &'a VaultPayout<'a, 'info>,
+    pub worker_token_account: AccountInfo<'info>,
+    pub team: Option<TeamPayout<'a, 'info>>,
+    pub collateral_vault: Option<AccountInfo<'info>>,
+    pub payout_stream: Option<&'a mut PayoutStream>,
+    pub stream_vault: Option<AccountInfo<'info>>,
+    pub subcontract: Option<&'a mut Handshake>,
+    pub subcontract_vault: Option<AccountInfo<'info>>,
+    pub worker_record: &'a mut WorkerRecord,
+    pub keepers_pool: AccountInfo<'info>,
+    pub commons_treasury: AccountInfo<'info>,
+    pub buyback_treasury: AccountInfo<'info>,
+}
+
+/// What a release paid out, for the caller's event
+pub struct Settled {
+    pub amount: u64,
+    pub fee_terms: FeeTerms,
+    pub split: PinchSplit,
+    pub completed: bool,
+    pub collateral_returned: u64,
+    pub vested: u64,
+    pub lien_paid: u64,
+}
+
+/// Release the open tranche at `now`.
+pub fn execute(accounts: SettlementAccounts, now: i64) -> Result<Settled> {
+    let SettlementAccounts {
+        config,
+        handshake,
+        milestones,
+        allowed_mint,
+        payout,
+        worker_token_account,
+        team,
+        collateral_vault,
+        payout_stream,
+        stream_vault,
+        subcontract,
+        subcontract_vault,
+        worker_record,
+        keepers_pool,
+        commons_treasury,
+        buyback_treasury,
+    } = accounts;
+
+    require!(
+        team.is_some() == handshake.team_payout,
+        SourError::TeamMismatch
+    );
+    require!(
+        payout_stream.is_some() == handshake.vested_payout
+            && stream_vault.is_some() == handshake.vested_payout,
+        SourError::PayoutStreamMismatch
+    );
+
+    // Pinch rate + fee destinations for the escrow mint
+    let amount = handshake.current_tranche(milestones.as_deref())?;
+    let fee_terms = FeeTerms::for_mint(&handshake.mint, config, allowed_mint.as_deref())?;
+    fee_terms.check_destinations(
+        keepers_pool.key,
+        commons_treasury.key,
+        buyback_treasury.key,
+    )?;
+    let mut split = PinchSplit::compute(amount, &fee_terms)?;
+
+    // 1. Subcontract lien — a child that is no longer live hands it back
+    // to the worker's share
+    let lien_paid = settle_lien(
+        handshake,
+        subcontract,
+        subcontract_vault.as_ref(),
+        payout,
+        Some(&mut split),
+        true,
+        now,
+    )?;
+
+    // 2. Worker share (or stream) + Pinch
+    let worker_destination = stream_vault.as_ref().unwrap_or(&worker_token_account);
+    payout.pay_split(
+        &split,
+        worker_destination,
+        team.as_ref(),
+        &buyback_treasury,
+        &keepers_pool,
+        &commons_treasury,
+    )?;
+
+    // 3. Release the tranche; the last one returns the worker's collateral
+    let completed = handshake.release_tranche(amount, milestones, now)?;
+    let collateral_returned = if completed { handshake.take_collateral() } else { 0 };
+    if collateral_returned > 0 {
+        let collateral_vault = collateral_vault
+            .as_ref()
+            .ok_or(SourError::CollateralVaultMismatch)?;
+        payout.transfer_from(collateral_vault, &worker_token_account, collateral_returned)?;
+    }
+
+    // 4. Start vesting whatever reached the stream vault
+    let vested = match (payout_stream, &stream_vault) {
+        (Some(stream), Some(stream_vault)) => {
+            let vested = token_balance(stream_vault)?;
+            stream.start(handshake.worker, vested, now);
+            vested
+        }
+        _ => 0,
+    };
+
+    // Update config stats and the worker's track record
+    if completed {
+        config.total_completed = config
+            .total_completed
+            .checked_add(1)
+            .ok_or(SourError::MathOverflow)?;
+        worker_record.record_completed()?;
+    }
+    split.record(config, allowed_mint)?;
+
+    Ok(Settled {
+        amount,
+        fee_terms,
+        split,
+        completed,
+        collateral_returned,
+        vested,
+        lien_paid,
+    })
+}
+
+/// Settle `parent`'s outstanding lien as the parent leaves escrow at `now`,
+/// returning the amount paid into the subcontract.
+///
+/// A live subcontract is funded out of the worker's share in `split` —
+/// always when `must_fund` (failing if the share falls short), otherwise
+/// only when the share covers the lien. With no worker share, a short
+/// share or a subcontract that is already over, the lien is released
+/// instead: the subcontract escrows nothing and, if still open, is
+/// cancelled.
+pub fn settle_lien<'info>(
+    parent: &mut Handshake,
+    subcontract: Option<&mut Handshake>,
+    subcontract_vault: Option<&AccountInfo<'info>>,
+    payout: &VaultPayout<'_, 'info>,
+    split: Option<&mut PinchSplit>,
+    must_fund: bool,
+    now: i64,
+) -> Result<u64> {
+    // A parent owes its child's lien out of the worker's share; a child
+    // pays out only once that lien has arrived
+    let lien = parent.lien_owed()?;
+    require!(
+        subcontract.is_some() == (lien > 0),
+        SourError::SubcontractMismatch
+    );
+    let Some(subcontract) = subcontract else {
+        return Ok(0);
+    };
+    require!(
+        Some(subcontract.id) == parent.child_id && subcontract.parent_id == Some(parent.id),
+        SourError::SubcontractMismatch
+    );
+
+    let paid = match split {
+        Some(split)
+            if subcontract.awaits_lien() && (must_fund || split.to_worker >= lien) =>
+        {
+            let subcontract_vault = subcontract_vault.ok_or(SourError::SubcontractMismatch)?;
+            let paid = payout.pay_lien(split, lien, subcontract_vault)?;
+            // Fee-on-transfer mints deliver less than was sent
+            subcontract.fund_from_lien(token_balance(subcontract_vault)?);
+            paid
+        }
+        _ => {
+            subcontract.release_lien(now);
+            0
+        }
+    };
+    parent.lien = 0;
+
+    emit!(LienSettled {
+        parent_id: parent.id,
+        handshake_id: subcontract.id,
+        paid,
+        released: lien - paid,
+    });
+    Ok(paid)
+}
@@ -4,6 +4,8 @@
 
 use anchor_lang::prelude::*;
 
+use crate::errors::SourError;
+
 // ---------------------------------------------------------------------------
 // Handshake Account — one per escrow deal
 // ---------------------------------------------------------------------------
@@ -19,7 +21,10 @@ pub struct Handshake {
     /// Baker B — the one who accepts and delivers work
     pub worker: Pubkey,
 
-    /// Amount of $SOUR escrowed (in token smallest units)
+    /// Mint of the escrowed token ($SOUR or an allowlisted mint)
+    pub mint: Pubkey,
+
+    /// Amount escrowed (in token smallest units)
     pub amount: u64,
 
     /// Description of the work agreement
@@ -47,6 +52,86 @@ pub struct Handshake {
     /// Who raised the dispute (Pubkey::default if none)
     pub disputed_by: Pubkey,
 
+    /// Bond the disputer posted into the bond vault (0 if none)
+    pub dispute_bond: u64,
+
+    /// Bond the other party posted to match it (0 if not matched)
+    pub counter_bond: u64,
+
+    /// Number of milestone tranches (0 = single payout of `amount`)
+    pub milestone_count: u8,
+
+    /// Index of the milestone currently being worked on / reviewed
+    pub current_milestone: u8,
+
+    /// Escrow already released through approved milestones
+    pub released: u64,
+
+    /// Seconds the creator has to review a delivery before the worker may
+    /// claim payment (config default unless overridden at creation)
+    pub review_window_secs: i64,
+
+    /// Arbiter agreed at creation who may resolve disputes alongside the
+    /// protocol authority (Pubkey::default if none)
+    pub arbiter: Pubkey,
+
+    /// Arbiter's fee, in bps of the disputed escrow, paid when the arbiter
+    /// resolves the dispute
+    pub arbiter_fee_bps: u16,
+
+    /// Open bounties: completed handshakes a worker needs before accepting
+    pub min_completed: u32,
+
+    /// Open bounties: only workers on the bounty allowlist may accept
+    pub allowlisted: bool,
+
+    /// Open bounties: hired through `apply` + `select_application` only
+    /// (first-come `accept_handshake` is disabled)
+    pub by_application: bool,
+
+    /// Collateral the worker must lock in the collateral vault when
+    /// accepting (0 = none)
+    pub worker_collateral: u64,
+
+    /// Collateral currently held in the collateral vault
+    pub collateral_held: u64,
+
+    /// Revisions the creator may request before a rejected delivery must
+    /// go through `dispute`
+    pub max_revisions: u8,
+
+    /// Revisions requested so far
+    pub revision_count: u8,
+
+    /// Hash of the creator's latest revision feedback (zeroed if none)
+    pub feedback_hash: [u8; 32],
+
+    /// Content hash of the latest delivery (zeroed until delivered)
+    pub delivery_hash: [u8; 32],
+
+    /// Where the latest delivery can be fetched (empty if not given)
+    #[max_len(200)]
+    pub delivery_uri: String,
+
+    /// Worker's payout vests through the payout stream instead of being
+    /// paid out on approval
+    pub vested_payout: bool,
+
+    /// Worker's payout is split across the team payees (the worker is the
+    /// team lead)
+    pub team_payout: bool,
+
+    /// Handshake this one was subcontracted from (None for a direct deal)
+    pub parent_id: Option<u64>,
+
+    /// Subcontract carved out of this handshake (None if not subcontracted)
+    pub child_id: Option<u64>,
+
+    /// Escrow lien between parent and subcontract: on the parent, owed to
+    /// the subcontract out of the worker's payout; on the subcontract,
+    /// still awaited from the parent (0 once paid)
+    pub lien: u64,
+
     /// PDA bump seed for vault authority
     pub vault_bump: u8,
 
@@ -58,6 +143,453 @@ impl Handshake {
     /// Total size: 8 discriminator + fields
     /// We use InitSpace derive, but keep this for reference
     pub const SEED_PREFIX: &'static [u8] = b"handshake";
+
+    /// Upper bound for a per-handshake arbiter fee (1000 = 10%)
+    pub const MAX_ARBITER_FEE_BPS: u16 = 1000;
+
+    /// Revision requests allowed when the creator does not set a limit
+    pub const DEFAULT_MAX_REVISIONS: u8 = 2;
+
+    /// Upper bound for a per-handshake revision limit
+    pub const MAX_REVISIONS: u8 = 10;
+
+    /// Least time a worker is given to rework a delivery sent back by
+    /// `request_revision` (1 day)
+    pub const MIN_REVISION_SECS: i64 = 86_400;
+
+    /// Upper bound for a delivery URI (must match `max_len` above)
+    pub const MAX_DELIVERY_URI_LEN: usize = 200;
+
+    /// Write a fresh handshake from `init`; everything else starts empty.
+    /// Every field is listed, so a new one cannot be left at a stale value.
+    pub fn init(&mut self, init: HandshakeInit) {
+        *self = Handshake {
+            id: init.id,
+            creator: init.creator,
+            worker: init.worker,
+            mint: init.mint,
+            amount: init.amount,
+            description: init.description,
+            status: init.status,
+            created_at: init.created_at,
+            deadline_ts: init.deadline_ts,
+            accepted_at: init.accepted_at,
+            delivered_at: 0,
+            resolved_at: 0,
+            disputed_by: Pubkey::default(),
+            dispute_bond: 0,
+            counter_bond: 0,
+            milestone_count: init.milestone_count,
+            current_milestone: 0,
+            released: 0,
+            review_window_secs: init.review_window_secs,
+            arbiter: init.arbiter,
+            arbiter_fee_bps: init.arbiter_fee_bps,
+            min_completed: init.min_completed,
+            allowlisted: init.allowlisted,
+            by_application: init.by_application,
+            worker_collateral: init.worker_collateral,
+            collateral_held: 0,
+            max_revisions: init.max_revisions,
+            revision_count: 0,
+            feedback_hash: [0; 32],
+            delivery_hash: [0; 32],
+            delivery_uri: String::new(),
+            vested_payout: init.vested_payout,
+            team_payout: init.team_payout,
+            parent_id: init.parent_id,
+            child_id: None,
+            lien: init.lien,
+            vault_bump: init.vault_bump,
+            bump: init.bump,
+        };
+    }
+
+    /// Whether `key` may resolve this handshake's dispute: the agreed
+    /// arbiter, or the protocol authority as a fallback
+    pub fn is_resolver(&self, key: &Pubkey, config: &ProtocolConfig) -> bool {
+        *key == config.authority || (self.arbiter != Pubkey::default() && *key == self.arbiter)
+    }
+
+    /// Open bounty still waiting for a worker (the first eligible signer to
+    /// accept becomes the worker)
+    pub fn is_open(&self) -> bool {
+        self.worker == Pubkey::default()
+    }
+
+    /// Whether `worker` may take this open bounty: enough completed
+    /// handshakes on their record and, if allowlisted, on the allowlist
+    pub fn check_eligibility(
+        &self,
+        worker: &Pubkey,
+        record: Option<&WorkerRecord>,
+        allowlist: Option<&BountyAllowlist>,
+    ) -> Result<()> {
+        require!(*worker != self.creator, SourError::SelfHandshake);
+        require!(*worker != self.arbiter, SourError::InvalidArbiter);
+
+        if self.min_completed > 0 {
+            let record = record.ok_or(SourError::InsufficientTrackRecord)?;
+            require!(
+                record.completed >= self.min_completed,
+                SourError::InsufficientTrackRecord
+            );
+        }
+
+        if self.allowlisted {
+            let allowlist = allowlist.ok_or(SourError::NotAllowlisted)?;
+            require!(allowlist.workers.contains(worker), SourError::NotAllowlisted);
+        }
+        Ok(())
+    }
+
+    /// Lien this handshake owes its subcontract out of the worker's payout.
+    /// Fails while this handshake is itself a subcontract whose parent has
+    /// not paid in yet.
+    pub fn lien_owed(&self) -> Result<u64> {
+        if self.child_id.is_some() {
+            return Ok(self.lien);
+        }
+        require!(self.lien == 0, SourError::LienUnpaid);
+        Ok(0)
+    }
+
+    /// Record the lien paid in by the parent: what arrived in the vault
+    /// becomes this subcontract's escrow
+    pub fn fund_from_lien(&mut self, received: u64) {
+        self.amount = received;
+        self.lien = 0;
+    }
+
+    /// Whether this subcontract is still open and waiting on its parent's
+    /// lien (a settled or cancelled one can no longer use it)
+    pub fn awaits_lien(&self) -> bool {
+        self.lien > 0
+            && matches!(
+                self.status,
+                HandshakeStatus::Created
+                    | HandshakeStatus::Accepted
+                    | HandshakeStatus::Delivered
+                    | HandshakeStatus::Disputed
+            )
+    }
+
+    /// Record that the parent's lien will never arrive: nothing is escrowed
+    /// for this subcontract. An open subcontract is cancelled; a disputed
+    /// one stays Disputed so its ruling still settles the bonds.
+    pub fn release_lien(&mut self, now: i64) {
+        if self.lien == 0 {
+            return;
+        }
+        self.amount = 0;
+        self.lien = 0;
+        if matches!(
+            self.status,
+            HandshakeStatus::Created | HandshakeStatus::Accepted | HandshakeStatus::Delivered
+        ) {
+            self.status = HandshakeStatus::Cancelled;
+            self.resolved_at = now;
+        }
+    }
+
+    /// Hand over the held collateral for settlement, leaving none behind
+    pub fn take_collateral(&mut self) -> u64 {
+        std::mem::take(&mut self.collateral_held)
+    }
+
+    /// Escrow still held in the vault (not yet released to the worker)
+    pub fn remaining(&self) -> Result<u64> {
+        Ok(self
+            .amount
+            .checked_sub(self.released)
+            .ok_or(SourError::MathOverflow)?)
+    }
+
+    /// The open milestone, or None for single-payout handshakes.
+    /// Fails if a milestone handshake is missing its schedule account.
+    pub fn current_milestone<'a>(
+        &self,
+        milestones: Option<&'a Milestones>,
+    ) -> Result<Option<&'a Milestone>> {
+        if self.milestone_count == 0 {
+            return Ok(None);
+        }
+        let milestones = milestones.ok_or(SourError::MilestonesMismatch)?;
+        let milestone = milestones
+            .items
+            .get(self.current_milestone as usize)
+            .ok_or(SourError::MilestonesMismatch)?;
+        Ok(Some(milestone))
+    }
+
+    /// Escrow released by the next approval: the open tranche in milestone
+    /// mode, otherwise the whole amount.
+    pub fn current_tranche(&self, milestones: Option<&Milestones>) -> Result<u64> {
+        match self.current_milestone(milestones)? {
+            Some(milestone) => Ok(milestone.amount),
+            None => self.remaining(),
+        }
+    }
+
+    /// Mark the open tranche (or the whole escrow) as paid out. Milestone
+    /// handshakes return to Accepted until the last tranche is released.
+    /// Returns true once the handshake is fully settled.
+    pub fn release_tranche(
+        &mut self,
+        amount: u64,
+        milestones: Option<&mut Milestones>,
+        now: i64,
+    ) -> Result<bool> {
+        self.released = self
+            .released
+            .checked_add(amount)
+            .ok_or(SourError::MathOverflow)?;
+
+        if let Some(milestones) = milestones {
+            let milestone = milestones
+                .items
+                .get_mut(self.current_milestone as usize)
+                .ok_or(SourError::MilestonesMismatch)?;
+            milestone.paid_at = now;
+        }
+
+        let completed = self.current_milestone as u16 + 1 >= self.milestone_count as u16;
+        if completed {
+            self.status = HandshakeStatus::Approved;
+            self.resolved_at = now;
+        } else {
+            self.status = HandshakeStatus::Accepted;
+            self.current_milestone += 1;
+        }
+        Ok(completed)
+    }
+
+    /// Deadline for the work currently owed by the worker.
+    pub fn current_deadline(&self, milestones: Option<&Milestones>) -> Result<i64> {
+        match self.current_milestone(milestones)? {
+            Some(milestone) => Ok(milestone.deadline_ts),
+            None => Ok(self.deadline_ts),
+        }
+    }
+}
+
+/// Terms a new handshake starts with, passed to `Handshake::init`. The
+/// defaults are a plain Created handshake on protocol terms.
+pub struct HandshakeInit {
+    pub id: u64,
+    pub creator: Pubkey,
+    pub worker: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub description: String,
+    pub status: HandshakeStatus,
+    pub created_at: i64,
+    pub deadline_ts: i64,
+    pub accepted_at: i64,
+    pub milestone_count: u8,
+    pub review_window_secs: i64,
+    pub arbiter: Pubkey,
+    pub arbiter_fee_bps: u16,
+    pub min_completed: u32,
+    pub allowlisted: bool,
+    pub by_application: bool,
+    pub worker_collateral: u64,
+    pub max_revisions: u8,
+    pub vested_payout: bool,
+    pub team_payout: bool,
+    pub parent_id: Option<u64>,
+    pub lien: u64,
+    pub vault_bump: u8,
+    pub bump: u8,
+}
+
+impl Default for HandshakeInit {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            creator: Pubkey::default(),
+            worker: Pubkey::default(),
+            mint: Pubkey::default(),
+            amount: 0,
+            description: String::new(),
+            status: HandshakeStatus::Created,
+            created_at: 0,
+            deadline_ts: 0,
+            accepted_at: 0,
+            milestone_count: 0,
+            review_window_secs: 0,
+            arbiter: Pubkey::default(),
+            arbiter_fee_bps: 0,
+            min_completed: 0,
+            allowlisted: false,
+            by_application: false,
+            worker_collateral: 0,
+            max_revisions: Handshake::DEFAULT_MAX_REVISIONS,
+            vested_payout: false,
+            team_payout: false,
+            parent_id: None,
+            lien: 0,
+            vault_bump: 0,
+            bump: 0,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Milestones — optional tranche schedule, one per milestone handshake
+// Seeds: ["milestones", handshake_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct Milestones {
+    /// Handshake this schedule belongs to
+    pub handshake_id: u64,
+
+    /// Tranches, settled strictly in order
+    #[max_len(8)]
+    pub items: Vec<Milestone>,
+
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl Milestones {
+    pub const SEED_PREFIX: &'static [u8] = b"milestones";
+
+    /// Maximum tranches per handshake (must match `max_len` above)
+    pub const MAX_MILESTONES: usize = 8;
+
+    /// Apply an amendment to the schedule: the amount change lands on the
+    /// final tranche (which must still be unpaid), and the final tranche
+    /// moves to the new deadline with earlier ones capped at it.
+    pub fn amend(&mut self, amount_delta: i128, deadline_ts: Option<i64>) -> Result<()> {
+        let last = self.items.last_mut().ok_or(SourError::MilestonesMismatch)?;
+        require!(last.paid_at == 0, SourError::InvalidAmendment);
+        let amount = (last.amount as i128)
+            .checked_add(amount_delta)
+            .ok_or(SourError::MathOverflow)?;
+        require!(amount > 0, SourError::InvalidAmendment);
+        last.amount = u64::try_from(amount).map_err(|_| SourError::MathOverflow)?;
+
+        if let Some(deadline_ts) = deadline_ts {
+            for milestone in self.items.iter_mut() {
+                milestone.deadline_ts = milestone.deadline_ts.min(deadline_ts);
+            }
+            if let Some(last) = self.items.last_mut() {
+                last.deadline_ts = deadline_ts;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move every tranche deadline out by `delta_secs` (used when a bid
+    /// pushes the whole schedule back)
+    pub fn shift(&mut self, delta_secs: i64) -> Result<()> {
+        require!(delta_secs >= 0, SourError::InvalidMilestoneDeadline);
+        for milestone in self.items.iter_mut() {
+            milestone.deadline_ts = milestone
+                .deadline_ts
+                .checked_add(delta_secs)
+                .ok_or(SourError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Push the tranche at `index` out to `deadline_ts`; later tranches
+    /// due earlier move out with it.
+    pub fn extend(&mut self, index: u8, deadline_ts: i64) -> Result<()> {
+        let tranches = self
+            .items
+            .get_mut(index as usize..)
+            .ok_or(SourError::MilestonesMismatch)?;
+        for milestone in tranches.iter_mut() {
+            milestone.deadline_ts = milestone.deadline_ts.max(deadline_ts);
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Milestone {
+    /// Escrow released when this tranche is approved
+    pub amount: u64,
+
+    /// Tranche deadline (defaults to the handshake deadline)
+    pub deadline_ts: i64,
+
+    /// Unix timestamp when this tranche was delivered (0 if not yet)
+    pub delivered_at: i64,
+
+    /// Unix timestamp when this tranche was paid out (0 if not yet)
+    pub paid_at: i64,
+}
+
+/// Changes passed to `update_config` — None keeps the current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub pinch_bps: Option<u16>,
+    pub treasury_share_bps: Option<u16>,
+    pub keepers_share_bps: Option<u16>,
+    pub commons_share_bps: Option<u16>,
+    pub crank_tip_bps: Option<u16>,
+    pub review_window_secs: Option<i64>,
+    pub dispute_bond: Option<u64>,
+    pub collateral_commons_bps: Option<u16>,
+    pub bonus_pinch_bps: Option<u16>,
+}
+
+/// Arbiter terms passed to `create_handshake`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ArbiterTerms {
+    pub arbiter: Pubkey,
+    pub fee_bps: u16,
+}
+
+/// Open-bounty eligibility passed to `create_handshake`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BountyTerms {
+    /// Completed handshakes (per the worker's record) required to accept
+    pub min_completed: u32,
+    /// Workers allowed to accept — empty = anyone
+    pub allowlist: Vec<Pubkey>,
+    /// Hire through applications only (no first-come acceptance)
+    pub by_application: bool,
+}
+
+/// Vesting schedule passed to `create_handshake`: linear over
+/// `duration_secs` from approval, nothing released before `cliff_secs`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VestingTerms {
+    pub duration_secs: i64,
+    /// 0 = plain linear vesting
+    pub cliff_secs: i64,
+}
+
+/// Optional terms passed to `create_handshake`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct CreateOptions {
+    /// None = protocol default review window
+    pub review_window_secs: Option<i64>,
+    /// None = protocol authority resolves disputes
+    pub arbiter: Option<ArbiterTerms>,
+    /// Eligibility for open bounties (no worker account passed)
+    pub bounty: Option<BountyTerms>,
+    /// Collateral the worker locks when accepting (None = none)
+    pub worker_collateral: Option<u64>,
+    /// None = `Handshake::DEFAULT_MAX_REVISIONS`
+    pub max_revisions: Option<u8>,
+    /// Vest the worker's payout after approval (None = paid at once)
+    pub vesting: Option<VestingTerms>,
+    /// Split the worker's payout across a team led by the worker
+    /// (None = all to the worker)
+    pub team: Option<Vec<Payee>>,
+}
+
+/// Milestone terms passed to `create_handshake`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneInput {
+    pub amount: u64,
+    pub deadline_ts: Option<i64>,
 }
 
 // ---------------------------------------------------------------------------
@@ -75,7 +607,8 @@ pub enum HandshakeStatus {
     Approved,
     /// Disputed by either party
     Disputed,
-    /// Cancelled by creator before acceptance (terminal state)
+    /// Cancelled by creator before acceptance, or by mutual agreement
+    /// afterwards (terminal state)
     Cancelled,
     /// Dispute resolved by authority (terminal state)
     Resolved,
@@ -83,6 +616,42 @@ pub enum HandshakeStatus {
     Expired,
 }
 
+// ---------------------------------------------------------------------------
+// Dispute Ruling
+// ---------------------------------------------------------------------------
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DisputeRuling {
+    /// Full refund to the creator, no fee (0% to the worker)
+    Refund,
+    /// Pay the worker in full, minus Pinch (100% to the worker)
+    PayWorker,
+    /// Worker gets `worker_share_bps` of the escrow (minus Pinch), the
+    /// creator is refunded the rest
+    Split { worker_share_bps: u16 },
+}
+
+impl DisputeRuling {
+    /// Worker's share of the disputed escrow in bps (0..=10000)
+    pub fn worker_share_bps(&self) -> Result<u16> {
+        let bps = match self {
+            DisputeRuling::Refund => 0,
+            DisputeRuling::PayWorker => 10_000,
+            DisputeRuling::Split { worker_share_bps } => *worker_share_bps,
+        };
+        require!(bps <= 10_000, SourError::InvalidRuling);
+        Ok(bps)
+    }
+
+    /// The ruling paying the worker `bps` of the escrow
+    pub fn from_worker_share_bps(bps: u16) -> Self {
+        match bps {
+            0 => DisputeRuling::Refund,
+            10_000 => DisputeRuling::PayWorker,
+            worker_share_bps => DisputeRuling::Split { worker_share_bps },
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Protocol Config — global settings (one per program)
 // ---------------------------------------------------------------------------
@@ -136,8 +705,858 @@ pub struct ProtocolConfig {
 
     /// Bump for this PDA
     pub bump: u8,
+
+    // Fields below were appended after the first deployment; an account
+    // created before them is grown by `migrate_config`
+
+    /// Proposed next authority, waiting on `accept_authority`
+    /// (Pubkey::default() when no handover is pending)
+    pub pending_authority: Pubkey,
+
+    /// Tip paid from an expired escrow to whoever cranks `expire_handshake`,
+    /// in bps of the refunded amount (10 = 0.10%)
+    pub crank_tip_bps: u16,
+
+    /// Default review window after delivery before `claim_after_review`
+    /// opens (seconds)
+    pub review_window_secs: i64,
+
+    /// Bond (in $SOUR) a party must post to dispute a $SOUR handshake
+    /// (0 = disputes are free)
+    pub dispute_bond: u64,
+
+    /// Slice of a forfeited worker collateral sent to the Commons, in bps
+    /// (the creator receives the rest)
+    pub collateral_commons_bps: u16,
+
+    /// Pinch rate on approval bonuses, in bps of the bonus (0 = feeless)
+    pub bonus_pinch_bps: u16,
+
+    /// Running counter for recurring handshake IDs
+    pub recurring_count: u64,
+
+    /// Running counter for invoice IDs
+    pub invoice_count: u64,
 }
 
 impl ProtocolConfig {
     pub const SEED_PREFIX: &'static [u8] = b"config";
+
+    /// Account size before the appended fields (discriminator, five keys,
+    /// four bps values, six counters, bump) — what `migrate_config` grows
+    pub const LEGACY_SPACE: usize = 8 + 5 * 32 + 4 * 2 + 6 * 8 + 1;
+
+    /// Upper bound for the expiry crank tip (100 = 1%)
+    pub const MAX_CRANK_TIP_BPS: u16 = 100;
+
+    /// Upper bound for any review window (90 days)
+    pub const MAX_REVIEW_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
+
+    /// Upper bound for the Commons slice of forfeited collateral (5000 = 50%)
+    pub const MAX_COLLATERAL_COMMONS_BPS: u16 = 5000;
+
+    /// Upper bound for the Pinch rate on approval bonuses (1000 = 10%)
+    pub const MAX_BONUS_PINCH_BPS: u16 = 1000;
+
+    /// A review window must be positive and at most 90 days
+    pub fn is_valid_review_window(secs: i64) -> bool {
+        secs > 0 && secs <= Self::MAX_REVIEW_WINDOW_SECS
+    }
+
+    /// Check the fee and timing parameters (used by init and update)
+    pub fn validate(&self) -> Result<()> {
+        // Pinch fee must be reasonable (max 50%)
+        require!(self.pinch_bps <= 5000, SourError::InvalidPinchBps);
+
+        // Fee shares must sum to 10000 bps (100%)
+        let share_sum = self.treasury_share_bps as u32
+            + self.keepers_share_bps as u32
+            + self.commons_share_bps as u32;
+        require!(share_sum == 10_000, SourError::InvalidFeeShares);
+
+        // Expiry crank tip stays small
+        require!(
+            self.crank_tip_bps <= Self::MAX_CRANK_TIP_BPS,
+            SourError::InvalidCrankTip
+        );
+
+        require!(
+            Self::is_valid_review_window(self.review_window_secs),
+            SourError::InvalidReviewWindow
+        );
+
+        // Forfeited collateral mostly compensates the creator
+        require!(
+            self.collateral_commons_bps <= Self::MAX_COLLATERAL_COMMONS_BPS,
+            SourError::InvalidCollateralSlice
+        );
+
+        require!(
+            self.bonus_pinch_bps <= Self::MAX_BONUS_PINCH_BPS,
+            SourError::InvalidBonusPinch
+        );
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Allowed Mint — authority-managed allowlist entry for non-$SOUR escrows
+// Seeds: ["allowed_mint", mint]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedMint {
+    /// The SPL token mint this entry allows
+    pub mint: Pubkey,
+
+    /// Whether new handshakes may be created in this mint
+    pub enabled: bool,
+
+    /// Pinch fee for this mint in basis points (share split follows config)
+    pub pinch_bps: u16,
+
+    /// Keepers pool token account for this mint
+    pub keepers_pool: Pubkey,
+
+    /// Commons treasury token account for this mint
+    pub commons_treasury: Pubkey,
+
+    /// Buyback+LP treasury token account for this mint
+    pub buyback_treasury: Pubkey,
+
+    /// Bond (in this mint) a party must post to dispute (0 = free)
+    pub dispute_bond: u64,
+
+    /// Total of this mint sent to buyback+LP treasury
+    pub total_to_treasury: u64,
+
+    /// Total of this mint distributed to keepers
+    pub total_to_keepers: u64,
+
+    /// Total of this mint distributed to commons
+    pub total_to_commons: u64,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl AllowedMint {
+    pub const SEED_PREFIX: &'static [u8] = b"allowed_mint";
+}
+
+// ---------------------------------------------------------------------------
+// Worker Record — per-wallet track record used for bounty eligibility
+// Seeds: ["worker_record", worker]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct WorkerRecord {
+    /// The worker this record belongs to
+    pub worker: Pubkey,
+
+    /// Handshakes completed through approval or claim
+    pub completed: u32,
+
+    /// Accepted handshakes the worker walked away from
+    pub withdrawals: u32,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl WorkerRecord {
+    pub const SEED_PREFIX: &'static [u8] = b"worker_record";
+
+    /// Fill in a record `init_if_needed` has just created (no-op once open)
+    pub fn open_if_new(&mut self, worker: Pubkey, bump: u8) {
+        if self.worker == Pubkey::default() {
+            self.worker = worker;
+            self.bump = bump;
+        }
+    }
+
+    /// Credit one completed handshake
+    pub fn record_completed(&mut self) -> Result<()> {
+        self.completed = self
+            .completed
+            .checked_add(1)
+            .ok_or(SourError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a withdrawal from an accepted handshake
+    pub fn record_withdrawal(&mut self) -> Result<()> {
+        self.withdrawals = self
+            .withdrawals
+            .checked_add(1)
+            .ok_or(SourError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Bounty Allowlist — workers permitted to accept an open bounty
+// Seeds: ["bounty_allowlist", handshake_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct BountyAllowlist {
+    /// The open handshake
+    pub handshake_id: u64,
+
+    /// Eligible workers
+    #[max_len(16)]
+    pub workers: Vec<Pubkey>,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl BountyAllowlist {
+    pub const SEED_PREFIX: &'static [u8] = b"bounty_allowlist";
+
+    /// Upper bound for the allowlist (account space)
+    pub const MAX_WORKERS: usize = 16;
+}
+
+// ---------------------------------------------------------------------------
+// Application — a prospective worker's bid on an open bounty
+// Seeds: ["application", handshake_id, applicant]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct Application {
+    /// The open handshake applied to
+    pub handshake_id: u64,
+
+    /// The prospective worker
+    pub applicant: Pubkey,
+
+    /// Proposed escrow amount
+    pub amount: u64,
+
+    /// Proposed delivery deadline
+    pub deadline_ts: i64,
+
+    /// Hash of the off-chain pitch
+    pub pitch_hash: [u8; 32],
+
+    /// Rent the applicant deposited for the collateral vault (held in this
+    /// account's lamports); it pays for the vault if they are selected and
+    /// comes back with the rent otherwise
+    pub collateral_rent: u64,
+
+    /// Unix timestamp when the application was submitted
+    pub submitted_at: i64,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl Application {
+    pub const SEED_PREFIX: &'static [u8] = b"application";
+}
+
+// ---------------------------------------------------------------------------
+// Amendment — a pending change to an accepted handshake's terms
+// Seeds: ["amendment", handshake_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct Amendment {
+    /// The handshake being amended
+    pub handshake_id: u64,
+
+    /// Creator or worker who proposed the change
+    pub proposer: Pubkey,
+
+    /// Proposed delivery deadline (None = unchanged)
+    pub deadline_ts: Option<i64>,
+
+    /// Proposed escrow amount (None = unchanged)
+    pub amount: Option<u64>,
+
+    /// Unix timestamp when the amendment was proposed
+    pub proposed_at: i64,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl Amendment {
+    pub const SEED_PREFIX: &'static [u8] = b"amendment";
+
+    /// Whether the proposed terms can still be applied to `handshake`: at
+    /// least one change, a future deadline, and an amount above what has
+    /// already been released.
+    pub fn check(&self, handshake: &Handshake, now: i64) -> Result<()> {
+        require!(
+            self.deadline_ts.is_some() || self.amount.is_some(),
+            SourError::InvalidAmendment
+        );
+        if let Some(deadline_ts) = self.deadline_ts {
+            require!(deadline_ts > now, SourError::InvalidAmendment);
+        }
+        if let Some(amount) = self.amount {
+            require!(amount > handshake.released, SourError::InvalidAmendment);
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cancel Proposal — one party's offer to walk away with a negotiated split
+// Seeds: ["cancel_proposal", handshake_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct CancelProposal {
+    /// The handshake to cancel
+    pub handshake_id: u64,
+
+    /// Creator or worker who proposed it
+    pub proposer: Pubkey,
+
+    /// Share of the remaining escrow paid to the worker (minus Pinch); the
+    /// rest is refunded to the creator
+    pub worker_share_bps: u16,
+
+    /// Unix timestamp when it was proposed
+    pub proposed_at: i64,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl CancelProposal {
+    pub const SEED_PREFIX: &'static [u8] = b"cancel_proposal";
+}
+
+// ---------------------------------------------------------------------------
+// Payout Stream — vests the worker's payout after approval
+// Seeds: ["payout_stream", handshake_id]; tokens in ["stream_vault", id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutStream {
+    /// Handshake whose payout this stream vests
+    pub handshake_id: u64,
+
+    /// Worker entitled to the vested tokens (set when the stream starts)
+    pub worker: Pubkey,
+
+    /// Amount deposited at approval (0 until then)
+    pub total: u64,
+
+    /// Amount the worker has withdrawn so far
+    pub withdrawn: u64,
+
+    /// Vesting period, counted from approval
+    pub duration_secs: i64,
+
+    /// Nothing vests until this long after approval
+    pub cliff_secs: i64,
+
+    /// Unix timestamp of approval (0 until the stream starts)
+    pub start_ts: i64,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl PayoutStream {
+    pub const SEED_PREFIX: &'static [u8] = b"payout_stream";
+
+    /// Upper bound for a vesting period (4 years)
+    pub const MAX_DURATION_SECS: i64 = 4 * 365 * 24 * 60 * 60;
+
+    pub fn is_valid_terms(terms: &VestingTerms) -> bool {
+        terms.duration_secs > 0
+            && terms.duration_secs <= Self::MAX_DURATION_SECS
+            && terms.cliff_secs >= 0
+            && terms.cliff_secs <= terms.duration_secs
+    }
+
+    /// Start vesting `total` for `worker` from `now`
+    pub fn start(&mut self, worker: Pubkey, total: u64, now: i64) {
+        self.worker = worker;
+        self.total = total;
+        self.start_ts = now;
+    }
+
+    /// Amount vested by `now`: nothing before the cliff, then linear until
+    /// the end of the period
+    pub fn vested(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start_ts);
+        if self.start_ts == 0 || elapsed < self.cliff_secs {
+            return Ok(0);
+        }
+        if elapsed >= self.duration_secs {
+            return Ok(self.total);
+        }
+        Ok((self.total as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(SourError::MathOverflow)?
+            .checked_div(self.duration_secs as u128)
+            .ok_or(SourError::MathOverflow)? as u64)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Team Payees — fixed split of the worker's payout across a team
+// Seeds: ["team", handshake_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct TeamPayees {
+    /// Handshake whose worker payout is split
+    pub handshake_id: u64,
+
+    /// Team lead — the handshake's worker, who accepts for the team and
+    /// receives the rounding remainder
+    pub lead: Pubkey,
+
+    /// Payees and their shares of every worker payout (sum to 100%)
+    #[max_len(8)]
+    pub payees: Vec<Payee>,
+
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct Payee {
+    pub wallet: Pubkey,
+    /// Share of the worker payout in bps
+    pub share_bps: u16,
+}
+
+impl TeamPayees {
+    pub const SEED_PREFIX: &'static [u8] = b"team";
+    pub const MAX_PAYEES: usize = 8;
+
+    /// 1-8 distinct payees with non-zero shares summing to 10000 bps
+    pub fn is_valid(payees: &[Payee]) -> bool {
+        let share_sum: u32 = payees.iter().map(|payee| payee.share_bps as u32).sum();
+        !payees.is_empty()
+            && payees.len() <= Self::MAX_PAYEES
+            && share_sum == 10_000
+            && payees.iter().enumerate().all(|(i, payee)| {
+                payee.share_bps > 0
+                    && payee.wallet != Pubkey::default()
+                    && payees[..i].iter().all(|other| other.wallet != payee.wallet)
+            })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Recurring Handshake — a retainer paid period by period from one escrow
+// Seeds: ["recurring", recurring_id]; tokens in ["recurring_vault", id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct RecurringHandshake {
+    /// Unique sequential ID (separate from handshake IDs)
+    pub id: u64,
+
+    /// Baker A — funds the periods
+    pub creator: Pubkey,
+
+    /// Baker B — delivers each period
+    pub worker: Pubkey,
+
+    /// Mint of the escrowed token ($SOUR or an allowlisted mint)
+    pub mint: Pubkey,
+
+    /// Description of the retainer
+    #[max_len(280)]
+    pub description: String,
+
+    /// Paid out (minus Pinch) for each delivered period
+    pub amount_per_period: u64,
+
+    /// Length of one period
+    pub period_secs: i64,
+
+    /// Periods the retainer runs for — `max_periods` at creation, cut short
+    /// when either side stops renewal
+    pub end_periods: u16,
+
+    /// Periods the creator has funded so far
+    pub funded_periods: u16,
+
+    /// Periods settled (paid or refunded), in order; the next one to settle
+    /// is the current period
+    pub settled_periods: u16,
+
+    /// Funded periods, indexed by period — what each escrowed and when it
+    /// was delivered
+    #[max_len(120)]
+    pub periods: Vec<RecurringPeriod>,
+
+    /// Current status
+    pub status: RecurringStatus,
+
+    /// Unix timestamp of creation
+    pub created_at: i64,
+
+    /// Unix timestamp of acceptance — period 0 starts here (0 until then)
+    pub started_at: i64,
+
+    /// Content hash of the latest delivery
+    pub delivery_hash: [u8; 32],
+
+    /// PDA bump seed for the vault authority
+    pub vault_bump: u8,
+
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl RecurringHandshake {
+    pub const SEED_PREFIX: &'static [u8] = b"recurring";
+
+    /// Upper bound for the number of periods (must match `max_len` above)
+    pub const MAX_PERIODS: u16 = 120;
+
+    /// Upper bound for a period (1 year)
+    pub const MAX_PERIOD_SECS: i64 = 365 * 24 * 60 * 60;
+
+    pub fn is_valid_terms(amount_per_period: u64, period_secs: i64, max_periods: u16) -> bool {
+        amount_per_period > 0
+            && period_secs > 0
+            && period_secs <= Self::MAX_PERIOD_SECS
+            && max_periods > 0
+            && max_periods <= Self::MAX_PERIODS
+    }
+
+    /// Start of period `index`
+    pub fn period_start(&self, index: u16) -> Result<i64> {
+        Ok((index as i64)
+            .checked_mul(self.period_secs)
+            .and_then(|offset| self.started_at.checked_add(offset))
+            .ok_or(SourError::MathOverflow)?)
+    }
+
+    /// Index of the period running at `now` (periods start back to back)
+    pub fn period_at(&self, now: i64) -> u16 {
+        let elapsed = now.saturating_sub(self.started_at).max(0);
+        (elapsed / self.period_secs).min(u16::MAX as i64) as u16
+    }
+
+    /// Escrow for `periods` periods
+    pub fn amount_for(&self, periods: u16) -> Result<u64> {
+        Ok(self
+            .amount_per_period
+            .checked_mul(periods as u64)
+            .ok_or(SourError::MathOverflow)?)
+    }
+
+    /// The funded period `index`
+    pub fn period(&self, index: u16) -> Result<&RecurringPeriod> {
+        Ok(self
+            .periods
+            .get(index as usize)
+            .ok_or(SourError::PeriodNotFunded)?)
+    }
+
+    /// Record `count` newly funded periods sharing the `received` escrow;
+    /// the last one takes the rounding remainder
+    pub fn fund_periods(&mut self, count: u16, received: u64) -> Result<()> {
+        let each = received / count as u64;
+        let remainder = received % count as u64;
+        for index in 0..count {
+            let escrowed = if index + 1 == count { each + remainder } else { each };
+            self.periods.push(RecurringPeriod {
+                escrowed,
+                delivered_at: 0,
+            });
+        }
+        self.funded_periods = self
+            .funded_periods
+            .checked_add(count)
+            .ok_or(SourError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Escrow held for periods `from..to`
+    pub fn escrowed_between(&self, from: u16, to: u16) -> Result<u64> {
+        self.periods
+            .get(from as usize..to as usize)
+            .unwrap_or_default()
+            .iter()
+            .try_fold(0u64, |total, period| total.checked_add(period.escrowed))
+            .ok_or_else(|| SourError::MathOverflow.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RecurringPeriod {
+    /// Escrow this period pays out — what actually reached the vault for it
+    pub escrowed: u64,
+
+    /// Unix timestamp when this period was delivered (0 if not yet)
+    pub delivered_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RecurringStatus {
+    /// Waiting for the worker to accept
+    Created,
+    /// Periods running
+    Active,
+    /// Every period of the (possibly shortened) run has settled
+    Completed,
+    /// Stopped before acceptance; escrow refunded
+    Cancelled,
+}
+
+// ---------------------------------------------------------------------------
+// Invoice — a worker's bill, funded by the named client into a handshake
+// Seeds: ["invoice", invoice_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct Invoice {
+    /// Unique sequential ID (separate from handshake IDs)
+    pub id: u64,
+
+    /// Baker B — issued the invoice and becomes the handshake's worker
+    pub worker: Pubkey,
+
+    /// Baker A — the client expected to fund it
+    pub creator: Pubkey,
+
+    /// Mint the invoice is payable in ($SOUR or an allowlisted mint)
+    pub mint: Pubkey,
+
+    /// Amount billed
+    pub amount: u64,
+
+    /// Delivery deadline of the resulting handshake; the invoice can no
+    /// longer be funded after it
+    pub deadline_ts: i64,
+
+    /// Description of the work
+    #[max_len(280)]
+    pub description: String,
+
+    /// Unix timestamp of issue
+    pub created_at: i64,
+
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl Invoice {
+    pub const SEED_PREFIX: &'static [u8] = b"invoice";
+}
+
+// ---------------------------------------------------------------------------
+// Evidence Log — statements and artifacts from both sides of a dispute
+// Seeds: ["evidence", handshake_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct EvidenceLog {
+    /// The disputed handshake
+    pub handshake_id: u64,
+
+    /// Entries in submission order
+    #[max_len(8)]
+    pub entries: Vec<EvidenceEntry>,
+
+    /// Set when a resolution starts (panel drawn); no further submissions
+    pub closed: bool,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+/// One piece of evidence: a hash of the content plus where to find it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EvidenceEntry {
+    pub submitter: Pubkey,
+    pub content_hash: [u8; 32],
+    #[max_len(200)]
+    pub uri: String,
+    pub submitted_at: i64,
+}
+
+impl EvidenceLog {
+    pub const SEED_PREFIX: &'static [u8] = b"evidence";
+
+    /// Entries each participant may submit
+    pub const MAX_ENTRIES_PER_SIDE: usize = 4;
+
+    /// Upper bound for an evidence URI
+    pub const MAX_URI_LEN: usize = 200;
+
+    /// Entries already submitted by `submitter`
+    pub fn entries_by(&self, submitter: &Pubkey) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.submitter == *submitter)
+            .count()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Arbiter Registry — authority-managed pool that dispute panels are drawn from
+// Seeds: ["arbiter_registry"]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct ArbiterRegistry {
+    /// Registered arbiters
+    #[max_len(32)]
+    pub arbiters: Vec<Pubkey>,
+
+    /// Number of arbiters drawn for each dispute panel (odd, 1..=7)
+    pub panel_size: u8,
+
+    /// Seconds a panel has to vote once it is drawn
+    pub voting_window_secs: i64,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+impl ArbiterRegistry {
+    pub const SEED_PREFIX: &'static [u8] = b"arbiter_registry";
+
+    /// Upper bound for registered arbiters (account space)
+    pub const MAX_ARBITERS: usize = 32;
+
+    /// Upper bound for a dispute panel
+    pub const MAX_PANEL_SIZE: u8 = DisputeCase::MAX_PANEL as u8;
+
+    /// An odd panel of 1..=7 (no ties) and a voting window of at most 90 days
+    pub fn is_valid_panel(panel_size: u8, voting_window_secs: i64) -> bool {
+        panel_size > 0
+            && panel_size <= Self::MAX_PANEL_SIZE
+            && panel_size % 2 == 1
+            && ProtocolConfig::is_valid_review_window(voting_window_secs)
+    }
+
+    /// Registered arbiters other than the `excluded` keys
+    pub fn eligible(&self, excluded: &[Pubkey]) -> Vec<Pubkey> {
+        self.arbiters
+            .iter()
+            .filter(|arbiter| !excluded.contains(arbiter))
+            .copied()
+            .collect()
+    }
+
+    /// Draw `panel_size` distinct arbiters, skipping `excluded` keys
+    /// (the parties and the handshake's own arbiter). `seed` picks the
+    /// starting point; arbiters are then taken in registry order.
+    pub fn draw_panel(&self, seed: u64, excluded: &[Pubkey]) -> Result<Vec<Pubkey>> {
+        let eligible = self.eligible(excluded);
+        let panel_size = self.panel_size as usize;
+        require!(eligible.len() >= panel_size, SourError::PanelUnavailable);
+
+        let start = (seed % eligible.len() as u64) as usize;
+        Ok((0..panel_size)
+            .map(|i| eligible[(start + i) % eligible.len()])
+            .collect())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dispute Case — a panel vote on one disputed handshake
+// Seeds: ["dispute_case", handshake_id]
+// ---------------------------------------------------------------------------
+#[account]
+#[derive(InitSpace)]
+pub struct DisputeCase {
+    /// The disputed handshake
+    pub handshake_id: u64,
+
+    /// Drawn panel and each member's vote (empty until `draw_panel`)
+    #[max_len(7)]
+    pub panel: Vec<PanelSeat>,
+
+    /// Unix timestamp when the case was opened
+    pub opened_at: i64,
+
+    /// The panel is drawn from the hash of the first block at or after this
+    /// slot — unknown to either party when the case is opened
+    pub draw_slot: u64,
+
+    /// Votes are accepted until this timestamp (0 until the panel is drawn)
+    pub voting_ends_at: i64,
+
+    /// Set once `finalize_dispute` has executed the ruling
+    pub finalized: bool,
+
+    /// Bump for this PDA
+    pub bump: u8,
+}
+
+/// One panel member and their vote (None until cast)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PanelSeat {
+    pub arbiter: Pubkey,
+    pub vote: Option<DisputeRuling>,
+}
+
+impl DisputeCase {
+    pub const SEED_PREFIX: &'static [u8] = b"dispute_case";
+
+    /// Upper bound for a dispute panel (account space)
+    pub const MAX_PANEL: usize = 7;
+
+    /// Slots between opening a case and the block whose hash draws the panel
+    pub const DRAW_DELAY_SLOTS: u64 = 4;
+
+    /// True once `draw_panel` has seated the arbiters
+    pub fn is_drawn(&self) -> bool {
+        !self.panel.is_empty()
+    }
+
+    /// Whether the case can no longer produce a ruling, handing the dispute
+    /// back to `resolve_dispute`: the panel cannot be drawn (too few
+    /// eligible arbiters left in `registry`) or was not drawn within one
+    /// voting window of opening, or voting closed short of a quorum
+    pub fn is_stalled(&self, registry: &ArbiterRegistry, excluded: &[Pubkey], now: i64) -> bool {
+        if self.is_drawn() {
+            return now > self.voting_ends_at && self.votes_cast() < self.quorum();
+        }
+        registry.eligible(excluded).len() < registry.panel_size as usize
+            || now > self.opened_at.saturating_add(registry.voting_window_secs)
+    }
+
+    /// Number of votes cast so far
+    pub fn votes_cast(&self) -> usize {
+        self.panel.iter().filter(|seat| seat.vote.is_some()).count()
+    }
+
+    /// Votes needed to finalize: a strict majority of the panel
+    pub fn quorum(&self) -> usize {
+        self.panel.len() / 2 + 1
+    }
+
+    /// Median worker share of the votes cast. On an even count (a panel
+    /// short of full turnout) it is the mean of the two middle votes, so a
+    /// split panel lands halfway rather than on either party's side. With
+    /// only refund/pay votes and a clear majority this is the majority
+    /// outcome.
+    pub fn median_ruling(&self) -> Result<DisputeRuling> {
+        let mut shares = self
+            .panel
+            .iter()
+            .filter_map(|seat| seat.vote.as_ref())
+            .map(|vote| vote.worker_share_bps())
+            .collect::<Result<Vec<u16>>>()?;
+        require!(!shares.is_empty(), SourError::QuorumNotReached);
+        shares.sort_unstable();
+        let mid = shares.len() / 2;
+        let median = if shares.len() % 2 == 1 {
+            shares[mid]
+        } else {
+            ((shares[mid - 1] as u32 + shares[mid] as u32) / 2) as u16
+        };
+        Ok(DisputeRuling::from_worker_share_bps(median))
+    }
 }
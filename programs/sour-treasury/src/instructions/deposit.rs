@@ -1,11 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::TreasuryConfig;
 use crate::errors::TreasuryError;
 use crate::events::Deposited;
 
 // ---------------------------------------------------------------------------
 // Deposit — accept any SPL token into the Treasury vault
+// Classic SPL and Token-2022 mints both work; transfer-hook extra accounts
+// are passed as remaining accounts. Stats record the amount actually
+// received, so transfer-fee mints don't inflate total_deposited.
 // Called by Handshake program (approve/resolve) or by anyone directly.
 // ---------------------------------------------------------------------------
 
@@ -22,7 +26,7 @@ pub struct Deposit<'info> {
     pub config: Account<'info, TreasuryConfig>,
 
     /// The token being deposited (can be any SPL token — multi-token support)
-    pub deposit_mint: Account<'info, Mint>,
+    pub deposit_mint: InterfaceAccount<'info, Mint>,
 
     /// Depositor's token account
     #[account(
@@ -30,7 +34,7 @@ pub struct Deposit<'info> {
         constraint = depositor_token.owner == depositor.key(),
         constraint = depositor_token.mint == deposit_mint.key(),
     )]
-    pub depositor_token: Account<'info, TokenAccount>,
+    pub depositor_token: InterfaceAccount<'info, TokenAccount>,
 
     /// Treasury vault ATA for this token mint, owned by config PDA
     #[account(
@@ -38,45 +42,59 @@ pub struct Deposit<'info> {
         constraint = treasury_vault.mint == deposit_mint.key(),
         constraint = treasury_vault.owner == config.key(),
     )]
-    pub treasury_vault: Account<'info, TokenAccount>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+    amount: u64,
+) -> Result<()> {
     require!(amount > 0, TreasuryError::ZeroDeposit);
 
+    let balance_before = ctx.accounts.treasury_vault.amount;
+
     // Transfer tokens from depositor → treasury vault
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.depositor_token.to_account_info(),
-        to: ctx.accounts.treasury_vault.to_account_info(),
-        authority: ctx.accounts.depositor.to_account_info(),
-    };
-    token::transfer(
-        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+    invoke_transfer_checked(
+        ctx.accounts.token_program.key,
+        ctx.accounts.depositor_token.to_account_info(),
+        ctx.accounts.deposit_mint.to_account_info(),
+        ctx.accounts.treasury_vault.to_account_info(),
+        ctx.accounts.depositor.to_account_info(),
+        ctx.remaining_accounts,
         amount,
+        ctx.accounts.deposit_mint.decimals,
+        &[],
     )?;
 
+    // Reload vault to get updated balance (transfer-fee mints deliver less)
+    ctx.accounts.treasury_vault.reload()?;
+    let received = ctx
+        .accounts
+        .treasury_vault
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(TreasuryError::Overflow)?;
+    require!(received > 0, TreasuryError::ZeroDeposit);
+
     // Update lifetime stats
     let config = &mut ctx.accounts.config;
     config.total_deposited = config
         .total_deposited
-        .checked_add(amount)
+        .checked_add(received)
         .ok_or(TreasuryError::Overflow)?;
 
-    // Reload vault to get updated balance
-    ctx.accounts.treasury_vault.reload()?;
-
     emit!(Deposited {
         mint: ctx.accounts.deposit_mint.key(),
-        amount,
+        amount: received,
         depositor: ctx.accounts.depositor.key(),
         vault_balance: ctx.accounts.treasury_vault.amount,
     });
 
     msg!(
         "Deposited {} tokens into treasury (vault balance: {})",
-        amount,
+        received,
         ctx.accounts.treasury_vault.amount
     );
 
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::{TreasuryConfig, BatchRecord};
 use crate::errors::TreasuryError;
 use crate::events::BatchInitiated;
@@ -21,6 +22,9 @@ use crate::events::BatchInitiated;
 //   - LP tokens are sent to a protocol-owned vault (locked)
 //
 // Keeper calls complete_batch afterwards to record results.
+//
+// Uses transfer_checked so Token-2022 mints can be batched; transfer-hook
+// extra accounts are passed as remaining accounts.
 // ---------------------------------------------------------------------------
 
 #[derive(Accounts)]
@@ -47,7 +51,7 @@ pub struct ExecuteBatch<'info> {
     pub batch_record: Account<'info, BatchRecord>,
 
     /// The token mint being batched
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// Treasury vault holding accumulated tokens
     #[account(
@@ -55,7 +59,7 @@ pub struct ExecuteBatch<'info> {
         constraint = treasury_vault.mint == token_mint.key(),
         constraint = treasury_vault.owner == config.key(),
     )]
-    pub treasury_vault: Account<'info, TokenAccount>,
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// Keeper's token account to receive tokens for swap
     #[account(
@@ -63,13 +67,13 @@ pub struct ExecuteBatch<'info> {
         constraint = keeper_token.owner == keeper.key(),
         constraint = keeper_token.mint == token_mint.key(),
     )]
-    pub keeper_token: Account<'info, TokenAccount>,
+    pub keeper_token: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ExecuteBatch>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteBatch<'info>>) -> Result<()> {
     let vault_balance = ctx.accounts.treasury_vault.amount;
 
     require!(
@@ -93,18 +97,16 @@ pub fn handler(ctx: Context<ExecuteBatch>) -> Result<()> {
     let seeds = &[b"treasury-config".as_ref(), &[ctx.accounts.config.bump]];
     let signer_seeds = &[&seeds[..]];
 
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.treasury_vault.to_account_info(),
-        to: ctx.accounts.keeper_token.to_account_info(),
-        authority: ctx.accounts.config.to_account_info(),
-    };
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        ),
+    invoke_transfer_checked(
+        ctx.accounts.token_program.key,
+        ctx.accounts.treasury_vault.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.keeper_token.to_account_info(),
+        ctx.accounts.config.to_account_info(),
+        ctx.remaining_accounts,
         vault_balance,
+        ctx.accounts.token_mint.decimals,
+        signer_seeds,
     )?;
 
     // Record batch
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 use crate::state::TreasuryConfig;
 use crate::errors::TreasuryError;
 
@@ -22,7 +22,7 @@ pub struct InitializeConfig<'info> {
     pub config: Account<'info, TreasuryConfig>,
 
     /// The $SOUR token mint
-    pub sour_mint: Account<'info, Mint>,
+    pub sour_mint: InterfaceAccount<'info, Mint>,
 
     pub system_program: Program<'info, System>,
 }
@@ -3,7 +3,8 @@
  *
  * 1. Creates a test SOUR SPL token mint
  * 2. Creates 3 token accounts (keepers_pool, commons_treasury, buyback_treasury)
- * 3. Calls initialize_config with pinch_bps=200, shares 50/30/20
+ * 3. Calls initialize_config with pinch_bps=200, shares 50/30/20, crank tip 10 bps,
+ *    3-day review window
  */
 
 import {
@@ -140,15 +141,17 @@ async function main() {
   console.log("Config PDA:", configPda.toBase58());
 
   // Build instruction data:
-  // 8 bytes discriminator + 4 x u16 (2 bytes each) = 16 bytes total
+  // 8 bytes discriminator + 5 x u16 (2 bytes each) + 1 x i64 = 26 bytes total
   // Use exact discriminator from IDL
   const disc = Buffer.from([208, 127, 21, 1, 194, 190, 196, 70]);
-  const data = Buffer.alloc(16);
+  const data = Buffer.alloc(26);
   disc.copy(data, 0);
   data.writeUInt16LE(200, 8);    // pinch_bps = 2%
   data.writeUInt16LE(5000, 10);  // burn_share_bps = 50%
   data.writeUInt16LE(3000, 12);  // keepers_share_bps = 30%
   data.writeUInt16LE(2000, 14);  // commons_share_bps = 20%
+  data.writeUInt16LE(10, 16);    // crank_tip_bps = 0.10%
+  data.writeBigInt64LE(BigInt(3 * 24 * 60 * 60), 18); // review_window_secs = 3 days
 
   // Account order per IDL: config, sour_mint, keepers_pool, commons_treasury, authority, system_program
   const ix = new TransactionInstruction({
@@ -191,6 +194,8 @@ async function main() {
     treasuryShareBps: 5000,
     keepersShareBps: 3000,
     commonsShareBps: 2000,
+    crankTipBps: 10,
+    reviewWindowSecs: 3 * 24 * 60 * 60,
   };
   fs.writeFileSync(
     "scripts/devnet-addresses.json",
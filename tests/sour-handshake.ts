@@ -6,10 +6,35 @@
 //   1. Initialize config
 //   2. Create handshake (escrow $SOUR)
 //   3. Accept handshake
-//   4. Deliver work
+//   4. Deliver work (content hash + URI)
 //   5. Approve → Pinch fee applied (treasury + keepers + commons)
 //   6. Cancel (before acceptance)
 //   7. Dispute + Resolve
+//   8. Expire past deadline (crank tip)
+//   9. Milestone tranches (per-tranche approval)
+//  10. Claim after the review window
+//  11. Allowlisted non-$SOUR mint (own Pinch rate + pools)
+//  12. Token-2022 transfer-fee mint (escrows the received amount)
+//  13. update_config + two-step authority handover
+//  14. Split dispute ruling (worker share pays Pinch, rest refunded)
+//  15. Per-handshake arbiter resolves and earns its fee
+//  16. Evidence log + arbiter panel votes, finalize_dispute pays the median
+//      (a stalled case falls back to resolve_dispute)
+//  17. Dispute bonds (winner's returned, loser's to Commons)
+//  18. Open bounty — first eligible signer becomes the worker
+//  19. Applications — creator selects a bid, escrow topped up to match
+//  20. Amendments — escrow raised and deadline extended, then trimmed
+//  21. Mutual cancel — negotiated split, Pinch only on the worker share
+//  22. Worker declines, or withdraws after accepting (logged on record)
+//  23. Worker collateral — returned on approval, forfeited on withdraw
+//  24. Revision requests — delivery sent back, then limit enforced
+//  25. Vested payout — Pinch up front, worker share streamed
+//  26. Recurring handshake — period paid, renewal stopped, refund
+//  27. Approval bonus — paid from the creator, own Pinch rate
+//  28. Invoices — worker bills, client funds straight into Accepted
+//  29. Team payout — worker share split by bps, remainder to the lead
+//  30. Subcontract — child funded by a lien on the parent's payout
+//  31. Subcontract liens — paid or released on every exit of the parent
 // ============================================================================
 
 import * as anchor from "@coral-xyz/anchor";
@@ -17,6 +42,11 @@ import { Program } from "@coral-xyz/anchor";
 import { SourHandshake } from "../target/types/sour_handshake";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
+  getMintLen,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeMintInstruction,
   createMint,
   createAccount,
   mintTo,
@@ -52,6 +82,43 @@ describe("sour-handshake", () => {
   const TREASURY_SHARE = 5000;    // 50% of fee → buyback+LP
   const KEEPERS_SHARE = 3000;     // 30% of fee
   const COMMONS_SHARE = 2000;     // 20% of fee
+  const CRANK_TIP_BPS = 10;       // 0.10% of an expired escrow
+  const REVIEW_WINDOW_SECS = 3 * 24 * 60 * 60; // 3 days to review a delivery
+
+  // update_config argument that leaves every setting unchanged
+  const NO_CONFIG_CHANGE = {
+    pinchBps: null,
+    treasuryShareBps: null,
+    keepersShareBps: null,
+    commonsShareBps: null,
+    crankTipBps: null,
+    reviewWindowSecs: null,
+    disputeBond: null,
+    collateralCommonsBps: null,
+    bonusPinchBps: null,
+  };
+
+  // create_handshake options: protocol review window, no arbiter, not a
+  // bounty, no worker collateral, default revision limit, paid at once
+  const NO_OPTIONS = {
+    reviewWindowSecs: null,
+    arbiter: null,
+    bounty: null,
+    workerCollateral: null,
+    maxRevisions: null,
+    vesting: null,
+    team: null,
+  };
+
+  // Content hash committed by `deliver` (stands in for a hash of the work)
+  const DELIVERY_HASH = Array(32).fill(1);
+
+  // Worker record PDA — approve and claim credit it (opening it if needed)
+  const workerRecordOf = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("worker_record"), wallet.toBuffer()],
+      program.programId
+    )[0];
 
   before(async () => {
     // Transfer SOL to worker for tx fees (from authority wallet)
@@ -141,7 +208,7 @@ describe("sour-handshake", () => {
   // =========================================================================
   it("initializes protocol config", async () => {
     await program.methods
-      .initializeConfig(PINCH_BPS, TREASURY_SHARE, KEEPERS_SHARE, COMMONS_SHARE)
+      .initializeConfig(PINCH_BPS, TREASURY_SHARE, KEEPERS_SHARE, COMMONS_SHARE, CRANK_TIP_BPS, new anchor.BN(REVIEW_WINDOW_SECS))
       .accounts({
         config: configPda,
         sourMint,
@@ -158,6 +225,8 @@ describe("sour-handshake", () => {
     assert.equal(config.treasuryShareBps, TREASURY_SHARE);
     assert.equal(config.keepersShareBps, KEEPERS_SHARE);
     assert.equal(config.commonsShareBps, COMMONS_SHARE);
+    assert.equal(config.crankTipBps, CRANK_TIP_BPS);
+    assert.equal(config.reviewWindowSecs.toNumber(), REVIEW_WINDOW_SECS);
     assert.equal(config.handshakeCount.toNumber(), 0);
     assert.equal(config.totalToTreasury.toNumber(), 0);
     console.log("    ✓ Config initialized with 2% Pinch (50/30/20 split)");
@@ -188,15 +257,21 @@ describe("sour-handshake", () => {
     const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60; // 7 days
 
     await program.methods
-      .createHandshake("Design a logo for SOUR", new anchor.BN(ESCROW_AMOUNT), new anchor.BN(deadline))
+      .createHandshake("Design a logo for SOUR", new anchor.BN(ESCROW_AMOUNT), new anchor.BN(deadline), [], NO_OPTIONS)
       .accounts({
         config: configPda,
         handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
         vault: vaultPda,
         vaultAuthority: vaultAuthPda,
         creatorTokenAccount,
         worker: worker.publicKey,
-        sourMint,
+        mint: sourMint,
+        allowedMint: null,
         creator: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -228,12 +303,24 @@ describe("sour-handshake", () => {
       [Buffer.from("handshake"), idBytes],
       program.programId
     );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
 
     await program.methods
       .acceptHandshake()
       .accounts({
         handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
         worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([worker])
       .rpc();
@@ -258,9 +345,10 @@ describe("sour-handshake", () => {
     );
 
     await program.methods
-      .deliver()
+      .deliver(DELIVERY_HASH, "ipfs://sour-logo-final")
       .accounts({
         handshake: handshakePda,
+        milestones: null,
         worker: worker.publicKey,
       })
       .signers([worker])
@@ -269,6 +357,8 @@ describe("sour-handshake", () => {
     const handshake = await program.account.handshake.fetch(handshakePda);
     assert.deepEqual(handshake.status, { delivered: {} });
     assert.ok(handshake.deliveredAt.toNumber() > 0);
+    assert.deepEqual(handshake.deliveryHash, DELIVERY_HASH);
+    assert.equal(handshake.deliveryUri, "ipfs://sour-logo-final");
 
     console.log("    ✓ Work delivered");
   });
@@ -302,18 +392,30 @@ describe("sour-handshake", () => {
     const treasuryBefore = await getAccount(provider.connection, buybackTreasury);
 
     await program.methods
-      .approve()
+      .approve(null)
       .accounts({
         config: configPda,
         handshake: handshakePda,
+        milestones: null,
+        allowedMint: null,
         vault: vaultPda,
+        mint: sourMint,
         vaultAuthority: vaultAuthPda,
         workerTokenAccount,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        subcontract: null,
+        subcontractVault: null,
+        creatorTokenAccount: null,
         keepersPool,
         commonsTreasury,
         buybackTreasury,
         creator: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
@@ -361,6 +463,11 @@ describe("sour-handshake", () => {
     assert.equal(config.totalToKeepers.toNumber(), keepersAmount);
     assert.equal(config.totalToCommons.toNumber(), commonsAmount);
 
+    // The worker's record was opened by the approval and credited
+    const record = await program.account.workerRecord.fetch(workerRecordOf(worker.publicKey));
+    assert.ok(record.worker.equals(worker.publicKey));
+    assert.equal(record.completed, 1);
+
     console.log(`    ✓ Approved! Worker: ${workerAmount}, Treasury: ${treasuryAmount}, Keepers: ${keepersAmount}, Commons: ${commonsAmount}`);
   });
 
@@ -391,15 +498,21 @@ describe("sour-handshake", () => {
 
     // Create
     await program.methods
-      .createHandshake("Test cancel", new anchor.BN(cancelAmount), new anchor.BN(deadline))
+      .createHandshake("Test cancel", new anchor.BN(cancelAmount), new anchor.BN(deadline), [], NO_OPTIONS)
       .accounts({
         config: configPda,
         handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
         vault: vaultPda,
         vaultAuthority: vaultAuthPda,
         creatorTokenAccount,
         worker: worker.publicKey,
-        sourMint,
+        mint: sourMint,
+        allowedMint: null,
         creator: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -416,8 +529,11 @@ describe("sour-handshake", () => {
         config: configPda,
         handshake: handshakePda,
         vault: vaultPda,
+        mint: sourMint,
         vaultAuthority: vaultAuthPda,
         creatorTokenAccount,
+        payoutStream: null,
+        streamVault: null,
         creator: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -456,21 +572,39 @@ describe("sour-handshake", () => {
       [Buffer.from("vault_auth"), idBytes],
       program.programId
     );
+    const [evidenceLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("evidence"), idBytes],
+      program.programId
+    );
+    const [bondVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bond_vault"), idBytes],
+      program.programId
+    );
+    const [disputeCasePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_case"), idBytes],
+      program.programId
+    );
 
     const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
     const disputeAmount = 250_000_000;
 
     // Create
     await program.methods
-      .createHandshake("Dispute test", new anchor.BN(disputeAmount), new anchor.BN(deadline))
+      .createHandshake("Dispute test", new anchor.BN(disputeAmount), new anchor.BN(deadline), [], NO_OPTIONS)
       .accounts({
         config: configPda,
         handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
         vault: vaultPda,
         vaultAuthority: vaultAuthPda,
         creatorTokenAccount,
         worker: worker.publicKey,
-        sourMint,
+        mint: sourMint,
+        allowedMint: null,
         creator: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -481,7 +615,18 @@ describe("sour-handshake", () => {
     // Accept
     await program.methods
       .acceptHandshake()
-      .accounts({ handshake: handshakePda, worker: worker.publicKey })
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
       .signers([worker])
       .rpc();
 
@@ -491,7 +636,15 @@ describe("sour-handshake", () => {
       .accounts({
         config: configPda,
         handshake: handshakePda,
+        allowedMint: null,
+        evidenceLog: evidenceLogPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        signerTokenAccount: null,
         signer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([worker])
       .rpc();
@@ -499,22 +652,35 @@ describe("sour-handshake", () => {
     let handshake = await program.account.handshake.fetch(handshakePda);
     assert.deepEqual(handshake.status, { disputed: {} });
 
-    // Resolve: ruling = 0 (refund creator)
+    // Resolve: Refund ruling (0% to worker)
     const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
 
     await program.methods
-      .resolveDispute(0)
+      .resolveDispute({ refund: {} })
       .accounts({
         config: configPda,
         handshake: handshakePda,
+        disputeCase: disputeCasePda,
+        registry: null,
+        allowedMint: null,
         vault: vaultPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
         vaultAuthority: vaultAuthPda,
+        collateralVault: null,
+        subcontract: null,
+        subcontractVault: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
         creatorTokenAccount,
         workerTokenAccount,
         keepersPool,
         commonsTreasury,
         buybackTreasury,
-        authority: authority.publicKey,
+        arbiterTokenAccount: null,
+        resolver: authority.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc();
@@ -523,7 +689,7 @@ describe("sour-handshake", () => {
     assert.equal(
       Number(creatorAfter.amount) - Number(creatorBefore.amount),
       disputeAmount,
-      "Creator gets full refund on dispute ruling 0"
+      "Creator gets full refund on a Refund ruling"
     );
 
     handshake = await program.account.handshake.fetch(handshakePda);
@@ -533,7 +699,4123 @@ describe("sour-handshake", () => {
   });
 
   // =========================================================================
-  // Test 8: Validation — cannot self-handshake
+  // Test 8: Expire past deadline — anyone can crank, earns a tip
+  // =========================================================================
+  it("expires an unaccepted handshake past its deadline", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const handshakeId = config.handshakeCount;
+    const idBytes = handshakeId.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 2;
+    const expireAmount = 100_000_000;
+
+    await program.methods
+      .createHandshake("Expire test", new anchor.BN(expireAmount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    // Let the deadline pass
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    const crankerBefore = await getAccount(provider.connection, workerTokenAccount);
+
+    // Worker cranks the expiry (any signer works)
+    await program.methods
+      .expireHandshake()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        allowedMint: null,
+        vault: vaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        collateralVault: null,
+        subcontract: null,
+        commonsTreasury: null,
+        creatorTokenAccount,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        crankerTokenAccount: workerTokenAccount,
+        cranker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+
+    const crankTip = Math.floor(expireAmount * CRANK_TIP_BPS / 10_000);
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    const crankerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(
+      Number(creatorAfter.amount) - Number(creatorBefore.amount),
+      expireAmount - crankTip,
+      "Creator refunded minus crank tip"
+    );
+    assert.equal(
+      Number(crankerAfter.amount) - Number(crankerBefore.amount),
+      crankTip,
+      "Cranker receives the tip"
+    );
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { expired: {} });
+    assert.ok(handshake.resolvedAt.toNumber() > 0);
+
+    console.log(`    ✓ Expired: ${expireAmount - crankTip} refunded, ${crankTip} crank tip`);
+  });
+
+  // =========================================================================
+  // Test 9: Milestones — tranches delivered and approved one at a time
+  // =========================================================================
+  it("approves milestone tranches one at a time", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const handshakeId = config.handshakeCount;
+    const idBytes = handshakeId.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [milestonesPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("milestones"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const tranches = [300_000_000, 200_000_000];
+    const total = tranches[0] + tranches[1];
+
+    await program.methods
+      .createHandshake(
+        "Milestone test",
+        new anchor.BN(total),
+        new anchor.BN(deadline),
+        tranches.map((amount) => ({ amount: new anchor.BN(amount), deadlineTs: null })),
+        NO_OPTIONS
+      )
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: milestonesPda,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    for (let i = 0; i < tranches.length; i++) {
+      await program.methods
+        .deliver(DELIVERY_HASH, null)
+        .accounts({ handshake: handshakePda, milestones: milestonesPda, worker: worker.publicKey })
+        .signers([worker])
+        .rpc();
+
+      const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+
+      await program.methods
+        .approve(null)
+        .accounts({
+          config: configPda,
+          handshake: handshakePda,
+          milestones: milestonesPda,
+          allowedMint: null,
+          vault: vaultPda,
+          mint: sourMint,
+          vaultAuthority: vaultAuthPda,
+          workerTokenAccount,
+          workerRecord: workerRecordOf(worker.publicKey),
+          collateralVault: null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
+          subcontract: null,
+          subcontractVault: null,
+          creatorTokenAccount: null,
+          keepersPool,
+          commonsTreasury,
+          buybackTreasury,
+          creator: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const pinch = Math.floor(tranches[i] * PINCH_BPS / 10_000);
+      const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+      assert.equal(
+        Number(workerAfter.amount) - Number(workerBefore.amount),
+        tranches[i] - pinch,
+        `Worker paid tranche ${i} minus Pinch`
+      );
+
+      const handshake = await program.account.handshake.fetch(handshakePda);
+      if (i < tranches.length - 1) {
+        assert.deepEqual(handshake.status, { accepted: {} });
+        assert.equal(handshake.currentMilestone, i + 1);
+      } else {
+        assert.deepEqual(handshake.status, { approved: {} });
+      }
+    }
+
+    const vaultAfter = await getAccount(provider.connection, vaultPda);
+    assert.equal(Number(vaultAfter.amount), 0, "Vault should be empty");
+
+    console.log(`    ✓ ${tranches.length} milestones approved`);
+  });
+
+  // =========================================================================
+  // Test 10: Claim after review — silent creator, worker releases payment
+  // =========================================================================
+  it("worker claims payment after the review window", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const handshakeId = config.handshakeCount;
+    const idBytes = handshakeId.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const claimAmount = 400_000_000;
+
+    // 1-second review window override
+    await program.methods
+      .createHandshake("Claim test", new anchor.BN(claimAmount), new anchor.BN(deadline), [], { ...NO_OPTIONS, reviewWindowSecs: new anchor.BN(1) })
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: handshakePda, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    // Let the review window pass
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+
+    await program.methods
+      .claimAfterReview()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        allowedMint: null,
+        vault: vaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        workerTokenAccount,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        subcontract: null,
+        subcontractVault: null,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        claimer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const pinch = Math.floor(claimAmount * PINCH_BPS / 10_000);
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(
+      Number(workerAfter.amount) - Number(workerBefore.amount),
+      claimAmount - pinch,
+      "Worker paid escrow minus Pinch"
+    );
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { approved: {} });
+
+    console.log(`    ✓ Claimed after review: ${claimAmount - pinch} to worker`);
+  });
+
+  // =========================================================================
+  // Test 11: Allowlisted mint — USDC-style escrow with its own Pinch
+  // =========================================================================
+  it("escrows an allowlisted mint with its own Pinch rate", async () => {
+    const USDC_PINCH_BPS = 100; // 1%
+    const payer = (authority as any).payer;
+
+    const usdcMint = await createMint(provider.connection, payer, authority.publicKey, null, 6);
+    const newAccount = (owner: anchor.web3.PublicKey) =>
+      createAccount(provider.connection, payer, usdcMint, owner, anchor.web3.Keypair.generate());
+    const usdcCreator = await newAccount(authority.publicKey);
+    const usdcWorker = await newAccount(worker.publicKey);
+    const usdcKeepers = await newAccount(authority.publicKey);
+    const usdcCommons = await newAccount(authority.publicKey);
+    const usdcBuyback = await newAccount(authority.publicKey);
+    await mintTo(provider.connection, payer, usdcMint, usdcCreator, authority.publicKey, 1_000_000_000);
+
+    const [allowedMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("allowed_mint"), usdcMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .addAllowedMint(USDC_PINCH_BPS, new anchor.BN(0))
+      .accounts({
+        config: configPda,
+        allowedMint: allowedMintPda,
+        mint: usdcMint,
+        keepersPool: usdcKeepers,
+        commonsTreasury: usdcCommons,
+        buybackTreasury: usdcBuyback,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const handshakeId = config.handshakeCount;
+    const idBytes = handshakeId.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const usdcAmount = 250_000_000; // 250 USDC
+
+    await program.methods
+      .createHandshake("USDC test", new anchor.BN(usdcAmount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount: usdcCreator,
+        worker: worker.publicKey,
+        mint: usdcMint,
+        allowedMint: allowedMintPda,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: usdcMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: handshakePda, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .approve(null)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        allowedMint: allowedMintPda,
+        vault: vaultPda,
+        mint: usdcMint,
+        vaultAuthority: vaultAuthPda,
+        workerTokenAccount: usdcWorker,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        subcontract: null,
+        subcontractVault: null,
+        creatorTokenAccount: null,
+        keepersPool: usdcKeepers,
+        commonsTreasury: usdcCommons,
+        buybackTreasury: usdcBuyback,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const pinch = Math.floor(usdcAmount * USDC_PINCH_BPS / 10_000);
+    const workerAfter = await getAccount(provider.connection, usdcWorker);
+    assert.equal(Number(workerAfter.amount), usdcAmount - pinch, "Worker paid minus 1% Pinch");
+
+    const entry = await program.account.allowedMint.fetch(allowedMintPda);
+    assert.equal(
+      entry.totalToTreasury.toNumber() + entry.totalToKeepers.toNumber() + entry.totalToCommons.toNumber(),
+      pinch,
+      "Fee totals tracked on the allowlist entry"
+    );
+
+    console.log(`    ✓ USDC escrow approved with ${USDC_PINCH_BPS}bps Pinch`);
+  });
+
+  // =========================================================================
+  // Test 12: Token-2022 — transfer-fee mint escrows what actually arrived
+  // =========================================================================
+  it("escrows the received amount for a Token-2022 transfer-fee mint", async () => {
+    const TRANSFER_FEE_BPS = 100; // 1% withheld on every transfer
+    const payer = (authority as any).payer;
+
+    const feeMintKeypair = anchor.web3.Keypair.generate();
+    const feeMint = feeMintKeypair.publicKey;
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    await anchor.web3.sendAndConfirmTransaction(
+      provider.connection,
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: payer.publicKey,
+          newAccountPubkey: feeMint,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMint,
+          authority.publicKey,
+          authority.publicKey,
+          TRANSFER_FEE_BPS,
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(feeMint, 6, authority.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [payer, feeMintKeypair]
+    );
+
+    const newAccount = (owner: anchor.web3.PublicKey) =>
+      createAccount(
+        provider.connection,
+        payer,
+        feeMint,
+        owner,
+        anchor.web3.Keypair.generate(),
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+    const feeCreator = await newAccount(authority.publicKey);
+    await mintTo(
+      provider.connection,
+      payer,
+      feeMint,
+      feeCreator,
+      authority.publicKey,
+      1_000_000_000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const [allowedMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("allowed_mint"), feeMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .addAllowedMint(100, new anchor.BN(0))
+      .accounts({
+        config: configPda,
+        allowedMint: allowedMintPda,
+        mint: feeMint,
+        keepersPool: await newAccount(authority.publicKey),
+        commonsTreasury: await newAccount(authority.publicKey),
+        buybackTreasury: await newAccount(authority.publicKey),
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const sent = 100_000_000;
+
+    await program.methods
+      .createHandshake("Token-2022 test", new anchor.BN(sent), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount: feeCreator,
+        worker: worker.publicKey,
+        mint: feeMint,
+        allowedMint: allowedMintPda,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const received = sent - Math.floor(sent * TRANSFER_FEE_BPS / 10_000);
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    const vault = await getAccount(provider.connection, vaultPda, undefined, TOKEN_2022_PROGRAM_ID);
+    assert.equal(Number(vault.amount), received, "Vault holds the amount after the transfer fee");
+    assert.equal(handshake.amount.toNumber(), received, "Escrow records the received amount");
+
+    await program.methods
+      .cancel()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        vault: vaultPda,
+        mint: feeMint,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount: feeCreator,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const handshakeAfter = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshakeAfter.status, { cancelled: {} });
+
+    console.log(`    ✓ Token-2022 escrow recorded ${received} of ${sent} sent`);
+  });
+
+  // =========================================================================
+  // Test 13: update_config + two-step authority handover
+  // =========================================================================
+  it("updates config and hands authority over in two steps", async () => {
+    // Shares that no longer sum to 100% are rejected
+    try {
+      await program.methods
+        .updateConfig({ ...NO_CONFIG_CHANGE, treasuryShareBps: 6000 })
+        .accounts({
+          config: configPda,
+          keepersPool: null,
+          commonsTreasury: null,
+          buybackTreasury: null,
+          authority: authority.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidFeeShares");
+    }
+
+    await program.methods
+      .updateConfig({ ...NO_CONFIG_CHANGE, pinchBps: 300 })
+      .accounts({
+        config: configPda,
+        keepersPool: null,
+        commonsTreasury: null,
+        buybackTreasury: null,
+        authority: authority.publicKey,
+      })
+      .rpc();
+    let config = await program.account.protocolConfig.fetch(configPda);
+    assert.equal(config.pinchBps, 300);
+
+    // Restore the default Pinch for the remaining tests
+    await program.methods
+      .updateConfig({ ...NO_CONFIG_CHANGE, pinchBps: PINCH_BPS })
+      .accounts({
+        config: configPda,
+        keepersPool: null,
+        commonsTreasury: null,
+        buybackTreasury: null,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    // Step 1: propose — nothing changes yet
+    await program.methods
+      .proposeAuthority(worker.publicKey)
+      .accounts({ config: configPda, authority: authority.publicKey })
+      .rpc();
+    config = await program.account.protocolConfig.fetch(configPda);
+    assert.ok(config.authority.equals(authority.publicKey));
+    assert.ok(config.pendingAuthority.equals(worker.publicKey));
+
+    // Step 2: only the proposed key can accept
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accounts({ config: configPda, newAuthority: authority.publicKey })
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotPendingAuthority");
+    }
+
+    await program.methods
+      .acceptAuthority()
+      .accounts({ config: configPda, newAuthority: worker.publicKey })
+      .signers([worker])
+      .rpc();
+    config = await program.account.protocolConfig.fetch(configPda);
+    assert.ok(config.authority.equals(worker.publicKey));
+    assert.ok(config.pendingAuthority.equals(anchor.web3.PublicKey.default));
+
+    // Hand it back for the remaining tests
+    await program.methods
+      .proposeAuthority(authority.publicKey)
+      .accounts({ config: configPda, authority: worker.publicKey })
+      .signers([worker])
+      .rpc();
+    await program.methods
+      .acceptAuthority()
+      .accounts({ config: configPda, newAuthority: authority.publicKey })
+      .rpc();
+
+    console.log("    ✓ Config updated, authority handed over and back");
+  });
+
+  // =========================================================================
+  // Test 14: Split ruling — worker share pays Pinch, creator refunded the rest
+  // =========================================================================
+  it("resolves a dispute with a split ruling", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const [evidenceLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("evidence"), idBytes],
+      program.programId
+    );
+    const [bondVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bond_vault"), idBytes],
+      program.programId
+    );
+    const [disputeCasePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_case"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const disputeAmount = 100_000_000;
+    const WORKER_SHARE_BPS = 6000; // 60% of the work was done
+
+    await program.methods
+      .createHandshake("Split test", new anchor.BN(disputeAmount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .dispute()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        allowedMint: null,
+        evidenceLog: evidenceLogPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        signerTokenAccount: null,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+
+    await program.methods
+      .resolveDispute({ split: { workerShareBps: WORKER_SHARE_BPS } })
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        disputeCase: disputeCasePda,
+        registry: null,
+        allowedMint: null,
+        vault: vaultPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        collateralVault: null,
+        subcontract: null,
+        subcontractVault: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        arbiterTokenAccount: null,
+        resolver: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const workerGross = disputeAmount * WORKER_SHARE_BPS / 10_000;
+    const pinch = Math.floor(workerGross * PINCH_BPS / 10_000);
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(
+      Number(workerAfter.amount) - Number(workerBefore.amount),
+      workerGross - pinch,
+      "Worker gets their share minus Pinch"
+    );
+    assert.equal(
+      Number(creatorAfter.amount) - Number(creatorBefore.amount),
+      disputeAmount - workerGross,
+      "Creator refunded the rest, fee-free"
+    );
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { resolved: {} });
+    assert.equal(handshake.released.toNumber(), workerGross);
+
+    console.log(`    ✓ Split ruling: ${WORKER_SHARE_BPS / 100}% to worker`);
+  });
+
+  // =========================================================================
+  // Test 15: Per-handshake arbiter — resolves instead of the authority
+  // =========================================================================
+  it("lets the agreed arbiter resolve a dispute for its fee", async () => {
+    const payer = (authority as any).payer;
+    const arbiter = anchor.web3.Keypair.generate();
+    const arbiterTokenAccount = await createAccount(
+      provider.connection,
+      payer,
+      sourMint,
+      arbiter.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+    const ARBITER_FEE_BPS = 500; // 5%
+
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const [evidenceLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("evidence"), idBytes],
+      program.programId
+    );
+    const [bondVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bond_vault"), idBytes],
+      program.programId
+    );
+    const [disputeCasePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_case"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const disputeAmount = 100_000_000;
+
+    await program.methods
+      .createHandshake(
+        "Arbiter test",
+        new anchor.BN(disputeAmount),
+        new anchor.BN(deadline),
+        [],
+        { ...NO_OPTIONS, arbiter: { arbiter: arbiter.publicKey, feeBps: ARBITER_FEE_BPS } }
+      )
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .dispute()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        allowedMint: null,
+        evidenceLog: evidenceLogPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        signerTokenAccount: null,
+        signer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const resolveAccounts = (resolver: anchor.web3.PublicKey) => ({
+      config: configPda,
+      handshake: handshakePda,
+      disputeCase: disputeCasePda,
+      allowedMint: null,
+      vault: vaultPda,
+      bondVault: bondVaultPda,
+      mint: sourMint,
+      vaultAuthority: vaultAuthPda,
+      creatorTokenAccount,
+      creator: authority.publicKey,
+      workerTokenAccount,
+      keepersPool,
+      commonsTreasury,
+      buybackTreasury,
+      arbiterTokenAccount,
+      resolver,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    // A stranger cannot resolve
+    const stranger = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .resolveDispute({ payWorker: {} })
+        .accounts(resolveAccounts(stranger.publicKey))
+        .signers([stranger])
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotResolver");
+    }
+
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+
+    await program.methods
+      .resolveDispute({ payWorker: {} })
+      .accounts(resolveAccounts(arbiter.publicKey))
+      .signers([arbiter])
+      .rpc();
+
+    const arbiterFee = disputeAmount * ARBITER_FEE_BPS / 10_000;
+    const workerGross = disputeAmount - arbiterFee;
+    const pinch = Math.floor(workerGross * PINCH_BPS / 10_000);
+    const arbiterAfter = await getAccount(provider.connection, arbiterTokenAccount);
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(Number(arbiterAfter.amount), arbiterFee, "Arbiter fee paid off the top");
+    assert.equal(
+      Number(workerAfter.amount) - Number(workerBefore.amount),
+      workerGross - pinch,
+      "Worker paid the rest minus Pinch"
+    );
+
+    console.log(`    ✓ Arbiter resolved and earned ${arbiterFee} (${ARBITER_FEE_BPS}bps)`);
+  });
+
+  // =========================================================================
+  // Test 16: Evidence + arbiter panel — drawn from the registry, median paid
+  // =========================================================================
+  it("collects evidence, then finalizes with the panel's median ruling", async () => {
+    const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("arbiter_registry")],
+      program.programId
+    );
+    const arbiters = [0, 1, 2].map(() => anchor.web3.Keypair.generate());
+
+    await program.methods
+      .initializeArbiterRegistry(3, new anchor.BN(24 * 60 * 60))
+      .accounts({
+        config: configPda,
+        registry: registryPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    for (const arbiter of arbiters) {
+      await program.methods
+        .registerArbiter(arbiter.publicKey)
+        .accounts({ config: configPda, registry: registryPda, authority: authority.publicKey })
+        .rpc();
+    }
+
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const [evidenceLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("evidence"), idBytes],
+      program.programId
+    );
+    const [bondVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bond_vault"), idBytes],
+      program.programId
+    );
+    const [disputeCasePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_case"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const disputeAmount = 100_000_000;
+
+    await program.methods
+      .createHandshake("Panel test", new anchor.BN(disputeAmount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .dispute()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        allowedMint: null,
+        evidenceLog: evidenceLogPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        signerTokenAccount: null,
+        signer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    // Worker files evidence for the panel to review
+    const contentHash = Array.from(Buffer.alloc(32, 7));
+    await program.methods
+      .submitEvidence(contentHash, "ipfs://bafy-delivery-proof")
+      .accounts({ handshake: handshakePda, evidenceLog: evidenceLogPda, submitter: worker.publicKey })
+      .signers([worker])
+      .rpc();
+    let evidenceLog = await program.account.evidenceLog.fetch(evidenceLogPda);
+    assert.equal(evidenceLog.entries.length, 1);
+    assert.ok(evidenceLog.entries[0].submitter.equals(worker.publicKey));
+
+    await program.methods
+      .openDisputeCase()
+      .accounts({
+        registry: registryPda,
+        handshake: handshakePda,
+        disputeCase: disputeCasePda,
+        evidenceLog: evidenceLogPda,
+        payer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    let disputeCase = await program.account.disputeCase.fetch(disputeCasePda);
+    assert.equal(disputeCase.panel.length, 0);
+
+    // Once the case is open, only the panel can settle the dispute
+    try {
+      await program.methods
+        .resolveDispute({ payWorker: {} })
+        .accounts({
+          config: configPda,
+          handshake: handshakePda,
+          disputeCase: disputeCasePda,
+          registry: registryPda,
+          allowedMint: null,
+          vault: vaultPda,
+          bondVault: bondVaultPda,
+          mint: sourMint,
+          vaultAuthority: vaultAuthPda,
+          collateralVault: null,
+          subcontract: null,
+          subcontractVault: null,
+          payoutStream: null,
+          streamVault: null,
+          creator: authority.publicKey,
+          team: null,
+          creatorTokenAccount,
+          workerTokenAccount,
+          keepersPool,
+          commonsTreasury,
+          buybackTreasury,
+          arbiterTokenAccount: null,
+          resolver: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "DisputeCaseOpen");
+    }
+
+    // The panel is drawn from the block hash of a slot after opening
+    while ((await provider.connection.getSlot()) <= disputeCase.drawSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+    await program.methods
+      .drawPanel()
+      .accounts({
+        registry: registryPda,
+        handshake: handshakePda,
+        disputeCase: disputeCasePda,
+        slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .rpc();
+
+    disputeCase = await program.account.disputeCase.fetch(disputeCasePda);
+    assert.equal(disputeCase.panel.length, 3);
+
+    // Opening the case closed the evidence log
+    evidenceLog = await program.account.evidenceLog.fetch(evidenceLogPda);
+    assert.isTrue(evidenceLog.closed);
+    try {
+      await program.methods
+        .submitEvidence(contentHash, "ipfs://late")
+        .accounts({ handshake: handshakePda, evidenceLog: evidenceLogPda, submitter: authority.publicKey })
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "EvidenceClosed");
+    }
+
+    // Refund / 60% split / pay → median is the 60% split
+    const votes = [{ refund: {} }, { split: { workerShareBps: 6000 } }, { payWorker: {} }];
+    for (const [i, vote] of votes.entries()) {
+      await program.methods
+        .castVote(vote as any)
+        .accounts({ disputeCase: disputeCasePda, arbiter: arbiters[i].publicKey })
+        .signers([arbiters[i]])
+        .rpc();
+    }
+
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+
+    await program.methods
+      .finalizeDispute()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        disputeCase: disputeCasePda,
+        allowedMint: null,
+        vault: vaultPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        collateralVault: null,
+        subcontract: null,
+        subcontractVault: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const workerGross = disputeAmount * 6000 / 10_000;
+    const pinch = Math.floor(workerGross * PINCH_BPS / 10_000);
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(Number(workerAfter.amount) - Number(workerBefore.amount), workerGross - pinch);
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { resolved: {} });
+    const finalized = await program.account.disputeCase.fetch(disputeCasePda);
+    assert.isTrue(finalized.finalized);
+
+    // A case stalls when no panel can be drawn; the authority then resolves
+    // it. Handshakes with an agreed arbiter cannot be put before a panel.
+    const openDisputed = async (options: any) => {
+      const next = await program.account.protocolConfig.fetch(configPda);
+      const bytes = next.handshakeCount.toArrayLike(Buffer, "le", 8);
+      const [h, v, va, log, bond, dc] = [
+        "handshake",
+        "vault",
+        "vault_auth",
+        "evidence",
+        "bond_vault",
+        "dispute_case",
+      ].map(
+        (seed) =>
+          anchor.web3.PublicKey.findProgramAddressSync([Buffer.from(seed), bytes], program.programId)[0]
+      );
+      await program.methods
+        .createHandshake("Stalled panel test", new anchor.BN(disputeAmount), new anchor.BN(deadline), [], options)
+        .accounts({
+          config: configPda,
+          handshake: h,
+          milestones: null,
+          bountyAllowlist: null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
+          vault: v,
+          vaultAuthority: va,
+          creatorTokenAccount,
+          worker: worker.publicKey,
+          mint: sourMint,
+          allowedMint: null,
+          creator: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+      await program.methods
+        .acceptHandshake()
+        .accounts({
+          handshake: h,
+          workerRecord: null,
+          bountyAllowlist: null,
+          collateralVault: null,
+          workerTokenAccount: null,
+          mint: sourMint,
+          vaultAuthority: va,
+          worker: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+      await program.methods
+        .dispute()
+        .accounts({
+          config: configPda,
+          handshake: h,
+          allowedMint: null,
+          evidenceLog: log,
+          bondVault: bond,
+          mint: sourMint,
+          vaultAuthority: va,
+          signerTokenAccount: null,
+          signer: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+      const open = () =>
+        program.methods
+          .openDisputeCase()
+          .accounts({
+            registry: registryPda,
+            handshake: h,
+            disputeCase: dc,
+            evidenceLog: log,
+            payer: worker.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([worker])
+          .rpc();
+      return { handshake: h, vault: v, vaultAuth: va, bondVault: bond, disputeCase: dc, open };
+    };
+
+    const agreed = await openDisputed({
+      ...NO_OPTIONS,
+      arbiter: { arbiter: anchor.web3.Keypair.generate().publicKey, feeBps: 0 },
+    });
+    try {
+      await agreed.open();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "ArbiterAgreed");
+    }
+
+    const stalled = await openDisputed(NO_OPTIONS);
+    await stalled.open();
+    await program.methods
+      .removeArbiter(arbiters[2].publicKey)
+      .accounts({ config: configPda, registry: registryPda, authority: authority.publicKey })
+      .rpc();
+    const stalledCreatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .resolveDispute({ refund: {} })
+      .accounts({
+        config: configPda,
+        handshake: stalled.handshake,
+        disputeCase: stalled.disputeCase,
+        registry: registryPda,
+        allowedMint: null,
+        vault: stalled.vault,
+        bondVault: stalled.bondVault,
+        mint: sourMint,
+        vaultAuthority: stalled.vaultAuth,
+        collateralVault: null,
+        subcontract: null,
+        subcontractVault: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        arbiterTokenAccount: null,
+        resolver: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const stalledCreatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    assert.equal(Number(stalledCreatorAfter.amount) - Number(stalledCreatorBefore.amount), disputeAmount);
+    const stalledHandshake = await program.account.handshake.fetch(stalled.handshake);
+    assert.deepEqual(stalledHandshake.status, { resolved: {} });
+    await program.methods
+      .registerArbiter(arbiters[2].publicKey)
+      .accounts({ config: configPda, registry: registryPda, authority: authority.publicKey })
+      .rpc();
+
+
+    console.log("    ✓ Panel ruled a 60% split; finalize_dispute paid it out; a stalled case fell back to the authority");
+  });
+
+  // =========================================================================
+  // Test 17: Dispute bonds — winner's bond returned, loser's to Commons
+  // =========================================================================
+  it("returns the winner's dispute bond and forfeits the loser's", async () => {
+    const BOND = 5_000_000;
+    await program.methods
+      .updateConfig({ ...NO_CONFIG_CHANGE, disputeBond: new anchor.BN(BOND) })
+      .accounts({
+        config: configPda,
+        keepersPool: null,
+        commonsTreasury: null,
+        buybackTreasury: null,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const [evidenceLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("evidence"), idBytes],
+      program.programId
+    );
+    const [bondVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bond_vault"), idBytes],
+      program.programId
+    );
+    const [disputeCasePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_case"), idBytes],
+      program.programId
+    );
+
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const disputeAmount = 100_000_000;
+
+    await program.methods
+      .createHandshake("Bond test", new anchor.BN(disputeAmount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    // Worker disputes and posts the bond
+    await program.methods
+      .dispute()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        allowedMint: null,
+        evidenceLog: evidenceLogPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        signerTokenAccount: workerTokenAccount,
+        signer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    // Creator matches it
+    await program.methods
+      .matchDisputeBond()
+      .accounts({
+        handshake: handshakePda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        signerTokenAccount: creatorTokenAccount,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    let handshake = await program.account.handshake.fetch(handshakePda);
+    assert.equal(handshake.disputeBond.toNumber(), BOND);
+    assert.equal(handshake.counterBond.toNumber(), BOND);
+
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    const commonsBefore = await getAccount(provider.connection, commonsTreasury);
+
+    // Ruling favours the worker
+    await program.methods
+      .resolveDispute({ payWorker: {} })
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        disputeCase: disputeCasePda,
+        registry: null,
+        allowedMint: null,
+        vault: vaultPda,
+        bondVault: bondVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        collateralVault: null,
+        subcontract: null,
+        subcontractVault: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        arbiterTokenAccount: null,
+        resolver: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const pinch = Math.floor(disputeAmount * PINCH_BPS / 10_000);
+    const commonsCut = pinch - Math.floor(pinch * TREASURY_SHARE / 10_000) - Math.floor(pinch * KEEPERS_SHARE / 10_000);
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    const commonsAfter = await getAccount(provider.connection, commonsTreasury);
+    assert.equal(
+      Number(workerAfter.amount) - Number(workerBefore.amount),
+      disputeAmount - pinch + BOND,
+      "Worker paid and bond returned"
+    );
+    assert.equal(
+      Number(commonsAfter.amount) - Number(commonsBefore.amount),
+      commonsCut + BOND,
+      "Creator's matched bond forfeited to Commons"
+    );
+
+    // Disputes are free again for the remaining tests
+    await program.methods
+      .updateConfig({ ...NO_CONFIG_CHANGE, disputeBond: new anchor.BN(0) })
+      .accounts({
+        config: configPda,
+        keepersPool: null,
+        commonsTreasury: null,
+        buybackTreasury: null,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    console.log(`    ✓ Bonds settled: ${BOND} returned, ${BOND} forfeited`);
+  });
+
+  // =========================================================================
+  // Test 18: Open bounty — first eligible signer becomes the worker
+  // =========================================================================
+  it("lets an allowlisted worker claim an open bounty", async () => {
+    const outsider = anchor.web3.Keypair.generate();
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+
+    const outsiderRecordPda = workerRecordOf(outsider.publicKey);
+    await program.methods
+      .initializeWorkerRecord()
+      .accounts({
+        workerRecord: outsiderRecordPda,
+        worker: outsider.publicKey,
+        payer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Posts a bounty with the given eligibility, returning its PDAs
+    const postBounty = async (description: string, bounty: any) => {
+      const config = await program.account.protocolConfig.fetch(configPda);
+      const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+      const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("handshake"), idBytes],
+        program.programId
+      );
+      const [allowlistPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("bounty_allowlist"), idBytes],
+        program.programId
+      );
+      const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), idBytes],
+        program.programId
+      );
+      const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_auth"), idBytes],
+        program.programId
+      );
+
+      await program.methods
+        .createHandshake(description, new anchor.BN(10_000_000), new anchor.BN(deadline), [], {
+          ...NO_OPTIONS,
+          bounty,
+        })
+        .accounts({
+          config: configPda,
+          handshake: handshakePda,
+          milestones: null,
+          bountyAllowlist: bounty.allowlist.length > 0 ? allowlistPda : null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthPda,
+          creatorTokenAccount,
+          worker: null, // open bounty
+          mint: sourMint,
+          allowedMint: null,
+          creator: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+      return { handshakePda, allowlistPda, vaultAuthPda };
+    };
+
+    // Allowlisted bounty: an outsider is turned away, the worker takes it
+    const open = await postBounty("Mini Bake: translate docs", {
+      minCompleted: 0,
+      allowlist: [worker.publicKey],
+      byApplication: false,
+    });
+    let handshake = await program.account.handshake.fetch(open.handshakePda);
+    assert.ok(handshake.worker.equals(anchor.web3.PublicKey.default));
+    assert.equal(handshake.allowlisted, true);
+
+    try {
+      await program.methods
+        .acceptHandshake()
+        .accounts({
+          handshake: open.handshakePda,
+          workerRecord: null,
+          bountyAllowlist: open.allowlistPda,
+          collateralVault: null,
+          workerTokenAccount: null,
+          mint: sourMint,
+          vaultAuthority: open.vaultAuthPda,
+          worker: outsider.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+      assert.fail("Should have thrown NotAllowlisted error");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotAllowlisted");
+    }
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: open.handshakePda,
+        workerRecord: null,
+        bountyAllowlist: open.allowlistPda,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: open.vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    handshake = await program.account.handshake.fetch(open.handshakePda);
+    assert.ok(handshake.worker.equals(worker.publicKey));
+    assert.deepEqual(handshake.status, { accepted: {} });
+
+    // Track-record bounty: a fresh record doesn't meet the minimum
+    const seasoned = await postBounty("Golden Bake: audit", {
+      minCompleted: 1,
+      allowlist: [],
+      byApplication: false,
+    });
+    try {
+      await program.methods
+        .acceptHandshake()
+        .accounts({
+          handshake: seasoned.handshakePda,
+          workerRecord: outsiderRecordPda,
+          bountyAllowlist: null,
+          collateralVault: null,
+          workerTokenAccount: null,
+          mint: sourMint,
+          vaultAuthority: seasoned.vaultAuthPda,
+          worker: outsider.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+      assert.fail("Should have thrown InsufficientTrackRecord error");
+    } catch (err: any) {
+      assert.include(err.toString(), "InsufficientTrackRecord");
+    }
+
+    console.log("    ✓ Bounty claimed by allowlisted worker; track-record minimum enforced");
+  });
+
+  // =========================================================================
+  // Test 19: Applications — creator selects a bid, escrow topped up to match
+  // =========================================================================
+  it("hires an applicant and matches the escrow to their bid", async () => {
+    const rival = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: rival.publicKey,
+          lamports: anchor.web3.LAMPORTS_PER_SOL / 10,
+        })
+      )
+    );
+
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const applicationPda = (applicant: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("application"), idBytes, applicant.toBuffer()],
+        program.programId
+      )[0];
+
+    const posted = 10_000_000;
+    const bid = 12_000_000;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+
+    await program.methods
+      .createHandshake("Standard Bake: landing page", new anchor.BN(posted), new anchor.BN(deadline), [], {
+        ...NO_OPTIONS,
+        bounty: { minCompleted: 0, allowlist: [], byApplication: true },
+      })
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: null,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    // Two bids: the worker asks for more and more time, the rival undercuts
+    for (const [applicant, amount] of [
+      [worker, bid],
+      [rival, 8_000_000],
+    ] as [anchor.web3.Keypair, number][]) {
+      await program.methods
+        .apply(new anchor.BN(amount), new anchor.BN(deadline + 86400), Array(32).fill(7))
+        .accounts({
+          handshake: handshakePda,
+          application: applicationPda(applicant.publicKey),
+          workerRecord: null,
+          bountyAllowlist: null,
+          applicantTokenAccount: null,
+          mint: sourMint,
+          vaultAuthority: vaultAuthPda,
+          applicant: applicant.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([applicant])
+        .rpc();
+    }
+
+    // First-come acceptance is disabled for application bounties
+    try {
+      await program.methods
+        .acceptHandshake()
+        .accounts({
+          handshake: handshakePda,
+          workerRecord: null,
+          bountyAllowlist: null,
+          collateralVault: null,
+          workerTokenAccount: null,
+          mint: sourMint,
+          vaultAuthority: vaultAuthPda,
+          worker: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+      assert.fail("Should have thrown ApplicationRequired error");
+    } catch (err: any) {
+      assert.include(err.toString(), "ApplicationRequired");
+    }
+
+    const selectAccounts = {
+      handshake: handshakePda,
+      application: applicationPda(worker.publicKey),
+      milestones: null,
+      vault: vaultPda,
+      mint: sourMint,
+      vaultAuthority: vaultAuthPda,
+      collateralVault: null,
+      applicantTokenAccount: null,
+      creatorTokenAccount,
+      creator: authority.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // The creator pins the bid they reviewed — a swapped bid is rejected
+    try {
+      await program.methods
+        .selectApplication(new anchor.BN(bid + 1), new anchor.BN(deadline + 86400))
+        .accounts(selectAccounts)
+        .rpc();
+      assert.fail("Should have thrown BidMismatch error");
+    } catch (err: any) {
+      assert.include(err.toString(), "BidMismatch");
+    }
+
+    await program.methods
+      .selectApplication(new anchor.BN(bid), new anchor.BN(deadline + 86400))
+      .accounts(selectAccounts)
+      .rpc();
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.ok(handshake.worker.equals(worker.publicKey));
+    assert.deepEqual(handshake.status, { accepted: {} });
+    assert.equal(handshake.amount.toNumber(), bid);
+    assert.equal(handshake.deadlineTs.toNumber(), deadline + 86400);
+    const vault = await getAccount(provider.connection, vaultPda);
+    assert.equal(Number(vault.amount), bid, "Escrow topped up to the bid");
+
+    // The losing applicant reclaims their rent
+    await program.methods
+      .closeApplication()
+      .accounts({
+        application: applicationPda(rival.publicKey),
+        handshake: handshakePda,
+        applicantTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        applicant: rival.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([rival])
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(applicationPda(rival.publicKey)));
+
+    console.log(`    ✓ Bid selected, escrow ${posted} → ${bid}, losing application closed`);
+  });
+
+  // =========================================================================
+  // Test 20: Amendments — escrow raised and deadline extended, then trimmed
+  // =========================================================================
+  it("amends an accepted handshake by mutual agreement", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const [amendmentPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("amendment"), idBytes],
+      program.programId
+    );
+
+    const original = 50_000_000;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const extended = deadline + 7 * 24 * 60 * 60;
+
+    await program.methods
+      .createHandshake("Amendment test", new anchor.BN(original), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const acceptAccounts = (signer: anchor.web3.PublicKey, proposer: anchor.web3.PublicKey) => ({
+      config: configPda,
+      handshake: handshakePda,
+      amendment: amendmentPda,
+      milestones: null,
+      allowedMint: null,
+      vault: vaultPda,
+      mint: sourMint,
+      vaultAuthority: vaultAuthPda,
+      creatorTokenAccount,
+      proposer,
+      signer,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    // Creator widens the scope: more escrow, more time. The worker accepts
+    // and the top-up is pulled through the creator's delegation.
+    await program.methods
+      .proposeAmendment(new anchor.BN(extended), new anchor.BN(60_000_000))
+      .accounts({
+        handshake: handshakePda,
+        amendment: amendmentPda,
+        creatorTokenAccount,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        proposer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .acceptAmendment()
+        .accounts(acceptAccounts(authority.publicKey, authority.publicKey))
+        .rpc();
+      assert.fail("Should have thrown NotCounterparty error");
+    } catch (err: any) {
+      assert.include(err.toString(), "NotCounterparty");
+    }
+
+    await program.methods
+      .acceptAmendment()
+      .accounts(acceptAccounts(worker.publicKey, authority.publicKey))
+      .signers([worker])
+      .rpc();
+
+    let handshake = await program.account.handshake.fetch(handshakePda);
+    assert.equal(handshake.amount.toNumber(), 60_000_000);
+    assert.equal(handshake.deadlineTs.toNumber(), extended);
+    let vault = await getAccount(provider.connection, vaultPda);
+    assert.equal(Number(vault.amount), 60_000_000, "Top-up pulled into the vault");
+    assert.isNull(await provider.connection.getAccountInfo(amendmentPda));
+
+    // Worker trims it back; the creator accepts and is refunded
+    await program.methods
+      .proposeAmendment(null, new anchor.BN(55_000_000))
+      .accounts({
+        handshake: handshakePda,
+        amendment: amendmentPda,
+        creatorTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        proposer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .acceptAmendment()
+      .accounts(acceptAccounts(authority.publicKey, worker.publicKey))
+      .rpc();
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+
+    handshake = await program.account.handshake.fetch(handshakePda);
+    assert.equal(handshake.amount.toNumber(), 55_000_000);
+    assert.equal(handshake.deadlineTs.toNumber(), extended, "Deadline unchanged");
+    assert.equal(Number(creatorAfter.amount) - Number(creatorBefore.amount), 5_000_000);
+    vault = await getAccount(provider.connection, vaultPda);
+    assert.equal(Number(vault.amount), 55_000_000);
+
+    console.log("    ✓ Amended 50M → 60M (+7 days) → 55M");
+  });
+
+  // =========================================================================
+  // Test 21: Mutual cancel — negotiated split, Pinch only on the worker share
+  // =========================================================================
+  it("cancels an accepted handshake by mutual agreement", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const [proposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("cancel_proposal"), idBytes],
+      program.programId
+    );
+
+    const cancelAmount = 40_000_000;
+    const WORKER_SHARE_BPS = 2500; // 25% for work done so far
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+
+    await program.methods
+      .createHandshake("Mutual cancel test", new anchor.BN(cancelAmount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    // Worker proposes walking away with a quarter
+    await program.methods
+      .proposeMutualCancel(WORKER_SHARE_BPS)
+      .accounts({
+        handshake: handshakePda,
+        cancelProposal: proposalPda,
+        proposer: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const statsBefore = await program.account.protocolConfig.fetch(configPda);
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+
+    // Creator co-signs
+    await program.methods
+      .acceptMutualCancel()
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        cancelProposal: proposalPda,
+        allowedMint: null,
+        vault: vaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        collateralVault: null,
+        subcontract: null,
+        subcontractVault: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        proposer: worker.publicKey,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const workerGross = Math.floor(cancelAmount * WORKER_SHARE_BPS / 10_000);
+    const pinch = Math.floor(workerGross * PINCH_BPS / 10_000);
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(Number(workerAfter.amount) - Number(workerBefore.amount), workerGross - pinch);
+    assert.equal(
+      Number(creatorAfter.amount) - Number(creatorBefore.amount),
+      cancelAmount - workerGross,
+      "Creator refund is fee-free"
+    );
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { cancelled: {} });
+    const statsAfter = await program.account.protocolConfig.fetch(configPda);
+    assert.equal(statsAfter.totalDisputed.toNumber(), statsBefore.totalDisputed.toNumber());
+    assert.isNull(await provider.connection.getAccountInfo(proposalPda));
+
+    console.log(`    ✓ Mutually cancelled: ${workerGross - pinch} to worker, ${cancelAmount - workerGross} refunded`);
+  });
+
+  // =========================================================================
+  // Test 22: Worker declines, or withdraws after accepting (logged on record)
+  // =========================================================================
+  it("lets the worker decline or withdraw, refunding the creator", async () => {
+    const amount = 20_000_000;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const [workerRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("worker_record"), worker.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Creates a handshake for `worker`, returning its PDAs
+    const createFor = async (description: string) => {
+      const config = await program.account.protocolConfig.fetch(configPda);
+      const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+      const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("handshake"), idBytes],
+        program.programId
+      );
+      const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), idBytes],
+        program.programId
+      );
+      const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_auth"), idBytes],
+        program.programId
+      );
+
+      await program.methods
+        .createHandshake(description, new anchor.BN(amount), new anchor.BN(deadline), [], NO_OPTIONS)
+        .accounts({
+          config: configPda,
+          handshake: handshakePda,
+          milestones: null,
+          bountyAllowlist: null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthPda,
+          creatorTokenAccount,
+          worker: worker.publicKey,
+          mint: sourMint,
+          allowedMint: null,
+          creator: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+      return { handshakePda, vaultPda, vaultAuthPda };
+    };
+
+    // Decline before accepting
+    const declined = await createFor("Decline test");
+    let creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .declineHandshake()
+      .accounts({
+        handshake: declined.handshakePda,
+        vault: declined.vaultPda,
+        mint: sourMint,
+        vaultAuthority: declined.vaultAuthPda,
+        creatorTokenAccount,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+    let creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    assert.equal(Number(creatorAfter.amount) - Number(creatorBefore.amount), amount);
+    let handshake = await program.account.handshake.fetch(declined.handshakePda);
+    assert.deepEqual(handshake.status, { cancelled: {} });
+
+    // Withdraw after accepting
+    const withdrawn = await createFor("Withdraw test");
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: withdrawn.handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: withdrawn.vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const recordBefore = await program.account.workerRecord.fetch(workerRecordPda);
+    creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .withdrawFromHandshake()
+      .accounts({
+        config: configPda,
+        handshake: withdrawn.handshakePda,
+        workerRecord: workerRecordPda,
+        allowedMint: null,
+        vault: withdrawn.vaultPda,
+        mint: sourMint,
+        vaultAuthority: withdrawn.vaultAuthPda,
+        creatorTokenAccount,
+        collateralVault: null,
+        subcontract: null,
+        commonsTreasury: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+    creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    assert.equal(Number(creatorAfter.amount) - Number(creatorBefore.amount), amount);
+
+    const recordAfter = await program.account.workerRecord.fetch(workerRecordPda);
+    assert.equal(recordAfter.withdrawals, recordBefore.withdrawals + 1);
+    handshake = await program.account.handshake.fetch(withdrawn.handshakePda);
+    assert.deepEqual(handshake.status, { cancelled: {} });
+
+    console.log("    ✓ Declined and withdrawn handshakes refunded; withdrawal logged");
+  });
+
+  // =========================================================================
+  // Test 23: Worker collateral — returned on approval, forfeited on withdraw
+  // =========================================================================
+  it("locks worker collateral, returning it on approval and forfeiting it on withdraw", async () => {
+    const amount = 20_000_000;
+    const collateral = 5_000_000;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const [workerRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("worker_record"), worker.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Creates a collateral-backed handshake for `worker` and accepts it
+    const createAndAccept = async (description: string) => {
+      const config = await program.account.protocolConfig.fetch(configPda);
+      const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+      const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("handshake"), idBytes],
+        program.programId
+      );
+      const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), idBytes],
+        program.programId
+      );
+      const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_auth"), idBytes],
+        program.programId
+      );
+      const [collateralVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral_vault"), idBytes],
+        program.programId
+      );
+
+      await program.methods
+        .createHandshake(description, new anchor.BN(amount), new anchor.BN(deadline), [], {
+          ...NO_OPTIONS,
+          workerCollateral: new anchor.BN(collateral),
+        })
+        .accounts({
+          config: configPda,
+          handshake: handshakePda,
+          milestones: null,
+          bountyAllowlist: null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthPda,
+          creatorTokenAccount,
+          worker: worker.publicKey,
+          mint: sourMint,
+          allowedMint: null,
+          creator: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+      // Accepting without the collateral vault is rejected
+      try {
+        await program.methods
+          .acceptHandshake()
+          .accounts({
+            handshake: handshakePda,
+            workerRecord: null,
+            bountyAllowlist: null,
+            collateralVault: null,
+            workerTokenAccount: null,
+            mint: sourMint,
+            vaultAuthority: vaultAuthPda,
+            worker: worker.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([worker])
+          .rpc();
+        assert.fail("Should have thrown CollateralVaultMismatch error");
+      } catch (err: any) {
+        assert.include(err.toString(), "CollateralVaultMismatch");
+      }
+
+      const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+      await program.methods
+        .acceptHandshake()
+        .accounts({
+          handshake: handshakePda,
+          workerRecord: null,
+          bountyAllowlist: null,
+          collateralVault: collateralVaultPda,
+          workerTokenAccount,
+          mint: sourMint,
+          vaultAuthority: vaultAuthPda,
+          worker: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+      const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+      assert.equal(Number(workerBefore.amount) - Number(workerAfter.amount), collateral);
+
+      const handshake = await program.account.handshake.fetch(handshakePda);
+      assert.equal(handshake.collateralHeld.toNumber(), collateral);
+
+      return { handshakePda, vaultPda, vaultAuthPda, collateralVaultPda };
+    };
+
+    // Delivered and approved: the collateral comes back with the payout
+    const approved = await createAndAccept("Collateral approve test");
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: approved.handshakePda, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    let workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    await program.methods
+      .approve(null)
+      .accounts({
+        config: configPda,
+        handshake: approved.handshakePda,
+        milestones: null,
+        allowedMint: null,
+        vault: approved.vaultPda,
+        mint: sourMint,
+        vaultAuthority: approved.vaultAuthPda,
+        workerTokenAccount,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: approved.collateralVaultPda,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        subcontract: null,
+        subcontractVault: null,
+        creatorTokenAccount: null,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    let workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    const pinch = Math.floor(amount * PINCH_BPS / 10_000);
+    assert.equal(
+      Number(workerAfter.amount) - Number(workerBefore.amount),
+      amount - pinch + collateral
+    );
+    let handshake = await program.account.handshake.fetch(approved.handshakePda);
+    assert.equal(handshake.collateralHeld.toNumber(), 0);
+
+    // Withdrawn after accepting: the collateral goes to the creator
+    const withdrawn = await createAndAccept("Collateral withdraw test");
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .withdrawFromHandshake()
+      .accounts({
+        config: configPda,
+        handshake: withdrawn.handshakePda,
+        workerRecord: workerRecordPda,
+        allowedMint: null,
+        vault: withdrawn.vaultPda,
+        mint: sourMint,
+        vaultAuthority: withdrawn.vaultAuthPda,
+        creatorTokenAccount,
+        collateralVault: withdrawn.collateralVaultPda,
+        subcontract: null,
+        commonsTreasury: null, // no Commons slice configured
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    assert.equal(
+      Number(creatorAfter.amount) - Number(creatorBefore.amount),
+      amount + collateral
+    );
+    assert.isNull(
+      await provider.connection.getAccountInfo(withdrawn.collateralVaultPda),
+      "Collateral vault closed to the creator"
+    );
+
+    console.log("    ✓ Collateral returned on approval, forfeited to creator on withdraw");
+  });
+
+  // =========================================================================
+  // Test 24: Revision requests — delivery sent back, then limit enforced
+  // =========================================================================
+  it("lets the creator request revisions up to max_revisions", async () => {
+    const amount = 20_000_000;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    await program.methods
+      .createHandshake("Revision test", new anchor.BN(amount), new anchor.BN(deadline), [], {
+        ...NO_OPTIONS,
+        maxRevisions: 1,
+      })
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const deliver = () =>
+      program.methods
+        .deliver(DELIVERY_HASH, null)
+        .accounts({ handshake: handshakePda, milestones: null, worker: worker.publicKey })
+        .signers([worker])
+        .rpc();
+
+    // First revision: back to Accepted, deadline pushed out a day
+    await deliver();
+    const feedbackHash = Array(32).fill(9);
+    await program.methods
+      .requestRevision(feedbackHash, new anchor.BN(deadline + 86400))
+      .accounts({ handshake: handshakePda, milestones: null, creator: authority.publicKey })
+      .rpc();
+
+    let handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { accepted: {} });
+    assert.equal(handshake.revisionCount, 1);
+    assert.deepEqual(handshake.feedbackHash, feedbackHash);
+    assert.equal(handshake.deadlineTs.toNumber(), deadline + 86400);
+    assert.equal(handshake.deliveredAt.toNumber(), 0);
+
+    // Limit reached: the next rejection must be a dispute
+    await deliver();
+    try {
+      await program.methods
+        .requestRevision(feedbackHash, null)
+        .accounts({ handshake: handshakePda, milestones: null, creator: authority.publicKey })
+        .rpc();
+      assert.fail("Should have thrown RevisionsExhausted error");
+    } catch (err: any) {
+      assert.include(err.toString(), "RevisionsExhausted");
+    }
+
+    await program.methods
+      .approve(null)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        allowedMint: null,
+        vault: vaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        workerTokenAccount,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        subcontract: null,
+        subcontractVault: null,
+        creatorTokenAccount: null,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { approved: {} });
+
+    console.log("    ✓ Revision requested once, limit enforced, reworked delivery approved");
+  });
+
+  // =========================================================================
+  // Test 25: Vested payout — Pinch up front, worker share streamed
+  // =========================================================================
+  it("streams a vested payout and lets the worker withdraw it", async () => {
+    const amount = 20_000_000;
+    const vestingSecs = 3;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+    const [payoutStreamPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_stream"), idBytes],
+      program.programId
+    );
+    const [streamVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("stream_vault"), idBytes],
+      program.programId
+    );
+
+    await program.methods
+      .createHandshake("Vesting test", new anchor.BN(amount), new anchor.BN(deadline), [], {
+        ...NO_OPTIONS,
+        vesting: { durationSecs: new anchor.BN(vestingSecs), cliffSecs: new anchor.BN(0) },
+      })
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: payoutStreamPda,
+        streamVault: streamVaultPda,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: handshakePda, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    // Approval takes the Pinch and parks the worker share in the stream
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    const commonsBefore = await getAccount(provider.connection, commonsTreasury);
+    await program.methods
+      .approve(null)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        allowedMint: null,
+        vault: vaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        workerTokenAccount,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: null,
+        payoutStream: payoutStreamPda,
+        streamVault: streamVaultPda,
+        team: null,
+        subcontract: null,
+        subcontractVault: null,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const pinch = Math.floor(amount * PINCH_BPS / 10_000);
+    const workerShare = amount - pinch;
+    let workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    const commonsAfter = await getAccount(provider.connection, commonsTreasury);
+    assert.equal(Number(workerAfter.amount), Number(workerBefore.amount));
+    assert.ok(Number(commonsAfter.amount) > Number(commonsBefore.amount));
+    const streamVault = await getAccount(provider.connection, streamVaultPda);
+    assert.equal(Number(streamVault.amount), workerShare);
+
+    let stream = await program.account.payoutStream.fetch(payoutStreamPda);
+    assert.equal(stream.total.toNumber(), workerShare);
+    assert.ok(stream.worker.equals(worker.publicKey));
+
+    // After the vesting period the whole share is withdrawable
+    await new Promise((resolve) => setTimeout(resolve, (vestingSecs + 1) * 1000));
+    await program.methods
+      .withdrawVested()
+      .accounts({
+        handshake: handshakePda,
+        payoutStream: payoutStreamPda,
+        streamVault: streamVaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        workerTokenAccount,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+
+    workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(Number(workerAfter.amount) - Number(workerBefore.amount), workerShare);
+    stream = await program.account.payoutStream.fetch(payoutStreamPda);
+    assert.equal(stream.withdrawn.toNumber(), workerShare);
+
+    // Nothing left to withdraw
+    try {
+      await program.methods
+        .withdrawVested()
+        .accounts({
+          handshake: handshakePda,
+          payoutStream: payoutStreamPda,
+          streamVault: streamVaultPda,
+          mint: sourMint,
+          vaultAuthority: vaultAuthPda,
+          workerTokenAccount,
+          worker: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([worker])
+        .rpc();
+      assert.fail("Should have thrown NothingVested error");
+    } catch (err: any) {
+      assert.include(err.toString(), "NothingVested");
+    }
+
+    // Cancelling a vested handshake closes its stream accounts to the creator
+    const nextConfig = await program.account.protocolConfig.fetch(configPda);
+    const cancelIdBytes = nextConfig.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [cancelHandshakePda, cancelVaultPda, cancelVaultAuthPda, cancelStreamPda, cancelStreamVaultPda] =
+      ["handshake", "vault", "vault_auth", "payout_stream", "stream_vault"].map(
+        (prefix) =>
+          anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from(prefix), cancelIdBytes],
+            program.programId
+          )[0]
+      );
+    await program.methods
+      .createHandshake("Vesting cancel", new anchor.BN(amount), new anchor.BN(deadline), [], {
+        ...NO_OPTIONS,
+        vesting: { durationSecs: new anchor.BN(vestingSecs), cliffSecs: new anchor.BN(0) },
+      })
+      .accounts({
+        config: configPda,
+        handshake: cancelHandshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: cancelStreamPda,
+        streamVault: cancelStreamVaultPda,
+        team: null,
+        vault: cancelVaultPda,
+        vaultAuthority: cancelVaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .cancel()
+      .accounts({
+        config: configPda,
+        handshake: cancelHandshakePda,
+        vault: cancelVaultPda,
+        mint: sourMint,
+        vaultAuthority: cancelVaultAuthPda,
+        creatorTokenAccount,
+        payoutStream: cancelStreamPda,
+        streamVault: cancelStreamVaultPda,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(cancelStreamPda));
+    assert.isNull(await provider.connection.getAccountInfo(cancelStreamVaultPda));
+
+    // A mutual cancel never starts the stream: the worker's share is paid
+    // directly and the stream accounts are closed to the creator
+    const walkConfig = await program.account.protocolConfig.fetch(configPda);
+    const walkIdBytes = walkConfig.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [walkHandshakePda, walkVaultPda, walkVaultAuthPda, walkStreamPda, walkStreamVaultPda, walkProposalPda] =
+      ["handshake", "vault", "vault_auth", "payout_stream", "stream_vault", "cancel_proposal"].map(
+        (prefix) =>
+          anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from(prefix), walkIdBytes],
+            program.programId
+          )[0]
+      );
+    await program.methods
+      .createHandshake("Vesting walk-away", new anchor.BN(amount), new anchor.BN(deadline), [], {
+        ...NO_OPTIONS,
+        vesting: { durationSecs: new anchor.BN(vestingSecs), cliffSecs: new anchor.BN(0) },
+      })
+      .accounts({
+        config: configPda,
+        handshake: walkHandshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: walkStreamPda,
+        streamVault: walkStreamVaultPda,
+        team: null,
+        vault: walkVaultPda,
+        vaultAuthority: walkVaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: walkHandshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: walkVaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+    await program.methods
+      .proposeMutualCancel(10_000)
+      .accounts({
+        handshake: walkHandshakePda,
+        cancelProposal: walkProposalPda,
+        proposer: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+    const walkWorkerBefore = await getAccount(provider.connection, workerTokenAccount);
+    await program.methods
+      .acceptMutualCancel()
+      .accounts({
+        config: configPda,
+        handshake: walkHandshakePda,
+        cancelProposal: walkProposalPda,
+        allowedMint: null,
+        vault: walkVaultPda,
+        mint: sourMint,
+        vaultAuthority: walkVaultAuthPda,
+        collateralVault: null,
+        subcontract: null,
+        subcontractVault: null,
+        payoutStream: walkStreamPda,
+        streamVault: walkStreamVaultPda,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        proposer: worker.publicKey,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const walkWorkerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(
+      Number(walkWorkerAfter.amount) - Number(walkWorkerBefore.amount),
+      amount - Math.floor(amount * PINCH_BPS / 10_000),
+      "Worker share paid directly"
+    );
+    assert.isNull(await provider.connection.getAccountInfo(walkStreamPda));
+    assert.isNull(await provider.connection.getAccountInfo(walkStreamVaultPda));
+
+    console.log(`    ✓ Pinch taken at approval; ${workerShare} vested and withdrawn`);
+  });
+
+  // =========================================================================
+  // Test 26: Recurring handshake — period paid, renewal stopped, refund
+  // =========================================================================
+  it("runs a recurring handshake period by period until renewal stops", async () => {
+    const perPeriod = 5_000_000;
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.recurringCount.toArrayLike(Buffer, "le", 8);
+    const [recurringPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("recurring"), idBytes],
+      program.programId
+    );
+    const [recurringVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("recurring_vault"), idBytes],
+      program.programId
+    );
+    const [recurringAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("recurring_auth"), idBytes],
+      program.programId
+    );
+
+    // Monthly-style retainer: 3 periods of an hour, 2 funded up front
+    await program.methods
+      .createRecurring("Community moderation", new anchor.BN(perPeriod), new anchor.BN(3600), 3, 2)
+      .accounts({
+        config: configPda,
+        recurring: recurringPda,
+        vault: recurringVaultPda,
+        vaultAuthority: recurringAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    let vault = await getAccount(provider.connection, recurringVaultPda);
+    assert.equal(Number(vault.amount), 2 * perPeriod);
+
+    await program.methods
+      .acceptRecurring()
+      .accounts({ recurring: recurringPda, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    // Period 0: delivered and approved by the creator
+    await program.methods
+      .deliverPeriod(DELIVERY_HASH)
+      .accounts({ recurring: recurringPda, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+    const delivered = await program.account.recurringHandshake.fetch(recurringPda);
+    assert.equal(delivered.periods.length, 2);
+    assert.equal(delivered.periods[0].escrowed.toNumber(), perPeriod);
+    assert.isAbove(delivered.periods[0].deliveredAt.toNumber(), 0);
+    assert.equal(delivered.periods[1].deliveredAt.toNumber(), 0);
+
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    await program.methods
+      .settlePeriod()
+      .accounts({
+        config: configPda,
+        recurring: recurringPda,
+        allowedMint: null,
+        vault: recurringVaultPda,
+        mint: sourMint,
+        vaultAuthority: recurringAuthPda,
+        workerTokenAccount,
+        creatorTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        settler: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    const pinch = Math.floor(perPeriod * PINCH_BPS / 10_000);
+    assert.equal(Number(workerAfter.amount) - Number(workerBefore.amount), perPeriod - pinch);
+
+    // Top up the last period, then the worker stops renewal during period 1
+    await program.methods
+      .fundRecurring(1)
+      .accounts({
+        recurring: recurringPda,
+        vault: recurringVaultPda,
+        mint: sourMint,
+        creatorTokenAccount,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .stopRecurring()
+      .accounts({
+        recurring: recurringPda,
+        vault: recurringVaultPda,
+        mint: sourMint,
+        vaultAuthority: recurringAuthPda,
+        creatorTokenAccount,
+        signer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+
+    // Period 0 is still running, so the run ends with it: both later
+    // periods are refunded and the retainer is complete
+    assert.equal(Number(creatorAfter.amount) - Number(creatorBefore.amount), 2 * perPeriod);
+    const recurring = await program.account.recurringHandshake.fetch(recurringPda);
+    assert.equal(recurring.endPeriods, 1);
+    assert.equal(recurring.settledPeriods, 1);
+    assert.deepEqual(recurring.status, { completed: {} });
+    vault = await getAccount(provider.connection, recurringVaultPda);
+    assert.equal(Number(vault.amount), 0);
+
+    console.log("    ✓ Period paid with Pinch; renewal stopped, later periods refunded");
+  });
+
+  // =========================================================================
+  // Test 27: Approval bonus — paid from the creator, own Pinch rate
+  // =========================================================================
+  it("pays an approval bonus from the creator at the bonus Pinch rate", async () => {
+    const amount = 10_000_000;
+    const bonus = 2_000_000;
+    const bonusPinchBps = 500;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    const setBonusPinch = (bps: number) =>
+      program.methods
+        .updateConfig({ ...NO_CONFIG_CHANGE, bonusPinchBps: bps })
+        .accounts({
+          config: configPda,
+          keepersPool: null,
+          commonsTreasury: null,
+          buybackTreasury: null,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+    // Bonus Pinch above the cap is rejected
+    try {
+      await setBonusPinch(2000);
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidBonusPinch");
+    }
+    await setBonusPinch(bonusPinchBps);
+
+    await program.methods
+      .createHandshake("Bonus test", new anchor.BN(amount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: handshakePda, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    const approveAccounts = {
+      config: configPda,
+      handshake: handshakePda,
+      milestones: null,
+      allowedMint: null,
+      vault: vaultPda,
+      mint: sourMint,
+      vaultAuthority: vaultAuthPda,
+      workerTokenAccount,
+      workerRecord: workerRecordOf(worker.publicKey),
+      collateralVault: null,
+      payoutStream: null,
+      streamVault: null,
+      team: null,
+      subcontract: null,
+      subcontractVault: null,
+      creatorTokenAccount: null,
+      keepersPool,
+      commonsTreasury,
+      buybackTreasury,
+      creator: authority.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // A bonus needs the creator's token account to pay from
+    try {
+      await program.methods.approve(new anchor.BN(bonus)).accounts(approveAccounts).rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "BonusAccountRequired");
+    }
+
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .approve(new anchor.BN(bonus))
+      .accounts({ ...approveAccounts, creatorTokenAccount })
+      .rpc();
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+
+    // Escrow release pays the regular Pinch, the bonus only the bonus rate
+    const pinch = Math.floor(amount * PINCH_BPS / 10_000);
+    const bonusPinch = Math.floor(bonus * bonusPinchBps / 10_000);
+    assert.equal(
+      Number(workerAfter.amount) - Number(workerBefore.amount),
+      amount - pinch + bonus - bonusPinch
+    );
+    assert.equal(Number(creatorBefore.amount) - Number(creatorAfter.amount), bonus);
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { approved: {} });
+
+    // Back to feeless bonuses for the remaining tests
+    await setBonusPinch(0);
+
+    console.log(`    ✓ Bonus ${bonus} paid with ${bonusPinch} bonus Pinch`);
+  });
+
+  // =========================================================================
+  // Test 28: Invoices — worker bills, client funds straight into Accepted
+  // =========================================================================
+  it("turns a worker's invoice into an accepted handshake when funded", async () => {
+    const amount = 15_000_000;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const invoicePda = (id: anchor.BN) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("invoice"), id.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    const createInvoice = async (description: string) => {
+      const config = await program.account.protocolConfig.fetch(configPda);
+      const invoice = invoicePda(config.invoiceCount);
+      await program.methods
+        .createInvoice(description, new anchor.BN(amount), new anchor.BN(deadline))
+        .accounts({
+          config: configPda,
+          invoice,
+          creator: authority.publicKey,
+          mint: sourMint,
+          allowedMint: null,
+          worker: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+      return invoice;
+    };
+
+    // An unfunded invoice: only the worker may revoke it before the deadline
+    const revoked = await createInvoice("Logo refresh");
+    try {
+      await program.methods
+        .revokeInvoice()
+        .accounts({ invoice: revoked, worker: worker.publicKey, signer: authority.publicKey })
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvoiceNotRevocable");
+    }
+    await program.methods
+      .revokeInvoice()
+      .accounts({ invoice: revoked, worker: worker.publicKey, signer: worker.publicKey })
+      .signers([worker])
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(revoked));
+
+    // A funded invoice becomes the next handshake, already accepted
+    const invoice = await createInvoice("Brand guidelines");
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    await program.methods
+      .fundInvoice()
+      .accounts({
+        config: configPda,
+        invoice,
+        handshake: handshakePda,
+        vault: vaultPda,
+        vaultAuthority: vaultAuthPda,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const handshake = await program.account.handshake.fetch(handshakePda);
+    assert.deepEqual(handshake.status, { accepted: {} });
+    assert.ok(handshake.worker.equals(worker.publicKey));
+    assert.equal(handshake.amount.toNumber(), amount);
+    assert.equal(handshake.description, "Brand guidelines");
+    const vault = await getAccount(provider.connection, vaultPda);
+    assert.equal(Number(vault.amount), amount);
+    assert.isNull(await provider.connection.getAccountInfo(invoice));
+
+    // The worker can deliver right away
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: handshakePda, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    console.log("    ✓ Invoice revoked; second invoice funded as an accepted handshake");
+  });
+
+  // =========================================================================
+  // Test 29: Team payout — worker share split by bps, remainder to the lead
+  // =========================================================================
+  it("splits a team handshake's payout across the payees", async () => {
+    const payer = (authority as any).payer;
+    const amount = 10_000_001;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const designer = anchor.web3.Keypair.generate();
+    const developer = anchor.web3.Keypair.generate();
+    const designerTokenAccount = await createAccount(
+      provider.connection,
+      payer,
+      sourMint,
+      designer.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+    const developerTokenAccount = await createAccount(
+      provider.connection,
+      payer,
+      sourMint,
+      developer.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+    // The worker leads the team and keeps the rounding remainder
+    const team = [
+      { wallet: designer.publicKey, shareBps: 3333 },
+      { wallet: developer.publicKey, shareBps: 3333 },
+      { wallet: worker.publicKey, shareBps: 3334 },
+    ];
+
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const idBytes = config.handshakeCount.toArrayLike(Buffer, "le", 8);
+    const [handshakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("handshake"), idBytes],
+      program.programId
+    );
+    const [teamPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("team"), idBytes],
+      program.programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), idBytes],
+      program.programId
+    );
+    const [vaultAuthPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_auth"), idBytes],
+      program.programId
+    );
+
+    const createTeamHandshake = (payees: typeof team) =>
+      program.methods
+        .createHandshake("Team test", new anchor.BN(amount), new anchor.BN(deadline), [], {
+          ...NO_OPTIONS,
+          team: payees,
+        })
+        .accounts({
+          config: configPda,
+          handshake: handshakePda,
+          milestones: null,
+          bountyAllowlist: null,
+          payoutStream: null,
+          streamVault: null,
+          team: teamPda,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthPda,
+          creatorTokenAccount,
+          worker: worker.publicKey,
+          mint: sourMint,
+          allowedMint: null,
+          creator: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+    // Shares must add up to 100%
+    try {
+      await createTeamHandshake([{ ...team[0], shareBps: 5000 }, team[1]]);
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidTeam");
+    }
+    await createTeamHandshake(team);
+
+    // The lead accepts for the team
+    await program.methods
+      .acceptHandshake()
+      .accounts({
+        handshake: handshakePda,
+        workerRecord: null,
+        bountyAllowlist: null,
+        collateralVault: null,
+        workerTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: handshakePda, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    // Payee token accounts lead the remaining accounts, in payee order
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    await program.methods
+      .approve(null)
+      .accounts({
+        config: configPda,
+        handshake: handshakePda,
+        milestones: null,
+        allowedMint: null,
+        vault: vaultPda,
+        mint: sourMint,
+        vaultAuthority: vaultAuthPda,
+        workerTokenAccount,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: null,
+        payoutStream: null,
+        streamVault: null,
+        team: teamPda,
+        subcontract: null,
+        subcontractVault: null,
+        creatorTokenAccount: null,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        [designerTokenAccount, developerTokenAccount, workerTokenAccount].map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .rpc();
+
+    const toWorker = amount - Math.floor(amount * PINCH_BPS / 10_000);
+    const designerShare = Math.floor(toWorker * 3333 / 10_000);
+    const designerAfter = await getAccount(provider.connection, designerTokenAccount);
+    const developerAfter = await getAccount(provider.connection, developerTokenAccount);
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(Number(designerAfter.amount), designerShare);
+    assert.equal(Number(developerAfter.amount), designerShare);
+    assert.equal(
+      Number(workerAfter.amount) - Number(workerBefore.amount),
+      toWorker - 2 * designerShare
+    );
+
+    console.log(`    ✓ ${toWorker} split across the team; lead kept the remainder`);
+  });
+
+  // =========================================================================
+  // Test 30: Subcontract — child funded by a lien on the parent's payout
+  // =========================================================================
+  it("funds a subcontract from the parent's worker payout on approval", async () => {
+    const payer = (authority as any).payer;
+    const amount = 20_000_000;
+    const lien = 5_000_000;
+    const deadline = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const subcontractor = anchor.web3.Keypair.generate();
+    const subcontractorTokenAccount = await createAccount(
+      provider.connection,
+      payer,
+      sourMint,
+      subcontractor.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+    const pdas = (id: anchor.BN) => {
+      const idBytes = id.toArrayLike(Buffer, "le", 8);
+      const [handshake, vault, vaultAuth] = ["handshake", "vault", "vault_auth"].map(
+        (seed) =>
+          anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from(seed), idBytes],
+            program.programId
+          )[0]
+      );
+      return { handshake, vault, vaultAuth };
+    };
+
+    let config = await program.account.protocolConfig.fetch(configPda);
+    const parent = pdas(config.handshakeCount);
+    await program.methods
+      .createHandshake("Website build", new anchor.BN(amount), new anchor.BN(deadline), [], NO_OPTIONS)
+      .accounts({
+        config: configPda,
+        handshake: parent.handshake,
+        milestones: null,
+        bountyAllowlist: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        vault: parent.vault,
+        vaultAuthority: parent.vaultAuth,
+        creatorTokenAccount,
+        worker: worker.publicKey,
+        mint: sourMint,
+        allowedMint: null,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const accept = (handshake: typeof parent, signer: anchor.web3.Keypair) =>
+      program.methods
+        .acceptHandshake()
+        .accounts({
+          handshake: handshake.handshake,
+          workerRecord: null,
+          bountyAllowlist: null,
+          collateralVault: null,
+          workerTokenAccount: null,
+          mint: sourMint,
+          vaultAuthority: handshake.vaultAuth,
+          worker: signer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    const deliver = (handshake: typeof parent, signer: anchor.web3.Keypair) =>
+      program.methods
+        .deliver(DELIVERY_HASH, null)
+        .accounts({ handshake: handshake.handshake, milestones: null, worker: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    await accept(parent, worker);
+
+    // The worker carves out part of the payout — never more than it
+    config = await program.account.protocolConfig.fetch(configPda);
+    const child = pdas(config.handshakeCount);
+    const subcontract = (lienAmount: number, to = subcontractor.publicKey) =>
+      program.methods
+        .subcontract("Landing page copy", new anchor.BN(lienAmount), new anchor.BN(deadline - 3600))
+        .accounts({
+          config: configPda,
+          parent: parent.handshake,
+          allowedMint: null,
+          handshake: child.handshake,
+          vault: child.vault,
+          vaultAuthority: child.vaultAuth,
+          subcontractor: to,
+          mint: sourMint,
+          worker: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([worker])
+        .rpc();
+    try {
+      await subcontract(amount);
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidSubcontract");
+    }
+    // ...and never as an open bounty anyone could take
+    try {
+      await subcontract(lien, anchor.web3.PublicKey.default);
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "InvalidSubcontract");
+    }
+    await subcontract(lien);
+
+    const parentAccount = await program.account.handshake.fetch(parent.handshake);
+    let childAccount = await program.account.handshake.fetch(child.handshake);
+    assert.ok(parentAccount.childId.eq(childAccount.id));
+    assert.ok(childAccount.parentId.eq(parentAccount.id));
+    assert.equal(parentAccount.lien.toNumber(), lien);
+    assert.ok(childAccount.creator.equals(worker.publicKey));
+
+    const approveAccounts = (
+      handshake: typeof parent,
+      creator: anchor.web3.PublicKey,
+      workerWallet: anchor.web3.PublicKey,
+      workerAccount: anchor.web3.PublicKey,
+      subcontract: typeof parent | null
+    ) => ({
+      config: configPda,
+      handshake: handshake.handshake,
+      milestones: null,
+      allowedMint: null,
+      vault: handshake.vault,
+      mint: sourMint,
+      vaultAuthority: handshake.vaultAuth,
+      workerTokenAccount: workerAccount,
+      workerRecord: workerRecordOf(workerWallet),
+      collateralVault: null,
+      payoutStream: null,
+      streamVault: null,
+      team: null,
+      subcontract: subcontract?.handshake ?? null,
+      subcontractVault: subcontract?.vault ?? null,
+      creatorTokenAccount: null,
+      keepersPool,
+      commonsTreasury,
+      buybackTreasury,
+      creator,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+
+    // The subcontractor works on the child, but it cannot pay out yet
+    await accept(child, subcontractor);
+    await deliver(child, subcontractor);
+    try {
+      await program.methods
+        .approve(null)
+        .accounts(approveAccounts(child, worker.publicKey, subcontractor.publicKey, subcontractorTokenAccount, null))
+        .signers([worker])
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "LienUnpaid");
+    }
+
+    // Approving the parent pays the lien into the child's vault first
+    await deliver(parent, worker);
+    const workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    await program.methods
+      .approve(null)
+      .accounts(approveAccounts(parent, authority.publicKey, worker.publicKey, workerTokenAccount, child))
+      .rpc();
+    const workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    const toWorker = amount - Math.floor(amount * PINCH_BPS / 10_000);
+    assert.equal(Number(workerAfter.amount) - Number(workerBefore.amount), toWorker - lien);
+    const childVault = await getAccount(provider.connection, child.vault);
+    assert.equal(Number(childVault.amount), lien);
+    childAccount = await program.account.handshake.fetch(child.handshake);
+    assert.equal(childAccount.lien.toNumber(), 0);
+
+    // Now the worker can approve the subcontractor's delivery
+    await program.methods
+      .approve(null)
+      .accounts(approveAccounts(child, worker.publicKey, subcontractor.publicKey, subcontractorTokenAccount, null))
+      .signers([worker])
+      .rpc();
+    const subcontractorAfter = await getAccount(provider.connection, subcontractorTokenAccount);
+    assert.equal(Number(subcontractorAfter.amount), lien - Math.floor(lien * PINCH_BPS / 10_000));
+
+    console.log(`    ✓ Lien of ${lien} paid from the parent; subcontract approved`);
+  });
+
+  // =========================================================================
+  // Test 31: Subcontract liens — paid or released on every exit of the parent
+  // =========================================================================
+  it("pays or releases a subcontract lien on every exit of the parent", async () => {
+    const amount = 20_000_000;
+    const lien = 5_000_000;
+    const subcontractor = anchor.web3.Keypair.generate();
+    const pdas = (id: anchor.BN) => {
+      const idBytes = id.toArrayLike(Buffer, "le", 8);
+      const [handshake, vault, vaultAuth, proposal, amendment, evidenceLog, bondVault, disputeCase] = [
+        "handshake",
+        "vault",
+        "vault_auth",
+        "cancel_proposal",
+        "amendment",
+        "evidence",
+        "bond_vault",
+        "dispute_case",
+      ].map(
+        (seed) =>
+          anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from(seed), idBytes],
+            program.programId
+          )[0]
+      );
+      return { handshake, vault, vaultAuth, proposal, amendment, evidenceLog, bondVault, disputeCase };
+    };
+
+    // An accepted parent with `lien` carved out of it into an open child
+    const openWithLien = async (description: string, deadline: number) => {
+      let config = await program.account.protocolConfig.fetch(configPda);
+      const parent = pdas(config.handshakeCount);
+      await program.methods
+        .createHandshake(description, new anchor.BN(amount), new anchor.BN(deadline), [], NO_OPTIONS)
+        .accounts({
+          config: configPda,
+          handshake: parent.handshake,
+          milestones: null,
+          bountyAllowlist: null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
+          vault: parent.vault,
+          vaultAuthority: parent.vaultAuth,
+          creatorTokenAccount,
+          worker: worker.publicKey,
+          mint: sourMint,
+          allowedMint: null,
+          creator: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+      await program.methods
+        .acceptHandshake()
+        .accounts({
+          handshake: parent.handshake,
+          workerRecord: null,
+          bountyAllowlist: null,
+          collateralVault: null,
+          workerTokenAccount: null,
+          mint: sourMint,
+          vaultAuthority: parent.vaultAuth,
+          worker: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+
+      config = await program.account.protocolConfig.fetch(configPda);
+      const child = pdas(config.handshakeCount);
+      await program.methods
+        .subcontract(`${description} (subcontract)`, new anchor.BN(lien), new anchor.BN(deadline - 1))
+        .accounts({
+          config: configPda,
+          parent: parent.handshake,
+          allowedMint: null,
+          handshake: child.handshake,
+          vault: child.vault,
+          vaultAuthority: child.vaultAuth,
+          subcontractor: subcontractor.publicKey,
+          mint: sourMint,
+          worker: worker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([worker])
+        .rpc();
+      return { parent, child };
+    };
+
+    const assertReleased = async (parent: ReturnType<typeof pdas>, child: ReturnType<typeof pdas>) => {
+      const parentAccount = await program.account.handshake.fetch(parent.handshake);
+      const childAccount = await program.account.handshake.fetch(child.handshake);
+      assert.equal(parentAccount.lien.toNumber(), 0);
+      assert.equal(childAccount.lien.toNumber(), 0);
+      assert.equal(childAccount.amount.toNumber(), 0);
+      assert.deepEqual(childAccount.status, { cancelled: {} });
+      const childVault = await getAccount(provider.connection, child.vault);
+      assert.equal(Number(childVault.amount), 0);
+    };
+
+    const week = Math.floor(Date.now() / 1000) + 7 * 24 * 60 * 60;
+    const toWorker = (gross: number) => gross - Math.floor(gross * PINCH_BPS / 10_000);
+
+    // Expiry: opened first with a short deadline, cranked at the end
+    const expiring = await openWithLien("Lien expiry", Math.floor(Date.now() / 1000) + 6);
+
+    // Amendment: the escrow cannot be cut below the lien plus fees
+    const amended = await openWithLien("Lien amendment", week);
+    await program.methods
+      .proposeAmendment(null, new anchor.BN(lien))
+      .accounts({
+        handshake: amended.parent.handshake,
+        amendment: amended.parent.amendment,
+        creatorTokenAccount: null,
+        mint: sourMint,
+        vaultAuthority: amended.parent.vaultAuth,
+        proposer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+    try {
+      await program.methods
+        .acceptAmendment()
+        .accounts({
+          config: configPda,
+          handshake: amended.parent.handshake,
+          amendment: amended.parent.amendment,
+          milestones: null,
+          allowedMint: null,
+          vault: amended.parent.vault,
+          mint: sourMint,
+          vaultAuthority: amended.parent.vaultAuth,
+          creatorTokenAccount,
+          proposer: worker.publicKey,
+          signer: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (err: any) {
+      assert.include(err.toString(), "LienUncovered");
+    }
+
+    // Mutual cancel: a worker share that covers the lien pays it in
+    const WORKER_SHARE_BPS = 5000;
+    await program.methods
+      .proposeMutualCancel(WORKER_SHARE_BPS)
+      .accounts({
+        handshake: amended.parent.handshake,
+        cancelProposal: amended.parent.proposal,
+        proposer: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+    let workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    await program.methods
+      .acceptMutualCancel()
+      .accounts({
+        config: configPda,
+        handshake: amended.parent.handshake,
+        cancelProposal: amended.parent.proposal,
+        allowedMint: null,
+        vault: amended.parent.vault,
+        mint: sourMint,
+        vaultAuthority: amended.parent.vaultAuth,
+        collateralVault: null,
+        subcontract: amended.child.handshake,
+        subcontractVault: amended.child.vault,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        proposer: worker.publicKey,
+        signer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    let workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    const cancelShare = toWorker(Math.floor(amount * WORKER_SHARE_BPS / 10_000));
+    assert.equal(Number(workerAfter.amount) - Number(workerBefore.amount), cancelShare - lien);
+    const fundedChild = await program.account.handshake.fetch(amended.child.handshake);
+    assert.equal(fundedChild.amount.toNumber(), lien);
+    assert.equal(fundedChild.lien.toNumber(), 0);
+    assert.equal(Number((await getAccount(provider.connection, amended.child.vault)).amount), lien);
+
+    // Ruling: a refund leaves no worker share, so the lien is released
+    const disputed = await openWithLien("Lien ruling", week);
+    await program.methods
+      .dispute()
+      .accounts({
+        config: configPda,
+        handshake: disputed.parent.handshake,
+        allowedMint: null,
+        evidenceLog: disputed.parent.evidenceLog,
+        bondVault: disputed.parent.bondVault,
+        mint: sourMint,
+        vaultAuthority: disputed.parent.vaultAuth,
+        signerTokenAccount: null,
+        signer: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+    const creatorBefore = await getAccount(provider.connection, creatorTokenAccount);
+    await program.methods
+      .resolveDispute({ refund: {} })
+      .accounts({
+        config: configPda,
+        handshake: disputed.parent.handshake,
+        disputeCase: disputed.parent.disputeCase,
+        registry: null,
+        allowedMint: null,
+        vault: disputed.parent.vault,
+        bondVault: disputed.parent.bondVault,
+        mint: sourMint,
+        vaultAuthority: disputed.parent.vaultAuth,
+        collateralVault: null,
+        subcontract: disputed.child.handshake,
+        subcontractVault: disputed.child.vault,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        team: null,
+        creatorTokenAccount,
+        workerTokenAccount,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        arbiterTokenAccount: null,
+        resolver: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const creatorAfter = await getAccount(provider.connection, creatorTokenAccount);
+    assert.equal(Number(creatorAfter.amount) - Number(creatorBefore.amount), amount);
+    await assertReleased(disputed.parent, disputed.child);
+
+    // Withdrawal: the worker walks away, so does the lien
+    const withdrawn = await openWithLien("Lien withdrawal", week);
+    await program.methods
+      .withdrawFromHandshake()
+      .accounts({
+        config: configPda,
+        handshake: withdrawn.parent.handshake,
+        workerRecord: workerRecordOf(worker.publicKey),
+        allowedMint: null,
+        vault: withdrawn.parent.vault,
+        mint: sourMint,
+        vaultAuthority: withdrawn.parent.vaultAuth,
+        creatorTokenAccount,
+        collateralVault: null,
+        subcontract: withdrawn.child.handshake,
+        commonsTreasury: null,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        worker: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+    await assertReleased(withdrawn.parent, withdrawn.child);
+
+    // Approval after the child is cancelled: the lien goes back to the worker
+    const abandoned = await openWithLien("Lien abandoned", week);
+    await program.methods
+      .cancel()
+      .accounts({
+        config: configPda,
+        handshake: abandoned.child.handshake,
+        vault: abandoned.child.vault,
+        mint: sourMint,
+        vaultAuthority: abandoned.child.vaultAuth,
+        creatorTokenAccount: workerTokenAccount,
+        payoutStream: null,
+        streamVault: null,
+        creator: worker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([worker])
+      .rpc();
+    await program.methods
+      .deliver(DELIVERY_HASH, null)
+      .accounts({ handshake: abandoned.parent.handshake, milestones: null, worker: worker.publicKey })
+      .signers([worker])
+      .rpc();
+    workerBefore = await getAccount(provider.connection, workerTokenAccount);
+    await program.methods
+      .approve(null)
+      .accounts({
+        config: configPda,
+        handshake: abandoned.parent.handshake,
+        milestones: null,
+        allowedMint: null,
+        vault: abandoned.parent.vault,
+        mint: sourMint,
+        vaultAuthority: abandoned.parent.vaultAuth,
+        workerTokenAccount,
+        workerRecord: workerRecordOf(worker.publicKey),
+        collateralVault: null,
+        payoutStream: null,
+        streamVault: null,
+        team: null,
+        subcontract: abandoned.child.handshake,
+        subcontractVault: abandoned.child.vault,
+        creatorTokenAccount: null,
+        keepersPool,
+        commonsTreasury,
+        buybackTreasury,
+        creator: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    workerAfter = await getAccount(provider.connection, workerTokenAccount);
+    assert.equal(Number(workerAfter.amount) - Number(workerBefore.amount), toWorker(amount));
+    await assertReleased(abandoned.parent, abandoned.child);
+
+    // Expiry: nothing reached the worker, so the lien is released
+    const expiringParent = await program.account.handshake.fetch(expiring.parent.handshake);
+    const wait = expiringParent.deadlineTs.toNumber() * 1000 - Date.now() + 2000;
+    if (wait > 0) {
+      await new Promise((resolve) => setTimeout(resolve, wait));
+    }
+    await program.methods
+      .expireHandshake()
+      .accounts({
+        config: configPda,
+        handshake: expiring.parent.handshake,
+        milestones: null,
+        allowedMint: null,
+        vault: expiring.parent.vault,
+        mint: sourMint,
+        vaultAuthority: expiring.parent.vaultAuth,
+        collateralVault: null,
+        subcontract: expiring.child.handshake,
+        commonsTreasury: null,
+        creatorTokenAccount,
+        payoutStream: null,
+        streamVault: null,
+        creator: authority.publicKey,
+        crankerTokenAccount: null,
+        cranker: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    await assertReleased(expiring.parent, expiring.child);
+
+    console.log("    ✓ Lien paid on a covering mutual cancel; released on ruling, withdrawal, abandonment and expiry");
+  });
+
+  // =========================================================================
+  // Test 32: Validation — cannot self-handshake
   // =========================================================================
   it("rejects self-handshake (creator = worker)", async () => {
     const config = await program.account.protocolConfig.fetch(configPda);
@@ -557,15 +4839,21 @@ describe("sour-handshake", () => {
 
     try {
       await program.methods
-        .createHandshake("Self deal", new anchor.BN(100), new anchor.BN(deadline))
+        .createHandshake("Self deal", new anchor.BN(100), new anchor.BN(deadline), [], NO_OPTIONS)
         .accounts({
           config: configPda,
           handshake: handshakePda,
+          milestones: null,
+          bountyAllowlist: null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
           vault: vaultPda,
           vaultAuthority: vaultAuthPda,
           creatorTokenAccount,
           worker: authority.publicKey, // same as creator!
-          sourMint,
+          mint: sourMint,
+          allowedMint: null,
           creator: authority.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -580,7 +4868,7 @@ describe("sour-handshake", () => {
   });
 
   // =========================================================================
-  // Test 9: Validation — zero amount rejected
+  // Test 33: Validation — zero amount rejected
   // =========================================================================
   it("rejects zero escrow amount", async () => {
     const config = await program.account.protocolConfig.fetch(configPda);
@@ -604,15 +4892,21 @@ describe("sour-handshake", () => {
 
     try {
       await program.methods
-        .createHandshake("Zero amount", new anchor.BN(0), new anchor.BN(deadline))
+        .createHandshake("Zero amount", new anchor.BN(0), new anchor.BN(deadline), [], NO_OPTIONS)
         .accounts({
           config: configPda,
           handshake: handshakePda,
+          milestones: null,
+          bountyAllowlist: null,
+          payoutStream: null,
+          streamVault: null,
+          team: null,
           vault: vaultPda,
           vaultAuthority: vaultAuthPda,
           creatorTokenAccount,
           worker: worker.publicKey,
-          sourMint,
+          mint: sourMint,
+          allowedMint: null,
           creator: authority.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,